@@ -0,0 +1,276 @@
+//! Sending SP-API `/batches/...` operations, which wrap many `{uri, method, body, headers}`
+//! sub-requests in one call and answer each with its own status.
+use std::collections::HashMap;
+use std::time::Duration;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{json, Map, Value};
+use crate::error_handling::Errors;
+use crate::general::{parse_response, Client};
+
+/// A batch endpoint, its maximum number of sub-requests per call and its rate limit.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchEndpoint {
+    pub url: &'static str,
+    pub max_requests: usize,
+    /// The minimum time between two calls, i.e. `1 / rate`.
+    pub interval: Duration,
+}
+
+fn serialize_method<S: Serializer>(method: &Method, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(method.as_str())
+}
+
+/// One sub-request of a batch, as sent to Amazon.
+///
+/// Operation parameters such as `marketplaceId` are sent next to `uri` and `method`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchRequest {
+    pub uri: String,
+    #[serde(serialize_with = "serialize_method")]
+    pub method: Method,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    #[serde(flatten)]
+    pub params: Map<String, Value>,
+}
+impl BatchRequest {
+    pub fn new(method: Method, uri: &str) -> Self {
+        BatchRequest { uri: uri.to_string(), method, body: None, headers: HashMap::new(), params: Map::new() }
+    }
+    pub fn param(mut self, name: &str, value: impl Serialize) -> Self {
+        self.params.insert(name.to_string(), json!(value));
+        self
+    }
+    pub fn body(mut self, body: Value) -> Self {
+        self.body = Some(body);
+        self
+    }
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HttpStatusLine {
+    pub status_code: Option<u16>,
+    pub reason_phrase: Option<String>,
+}
+
+/// One sub-response of a batch, as returned by Amazon.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BatchResponse {
+    pub status: HttpStatusLine,
+    pub headers: HashMap<String, String>,
+    pub body: Value,
+    /// The parameters of the sub-request, echoed back by some operations.
+    pub request: Value,
+}
+impl BatchResponse {
+    pub fn is_success(&self) -> bool {
+        self.status.status_code.is_some_and(|s| (200..300).contains(&s))
+    }
+
+    /// The typed body of a successful sub-response, or its status and errors.
+    pub fn into_result<T: DeserializeOwned>(self) -> Result<T, Errors> {
+        let status = self.status.status_code.unwrap_or_default();
+        if self.is_success() {
+            return Ok(serde_json::from_value(self.body)?);
+        }
+        let message = match self.body.get("errors") {
+            Some(errors) => errors.to_string(),
+            None => self.status.reason_phrase.unwrap_or_default(),
+        };
+        Err(Errors::ApiError(status, message))
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct BatchResponses {
+    responses: Vec<BatchResponse>,
+}
+
+/// A typed sub-request of a batch operation.
+pub trait SubRequest {
+    type Response: DeserializeOwned;
+
+    fn to_batch_request(&self) -> BatchRequest;
+
+    /// Whether `response` answers this request, or `None` when the response doesn't tell. Responses
+    /// that don't tell are matched by their position in the batch.
+    fn matches(&self, _response: &BatchResponse) -> Option<bool> {
+        None
+    }
+}
+
+/// A sub-request together with the sub-response matched to it.
+#[derive(Debug)]
+pub struct BatchResult<Req, Res> {
+    pub request: Req,
+    pub headers: HashMap<String, String>,
+    pub result: Result<Res, Errors>,
+}
+impl<Req, Res> BatchResult<Req, Res> {
+    pub fn map_request<R>(self, f: impl FnOnce(Req) -> R) -> BatchResult<R, Res> {
+        BatchResult { request: f(self.request), headers: self.headers, result: self.result }
+    }
+}
+
+/// Every request of a batch operation with the sub-response matched to it, plus the
+/// sub-responses that could not be matched to any request.
+#[derive(Debug)]
+pub struct BatchResults<Req, Res> {
+    /// In the order of the requests. A request that Amazon did not answer gets an
+    /// `Errors::CustomError`.
+    pub results: Vec<BatchResult<Req, Res>>,
+    /// Sub-responses that answer a request that already has one, or no request of their batch.
+    pub unmatched: Vec<BatchResponse>,
+}
+impl<Req, Res> BatchResults<Req, Res> {
+    pub fn map_request<R>(self, mut f: impl FnMut(Req) -> R) -> BatchResults<R, Res> {
+        BatchResults { results: self.results.into_iter().map(|r| r.map_request(&mut f)).collect(), unmatched: self.unmatched }
+    }
+}
+
+/// Sends `requests` to `endpoint` in chunks of at most `max_requests`, spaced by the endpoint's
+/// rate limit, and returns every request with the sub-response matched to it, in the order of
+/// `requests`.
+pub async fn send<R: SubRequest>(
+    client: &mut Client,
+    endpoint: &BatchEndpoint,
+    requests: Vec<R>,
+) -> Result<BatchResults<R, R::Response>, Errors> {
+    let mut results = BatchResults { results: Vec::with_capacity(requests.len()), unmatched: Vec::new() };
+    let mut requests = requests.into_iter().peekable();
+    let mut first = true;
+    while requests.peek().is_some() {
+        if !first {
+            tokio::time::sleep(endpoint.interval).await;
+        }
+        first = false;
+
+        let chunk = requests.by_ref().take(endpoint.max_requests.max(1)).collect::<Vec<R>>();
+        let body = json!({ "requests": chunk.iter().map(|r| r.to_batch_request()).collect::<Vec<BatchRequest>>() });
+        let batch: BatchResponses = parse_response(
+            client
+                .make_request_w_body(endpoint.url, Method::POST, None::<Vec<(String, String)>>, body.to_string())
+                .await?,
+        )
+        .await?;
+        let (matched, unmatched) = match_responses(chunk, batch.responses);
+        results.results.extend(matched);
+        results.unmatched.extend(unmatched);
+    }
+    Ok(results)
+}
+
+/// Matches each response to the first free request it answers or, when it doesn't tell, to the
+/// request at its position. Returns the responses that match no free request separately.
+fn match_responses<R: SubRequest>(chunk: Vec<R>, responses: Vec<BatchResponse>) -> (Vec<BatchResult<R, R::Response>>, Vec<BatchResponse>) {
+    let mut matched: Vec<Option<BatchResponse>> = chunk.iter().map(|_| None).collect();
+    let mut unmatched = Vec::new();
+    for (position, response) in responses.into_iter().enumerate() {
+        let is_free = |i: usize| matched[i].is_none();
+        let index = (0..chunk.len())
+            .find(|&i| is_free(i) && chunk[i].matches(&response) == Some(true))
+            .or_else(|| Some(position).filter(|&i| i < chunk.len() && is_free(i) && chunk[i].matches(&response).is_none()));
+        match index {
+            Some(i) => matched[i] = Some(response),
+            None => unmatched.push(response),
+        }
+    }
+
+    let results = chunk
+        .into_iter()
+        .zip(matched)
+        .map(|(request, response)| match response {
+            Some(response) => BatchResult { request, headers: response.headers.clone(), result: response.into_result() },
+            None => BatchResult {
+                request,
+                headers: HashMap::new(),
+                result: Err(Errors::CustomError("The batch response has no response for this request".to_string())),
+            },
+        })
+        .collect();
+    (results, unmatched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A request for `id`. Its responses only tell which request they answer when `echoed` is set.
+    struct Lookup {
+        id: &'static str,
+        echoed: bool,
+    }
+    impl SubRequest for Lookup {
+        type Response = Value;
+
+        fn to_batch_request(&self) -> BatchRequest {
+            BatchRequest::new(Method::GET, "/lookup").param("id", self.id)
+        }
+        fn matches(&self, response: &BatchResponse) -> Option<bool> {
+            if !self.echoed {
+                return None;
+            }
+            response.request.get("id").and_then(Value::as_str).map(|id| id == self.id)
+        }
+    }
+
+    fn lookups(ids: &[&'static str], echoed: bool) -> Vec<Lookup> {
+        ids.iter().map(|&id| Lookup { id, echoed }).collect()
+    }
+
+    fn response(id: Option<&str>, value: u32) -> BatchResponse {
+        BatchResponse {
+            status: HttpStatusLine { status_code: Some(200), reason_phrase: None },
+            body: json!({ "value": value }),
+            request: id.map(|id| json!({ "id": id })).unwrap_or(Value::Null),
+            ..Default::default()
+        }
+    }
+
+    fn values(results: &[BatchResult<Lookup, Value>]) -> Vec<(&str, Option<u64>)> {
+        results.iter().map(|r| (r.request.id, r.result.as_ref().ok().and_then(|v| v["value"].as_u64()))).collect()
+    }
+
+    #[test]
+    fn in_order_responses_are_matched_by_position() {
+        let (results, unmatched) = match_responses(lookups(&["a", "b"], false), vec![response(None, 1), response(None, 2)]);
+        assert_eq!(values(&results), vec![("a", Some(1)), ("b", Some(2))]);
+        assert!(unmatched.is_empty());
+    }
+
+    #[test]
+    fn out_of_order_responses_are_matched_by_request() {
+        let responses = vec![response(Some("c"), 3), response(Some("a"), 1), response(Some("b"), 2)];
+        let (results, unmatched) = match_responses(lookups(&["a", "b", "c"], true), responses);
+        assert_eq!(values(&results), vec![("a", Some(1)), ("b", Some(2)), ("c", Some(3))]);
+        assert!(unmatched.is_empty());
+    }
+
+    #[test]
+    fn duplicate_responses_are_returned_as_unmatched() {
+        let responses = vec![response(Some("a"), 1), response(Some("a"), 2)];
+        let (results, unmatched) = match_responses(lookups(&["a", "b"], true), responses);
+        assert_eq!(values(&results), vec![("a", Some(1)), ("b", None)]);
+        assert!(matches!(results[1].result, Err(Errors::CustomError(_))));
+        assert_eq!(unmatched.len(), 1);
+        assert_eq!(unmatched[0].body["value"], 2);
+    }
+
+    #[test]
+    fn responses_for_unknown_requests_are_returned_as_unmatched() {
+        let (results, unmatched) = match_responses(lookups(&["a"], true), vec![response(Some("z"), 9), response(None, 1)]);
+        assert_eq!(values(&results), vec![("a", None)]);
+        assert_eq!(unmatched.len(), 2);
+    }
+}
@@ -10,6 +10,22 @@ pub enum Errors {
     ParseError(#[from] ParseError),
     #[error(transparent)]
     SerdeError(#[from] serde_json::Error),
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error(transparent)]
+    XmlError(#[from] quick_xml::DeError),
+    #[error(transparent)]
+    CsvError(#[from] csv::Error),
+    #[error(transparent)]
+    Base64Error(#[from] base64::DecodeError),
     #[error("Error: {0}")]
     CustomError(String),
+    #[error("Request failed with status {0}: {1}")]
+    ApiError(u16, String),
+    #[error("Operation failed: {0}")]
+    OperationFailed(String),
+    #[error("Operation timed out")]
+    Timeout,
+    #[error("Operation cancelled")]
+    Cancelled,
 }
\ No newline at end of file
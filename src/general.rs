@@ -1,6 +1,9 @@
 use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::io::Read;
 use crate::error_handling::Errors;
 use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde_json::json;
 use std::time::{ Instant};
@@ -8,7 +11,11 @@ use reqwest::{ Response, Url};
 const ENDPOINT_NA: &str = "https://sellingpartnerapi-na.amazon.com";
 const ENDPOINT_EU: &str = "https://sellingpartnerapi-eu.amazon.com";
 const ENDPOINT_FE: &str = "https://sellingpartnerapi-fe.amazon.com";
+const SANDBOX_ENDPOINT_NA: &str = "https://sandbox.sellingpartnerapi-na.amazon.com";
+const SANDBOX_ENDPOINT_EU: &str = "https://sandbox.sellingpartnerapi-eu.amazon.com";
+const SANDBOX_ENDPOINT_FE: &str = "https://sandbox.sellingpartnerapi-fe.amazon.com";
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CountryMarketplace {
     Canada,
     UnitedStates,
@@ -45,10 +52,27 @@ pub struct ClientInformation {
 struct AccessToken {
     access_token: String,
     expires_in: i64,
+    /// Not returned for grantless tokens.
+    #[serde(default)]
     refresh_token: String,
     token_type: String,
 }
 
+/// The scope of a grantless token, for operations that are called without seller authorization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GrantlessScope {
+    Notifications,
+    ClientCredentialRotation,
+}
+impl GrantlessScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GrantlessScope::Notifications => "sellingpartnerapi::notifications",
+            GrantlessScope::ClientCredentialRotation => "sellingpartnerapi::client_credential:rotation",
+        }
+    }
+}
+
 impl ClientInformation {
     async fn get_access_token(&self, x: &reqwest::Client) -> Result<AccessToken, Errors> {
         let ff = json!({
@@ -57,6 +81,23 @@ impl ClientInformation {
         "client_secret":self.client_secret,
         "grant_type": "refresh_token"});
 
+        Ok(x.request(
+            reqwest::Method::POST,
+            "https://api.amazon.com/auth/o2/token",
+        )
+        .body(ff.to_string())
+        .send()
+        .await?
+        .json::<AccessToken>()
+        .await?)
+    }
+    async fn get_grantless_token(&self, x: &reqwest::Client, scope: GrantlessScope) -> Result<AccessToken, Errors> {
+        let ff = json!({
+        "scope": scope.as_str(),
+        "client_id": self.client_id,
+        "client_secret":self.client_secret,
+        "grant_type": "client_credentials"});
+
         Ok(x.request(
             reqwest::Method::POST,
             "https://api.amazon.com/auth/o2/token",
@@ -97,6 +138,14 @@ impl CountryMarketplace {
             CountryMarketplace::Japan => ("A1VC38T7YXB528", ENDPOINT_FE),
         }
     }
+    /// Returns the sandbox endpoint of the region the country belongs to.
+    pub fn sandbox_endpoint(&self) -> &'static str {
+        match self.details().1 {
+            ENDPOINT_NA => SANDBOX_ENDPOINT_NA,
+            ENDPOINT_EU => SANDBOX_ENDPOINT_EU,
+            _ => SANDBOX_ENDPOINT_FE,
+        }
+    }
 }
 #[macro_export] macro_rules! enum_to_string {
     ($($type:ty)*) => {
@@ -109,15 +158,17 @@ pub struct Client {
     client_information: ClientInformation,
     last_refresh: Instant,
     reqwest_client: reqwest::Client,
+    grantless_tokens: HashMap<GrantlessScope, (AccessToken, Instant)>,
+    sandbox: bool,
 }
 impl Client {
     async fn refresh_token(&mut self) {
-        match self
+        if let Ok(o) = self
             .client_information
             .get_access_token(&self.reqwest_client).await
         {
-            Ok(o) => self.access_token = o,
-            Err(_) => {}
+            self.access_token = o;
+            self.last_refresh = Instant::now();
         }
     }
     pub async fn new(client: ClientInformation) -> Result<Self, Errors> {
@@ -128,10 +179,27 @@ impl Client {
                 client_information: client,
                 last_refresh: Instant::now(),
                 reqwest_client,
+                grantless_tokens: HashMap::new(),
+                sandbox: false,
             }),
             Err(e) => Err(e),
         }
     }
+    /// Sends every request to the sandbox endpoint of the region instead of production.
+    pub fn sandbox(mut self, sandbox: bool) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+    pub fn is_sandbox(&self) -> bool {
+        self.sandbox
+    }
+    fn endpoint(&self) -> &'static str {
+        if self.sandbox {
+            self.client_information.country_marketplace.sandbox_endpoint()
+        } else {
+            self.client_information.country_marketplace.details().1
+        }
+    }
     async fn check_validity(&mut self) {
         if Instant::now().duration_since(self.last_refresh).as_secs()
             > (self.access_token.expires_in - 10) as u64
@@ -139,9 +207,25 @@ impl Client {
             self.refresh_token().await;
         }
     }
+    /// Returns a valid grantless token for `scope`, requesting a new one when it is missing or
+    /// about to expire.
+    async fn grantless_token(&mut self, scope: GrantlessScope) -> Result<String, Errors> {
+        if let Some((token, fetched)) = self.grantless_tokens.get(&scope) {
+            if Instant::now().duration_since(*fetched).as_secs() + 10 < token.expires_in.max(0) as u64 {
+                return Ok(token.access_token.clone());
+            }
+        }
+        let token = self.client_information.get_grantless_token(&self.reqwest_client, scope).await?;
+        let access_token = token.access_token.clone();
+        self.grantless_tokens.insert(scope, (token, Instant::now()));
+        Ok(access_token)
+    }
     fn create_header(&mut self) -> HeaderMap {
+        Self::header_for_token(&self.access_token.access_token)
+    }
+    fn header_for_token(token: &str) -> HeaderMap {
         let mut header_map = HeaderMap::new();
-        header_map.insert("x-amz-access-token", self.access_token.access_token.parse().unwrap());
+        header_map.insert("x-amz-access-token", token.parse().unwrap());
         header_map.insert("CONTENT_TYPE", "application/json".parse().unwrap());
         header_map.insert("user-agent", "Amazon-SP-API-rs 0.1.0".parse().unwrap());
         header_map
@@ -154,13 +238,29 @@ impl Client {
         V: AsRef<str>,
     {
         //parameters.( ("marketplaceIds", self.client_information.country_marketplace.details().0));
+        self.check_validity().await;
         if let Some(params) = parameters {
-            Ok(self.reqwest_client.request(method, Url::parse_with_params(format!("{}{}", self.client_information.country_marketplace.details().1, path).as_str(), params)?).headers(self.create_header()).send().await?)
+            Ok(self.reqwest_client.request(method, Url::parse_with_params(format!("{}{}", self.endpoint(), path).as_str(), params)?).headers(self.create_header()).send().await?)
         }  else {
-            Ok(self.reqwest_client.request(method, Url::parse(format!("{}{}", self.client_information.country_marketplace.details().1, path).as_str())?).headers(self.create_header()).send().await?)
+            Ok(self.reqwest_client.request(method, Url::parse(format!("{}{}", self.endpoint(), path).as_str())?).headers(self.create_header()).send().await?)
 
         }
-        //Ok(self.reqwest_client.request(method, Url::parse_with_params(format!("{}{}",self.client_information.country_marketplace.details().1, path).as_str(), parameters)?).headers(self.create_header()).send().await?)
+        //Ok(self.reqwest_client.request(method, Url::parse_with_params(format!("{}{}",self.endpoint(), path).as_str(), parameters)?).headers(self.create_header()).send().await?)
+    }
+    /// Uploads document content to a pre-signed URL returned by an SP-API `create...Document`
+    /// operation. The URL already carries its own authorization, so no SP-API headers are sent.
+    pub async fn upload_document(&self, url: &str, content_type: &str, content: Vec<u8>) -> Result<(), Errors> {
+        let response = self.reqwest_client.put(url).header(reqwest::header::CONTENT_TYPE, content_type).body(content).send().await?;
+        check_response(response).await
+    }
+    /// Downloads document content from a pre-signed URL, e.g. a report or feed processing report.
+    pub async fn download_document(&self, url: &str) -> Result<Vec<u8>, Errors> {
+        let response = self.reqwest_client.get(url).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Errors::ApiError(status.as_u16(), response.text().await?));
+        }
+        Ok(response.bytes().await?.to_vec())
     }
     pub async fn make_request_w_body<I, K, V>(&mut self, path: &str, method: reqwest::Method, parameters: Option<I>, body: String) -> Result<Response, Errors>
     where
@@ -169,11 +269,108 @@ impl Client {
         K: AsRef<str>,
         V: AsRef<str>,
     {
+        self.check_validity().await;
         if let Some(param) = parameters {
-            Ok(self.reqwest_client.request(method, Url::parse_with_params(format!("{}{}",self.client_information.country_marketplace.details().1, path).as_str(), param)?).headers(self.create_header()).body(body).send().await?)
+            Ok(self.reqwest_client.request(method, Url::parse_with_params(format!("{}{}",self.endpoint(), path).as_str(), param)?).headers(self.create_header()).body(body).send().await?)
         } else {
-            Ok(self.reqwest_client.request(method, Url::parse(format!("{}{}",self.client_information.country_marketplace.details().1, path).as_str())?).headers(self.create_header()).body(body).send().await?)
+            Ok(self.reqwest_client.request(method, Url::parse(format!("{}{}",self.endpoint(), path).as_str())?).headers(self.create_header()).body(body).send().await?)
         }
     }
+    /// Like `make_request_w_body`, with extra request headers, e.g. `x-amzn-idempotency-token`.
+    pub async fn make_request_w_headers<I, K, V>(&mut self, path: &str, method: reqwest::Method, parameters: Option<I>, headers: &[(&str, &str)], body: Option<String>) -> Result<Response, Errors>
+    where
+        I: IntoIterator + std::fmt::Debug,
+        I::Item: Borrow<(K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.check_validity().await;
+        let base = format!("{}{}", self.endpoint(), path);
+        let url = match parameters {
+            Some(params) => Url::parse_with_params(&base, params)?,
+            None => Url::parse(&base)?,
+        };
+        let mut request = self.reqwest_client.request(method, url).headers(self.create_header());
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        Ok(request.send().await?)
+    }
+    /// Like `make_request_w_body`, for the operations that require a grantless token instead of
+    /// the seller's access token, e.g. the Notifications API destination operations.
+    pub async fn make_grantless_request<I, K, V>(&mut self, scope: GrantlessScope, path: &str, method: reqwest::Method, parameters: Option<I>, body: Option<String>) -> Result<Response, Errors>
+    where
+        I: IntoIterator + std::fmt::Debug,
+        I::Item: Borrow<(K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let token = self.grantless_token(scope).await?;
+        let base = format!("{}{}", self.endpoint(), path);
+        let url = match parameters {
+            Some(params) => Url::parse_with_params(&base, params)?,
+            None => Url::parse(&base)?,
+        };
+        let mut request = self.reqwest_client.request(method, url).headers(Self::header_for_token(&token));
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        Ok(request.send().await?)
+    }
+}
+
+/// Checks the status of an SP-API response and deserializes its body.
+///
+/// Non-success responses are returned as `Errors::ApiError` with the raw body, so the
+/// `errors` array Amazon sends back is not lost.
+pub async fn parse_response<T: DeserializeOwned>(response: Response) -> Result<T, Errors> {
+    let status = response.status();
+    let text = response.text().await?;
+    if !status.is_success() {
+        return Err(Errors::ApiError(status.as_u16(), text));
+    }
+    Ok(serde_json::from_str(&text)?)
+}
+
+#[derive(Deserialize)]
+struct PayloadResponse<T> {
+    payload: T,
+}
+
+/// Like `parse_response`, for the older (v0) operations that wrap their result in a `payload` field.
+pub async fn parse_payload<T: DeserializeOwned>(response: Response) -> Result<T, Errors> {
+    Ok(parse_response::<PayloadResponse<T>>(response).await?.payload)
+}
+
+/// Checks the status of an SP-API response that has no body worth reading, e.g. a `204` from a
+/// delete or cancel operation.
+pub async fn check_response(response: Response) -> Result<(), Errors> {
+    let status = response.status();
+    if !status.is_success() {
+        return Err(Errors::ApiError(status.as_u16(), response.text().await?));
+    }
+    Ok(())
+}
 
+/// Decompresses a GZIP document, as returned for documents with `compressionAlgorithm: GZIP`.
+pub fn gunzip(content: &[u8]) -> Result<Vec<u8>, Errors> {
+    let mut decoded = Vec::new();
+    flate2::read::GzDecoder::new(content).read_to_end(&mut decoded)?;
+    Ok(decoded)
+}
+
+/// Percent-encodes a value for use as a single path segment, e.g. a seller SKU that contains
+/// spaces or slashes.
+pub fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
 }
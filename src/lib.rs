@@ -1,5 +1,9 @@
+#![allow(clippy::too_many_arguments)]
 pub mod general;
+pub mod batch;
 pub mod error_handling;
 mod api;
 pub mod models;
+pub mod pagination;
+pub mod poller;
 //mod test;
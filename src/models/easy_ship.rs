@@ -0,0 +1,355 @@
+//! The v2022-03-23 Easy Ship API, for scheduling Amazon pickups of seller-fulfilled orders in
+//! India, Mexico and other Easy Ship marketplaces.
+use std::path::Path;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::error_handling::Errors;
+use crate::general::{parse_response, Client, CountryMarketplace};
+
+pub struct EasyShip;
+
+const BASE: &str = "/easyShip/2022-03-23";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnitOfLength {
+    #[serde(rename = "cm")]
+    Cm,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnitOfWeight {
+    #[serde(rename = "grams")]
+    Grams,
+    #[serde(rename = "g")]
+    G,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dimensions {
+    pub length: f64,
+    pub width: f64,
+    pub height: f64,
+    pub unit: UnitOfLength,
+}
+impl Dimensions {
+    pub fn cm(length: f64, width: f64, height: f64) -> Self {
+        Dimensions { length, width, height, unit: UnitOfLength::Cm }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Weight {
+    pub value: f64,
+    pub unit: UnitOfWeight,
+}
+impl Weight {
+    pub fn grams(value: f64) -> Self {
+        Weight { value, unit: UnitOfWeight::Grams }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HandoverMethod {
+    Pickup,
+    Dropoff,
+}
+
+/// A time window in which the package is handed over to the carrier.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeSlot {
+    pub slot_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handover_method: Option<HandoverMethod>,
+}
+impl TimeSlot {
+    pub fn new(slot_id: &str) -> Self {
+        TimeSlot { slot_id: slot_id.to_string(), start_time: None, end_time: None, handover_method: None }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListHandoverSlotsResponse {
+    pub amazon_order_id: String,
+    pub time_slots: Vec<TimeSlot>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageItem {
+    pub order_item_id: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order_item_serial_numbers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageDetails {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub package_items: Vec<PackageItem>,
+    pub package_time_slot: TimeSlot,
+    /// The seller's identifier of the package.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_identifier: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledPackageId {
+    pub amazon_order_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InvoiceData {
+    pub invoice_number: String,
+    pub invoice_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TrackingDetails {
+    pub tracking_id: Option<String>,
+}
+
+/// A scheduled Easy Ship package.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Package {
+    pub scheduled_package_id: ScheduledPackageId,
+    pub package_dimensions: Option<Dimensions>,
+    pub package_weight: Option<Weight>,
+    pub package_items: Vec<PackageItem>,
+    pub package_time_slot: TimeSlot,
+    pub package_identifier: Option<String>,
+    pub invoice: Option<InvoiceData>,
+    /// e.g. `ReadyForPickup`, `PickedUp`, `Delivered`, `Cancelled`.
+    pub package_status: Option<String>,
+    pub tracking_details: Option<TrackingDetails>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Packages {
+    pub packages: Vec<Package>,
+}
+
+/// The new time slot of a scheduled package.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePackageDetails {
+    pub scheduled_package_id: ScheduledPackageId,
+    pub package_time_slot: TimeSlot,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderScheduleDetails {
+    pub amazon_order_id: String,
+    /// The time slot and items; Amazon picks the earliest slot when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_details: Option<PackageDetails>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LabelFormat {
+    PDF,
+    ZPL,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Error {
+    pub code: String,
+    pub message: Option<String>,
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RejectedOrder {
+    pub amazon_order_id: String,
+    pub error: Option<Error>,
+}
+
+/// The outcome of `create_scheduled_package_bulk`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CreateScheduledPackagesResponse {
+    pub scheduled_packages: Vec<Package>,
+    pub rejected_orders: Vec<RejectedOrder>,
+    /// A pre-signed URL to a ZIP file with the labels and invoices of the scheduled packages.
+    pub printable_documents_url: Option<String>,
+}
+impl CreateScheduledPackagesResponse {
+    /// Downloads the ZIP file of labels and invoices to `path`.
+    pub async fn download_documents(&self, client: &Client, path: &Path) -> Result<(), Errors> {
+        let url = self
+            .printable_documents_url
+            .as_deref()
+            .ok_or_else(|| Errors::CustomError("No printable documents were returned".to_string()))?;
+        std::fs::write(path, client.download_document(url).await?)?;
+        Ok(())
+    }
+}
+
+impl EasyShip {
+    /// Returns time slots available for Easy Ship orders to be scheduled based on the package
+    /// weight and dimensions that the seller specifies.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    ///
+    /// # Parameters
+    /// - `amazon_order_id`: The order to get slots for.
+    /// - `marketplace`: The marketplace of the order.
+    /// - `package_dimensions` (optional): The package dimensions, which change the available slots.
+    /// - `package_weight` (optional): The package weight.
+    pub async fn list_handover_slots(
+        client: &mut Client,
+        amazon_order_id: &str,
+        marketplace: CountryMarketplace,
+        package_dimensions: Option<Dimensions>,
+        package_weight: Option<Weight>,
+    ) -> Result<ListHandoverSlotsResponse, Errors> {
+        let endpoint = format!("{}/timeSlot", BASE);
+        let mut body = json!({ "amazonOrderId": amazon_order_id, "marketplaceId": marketplace.details().0 });
+        if let Some(dimensions) = package_dimensions {
+            body["packageDimensions"] = json!(dimensions);
+        }
+        if let Some(weight) = package_weight {
+            body["packageWeight"] = json!(weight);
+        }
+
+        parse_response(client.make_request_w_body(&endpoint, Method::POST, None::<Vec<(String, String)>>, body.to_string()).await?).await
+    }
+
+    /// Returns information about a package, including dimensions, weight, time slot
+    /// information for handover, invoice and item information, and status.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    pub async fn get_scheduled_package(client: &mut Client, amazon_order_id: &str, marketplace: CountryMarketplace) -> Result<Package, Errors> {
+        let endpoint = format!("{}/package", BASE);
+        let params = vec![
+            ("amazonOrderId".to_string(), amazon_order_id.to_string()),
+            ("marketplaceId".to_string(), marketplace.details().0.to_string()),
+        ];
+
+        parse_response(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Schedules an Easy Ship order and returns the scheduled package information.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    ///
+    /// # Parameters
+    /// - `amazon_order_id`: The order to schedule.
+    /// - `marketplace`: The marketplace of the order.
+    /// - `package_details`: The time slot from `list_handover_slots`, and optionally the items.
+    pub async fn create_scheduled_package(
+        client: &mut Client,
+        amazon_order_id: &str,
+        marketplace: CountryMarketplace,
+        package_details: &PackageDetails,
+    ) -> Result<Package, Errors> {
+        let endpoint = format!("{}/package", BASE);
+        let body = json!({
+            "amazonOrderId": amazon_order_id,
+            "marketplaceId": marketplace.details().0,
+            "packageDetails": package_details,
+        })
+        .to_string();
+
+        parse_response(client.make_request_w_body(&endpoint, Method::POST, None::<Vec<(String, String)>>, body).await?).await
+    }
+
+    /// Updates the time slot for handing over the package indicated by the specified scheduled
+    /// package ids.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    pub async fn update_scheduled_packages(
+        client: &mut Client,
+        marketplace: CountryMarketplace,
+        updates: &[UpdatePackageDetails],
+    ) -> Result<Packages, Errors> {
+        let endpoint = format!("{}/package", BASE);
+        let body = json!({ "marketplaceId": marketplace.details().0, "updatePackageDetailsList": updates }).to_string();
+
+        parse_response(client.make_request_w_body(&endpoint, Method::PATCH, None::<Vec<(String, String)>>, body).await?).await
+    }
+
+    /// Schedules up to 100 Easy Ship orders at once and returns the scheduled packages, the
+    /// rejected orders and a link to the labels.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    ///
+    /// # Parameters
+    /// - `marketplace`: The marketplace of the orders.
+    /// - `orders`: The orders to schedule, at most 100.
+    /// - `label_format`: The format of the labels in the printable documents.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns the scheduled packages, the rejected orders with their errors, and `printable_documents_url`.
+    pub async fn create_scheduled_package_bulk(
+        client: &mut Client,
+        marketplace: CountryMarketplace,
+        orders: &[OrderScheduleDetails],
+        label_format: LabelFormat,
+    ) -> Result<CreateScheduledPackagesResponse, Errors> {
+        if orders.len() > 100 {
+            return Err(Errors::CustomError("At most 100 orders can be scheduled at once".to_string()));
+        }
+        let endpoint = format!("{}/packages/bulk", BASE);
+        let body = json!({
+            "marketplaceId": marketplace.details().0,
+            "orderScheduleDetailsList": orders,
+            "labelFormat": label_format,
+        })
+        .to_string();
+
+        parse_response(client.make_request_w_body(&endpoint, Method::POST, None::<Vec<(String, String)>>, body).await?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packages_round_trip_with_lowercase_units() {
+        let sample = json!({
+            "scheduledPackageId": {"amazonOrderId": "123-1234567-1234567", "packageId": "P1"},
+            "packageDimensions": {"length": 10.0, "width": 20.0, "height": 5.0, "unit": "cm"},
+            "packageWeight": {"value": 500.0, "unit": "g"},
+            "packageItems": [{"orderItemId": "11111111111111"}],
+            "packageTimeSlot": {"slotId": "S1", "handoverMethod": "Pickup"},
+            "packageStatus": "ReadyForPickup"
+        });
+        let package: Package = serde_json::from_value(sample.clone()).unwrap();
+        assert_eq!(package.package_dimensions, Some(Dimensions::cm(10.0, 20.0, 5.0)));
+        assert_eq!(package.package_weight.map(|w| w.unit), Some(UnitOfWeight::G));
+
+        let value = serde_json::to_value(&package).unwrap();
+        assert_eq!(value["packageDimensions"], sample["packageDimensions"]);
+        assert_eq!(value["packageWeight"], sample["packageWeight"]);
+        assert_eq!(serde_json::to_value(Weight::grams(1.0)).unwrap()["unit"], "grams");
+    }
+}
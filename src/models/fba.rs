@@ -0,0 +1,637 @@
+//! The FBA Inventory API, for the quantities of a seller's inventory in Amazon's fulfillment
+//! network, and the FBA Inbound Eligibility API, for whether an item can be sent to it.
+use std::collections::HashSet;
+use std::time::Duration;
+use tokio::time::Instant;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::error_handling::Errors;
+use crate::general::{check_response, encode_path_segment, parse_payload, parse_response, Client, CountryMarketplace};
+use crate::pagination::{Page, PagedRequest, Paginator};
+
+pub struct FbaInventory;
+pub struct FbaInboundEligibility;
+
+/// getInventorySummaries allows 2 requests per second.
+const INVENTORY_INTERVAL: Duration = Duration::from_millis(500);
+/// getInventorySummaries accepts at most this many seller SKUs per request.
+pub const MAX_SELLER_SKUS: usize = 50;
+/// getItemEligibilityPreview allows 1 request per second.
+const ELIGIBILITY_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+pub enum GranularityType {
+    Marketplace,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Granularity {
+    pub granularity_type: Option<String>,
+    pub granularity_id: Option<String>,
+}
+
+/// Units reserved for customer orders, transshipment or fulfillment center processing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReservedQuantity {
+    pub total_reserved_quantity: i64,
+    pub pending_customer_order_quantity: i64,
+    pub pending_transshipment_quantity: i64,
+    pub fc_processing_quantity: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResearchingQuantityEntry {
+    /// `researchingQuantityInShortTerm`, `researchingQuantityInMidTerm` or `researchingQuantityInLongTerm`.
+    pub name: String,
+    pub quantity: i64,
+}
+
+/// Units that are misplaced or warehouse damaged, and being confirmed at fulfillment centers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResearchingQuantity {
+    pub total_researching_quantity: i64,
+    pub researching_quantity_breakdown: Vec<ResearchingQuantityEntry>,
+}
+
+/// Units in an unsellable condition, by the reason they can't be sold.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UnfulfillableQuantity {
+    pub total_unfulfillable_quantity: i64,
+    pub customer_damaged_quantity: i64,
+    pub warehouse_damaged_quantity: i64,
+    pub distributor_damaged_quantity: i64,
+    pub carrier_damaged_quantity: i64,
+    pub defective_quantity: i64,
+    pub expired_quantity: i64,
+}
+
+/// The quantities of an item by state. Only returned when `details` is requested.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InventoryDetails {
+    /// Units that can be picked, packed and shipped.
+    pub fulfillable_quantity: i64,
+    /// Units in an inbound shipment that has not been shipped yet.
+    pub inbound_working_quantity: i64,
+    /// Units in an inbound shipment with tracking, not yet at the fulfillment center.
+    pub inbound_shipped_quantity: i64,
+    /// Units being received at the fulfillment center.
+    pub inbound_receiving_quantity: i64,
+    pub reserved_quantity: ReservedQuantity,
+    pub researching_quantity: ResearchingQuantity,
+    pub unfulfillable_quantity: UnfulfillableQuantity,
+}
+impl InventoryDetails {
+    /// The units on their way to a fulfillment center: working, shipped and receiving.
+    pub fn inbound_quantity(&self) -> i64 {
+        self.inbound_working_quantity + self.inbound_shipped_quantity + self.inbound_receiving_quantity
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InventorySummary {
+    pub asin: Option<String>,
+    pub fn_sku: Option<String>,
+    pub seller_sku: Option<String>,
+    /// e.g. `NewItem`, `UsedLikeNew`.
+    pub condition: Option<String>,
+    pub inventory_details: Option<InventoryDetails>,
+    pub last_updated_time: Option<String>,
+    pub product_name: Option<String>,
+    pub total_quantity: Option<i64>,
+    pub stores: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InventorySummariesPayload {
+    pub granularity: Granularity,
+    pub inventory_summaries: Vec<InventorySummary>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Pagination {
+    pub next_token: Option<String>,
+}
+
+/// The response of getInventorySummaries. The `pagination` sits next to the payload.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GetInventorySummariesResponse {
+    pub payload: InventorySummariesPayload,
+    pub pagination: Option<Pagination>,
+}
+
+/// The paged request behind `FbaInventory::inventory_summaries`.
+#[derive(Debug, Clone)]
+pub struct InventorySummariesRequest {
+    pub marketplace: CountryMarketplace,
+    pub details: bool,
+    pub start_date_time: Option<String>,
+    pub seller_skus: Vec<String>,
+}
+impl PagedRequest for InventorySummariesRequest {
+    type Item = InventorySummary;
+
+    async fn fetch_page(&mut self, client: &mut Client, next_token: Option<String>) -> Result<Page<InventorySummary>, Errors> {
+        let response = FbaInventory::get_inventory_summaries(
+            client,
+            self.details,
+            GranularityType::Marketplace,
+            self.marketplace,
+            self.start_date_time.clone(),
+            &self.seller_skus,
+            next_token,
+        )
+        .await?;
+        Ok(Page { items: response.payload.inventory_summaries, next_token: response.pagination.and_then(|p| p.next_token) })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryItem {
+    pub seller_sku: String,
+    pub marketplace_id: String,
+    pub quantity: u32,
+}
+
+impl FbaInventory {
+    /// Returns a list of inventory summaries, aggregated at the marketplace level.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    ///
+    /// # Parameters
+    /// - `details`: Whether to return `InventoryDetails`. Without it only `total_quantity` is returned.
+    /// - `granularity_type`: The granularity of the summaries; only `Marketplace` is supported.
+    /// - `marketplace`: The marketplace, also used as the granularity ID.
+    /// - `start_date_time` (optional): Only summaries changed at or after this date-time, in ISO 8601 format. Cannot be combined with `seller_skus`.
+    /// - `seller_skus`: Only these seller SKUs, at most 50. All SKUs when empty.
+    /// - `next_token` (optional): The token from the previous page.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns the summaries and the token for the next page.
+    pub async fn get_inventory_summaries(
+        client: &mut Client,
+        details: bool,
+        granularity_type: GranularityType,
+        marketplace: CountryMarketplace,
+        start_date_time: Option<String>,
+        seller_skus: &[String],
+        next_token: Option<String>,
+    ) -> Result<GetInventorySummariesResponse, Errors> {
+        if seller_skus.len() > MAX_SELLER_SKUS {
+            return Err(Errors::CustomError(format!("At most {} seller SKUs can be requested at once", MAX_SELLER_SKUS)));
+        }
+        let endpoint = "/fba/inventory/v1/summaries";
+        let marketplace_id = marketplace.details().0.to_string();
+        let mut params = vec![
+            ("details".to_string(), details.to_string()),
+            ("granularityType".to_string(), granularity_type.to_string()),
+            ("granularityId".to_string(), marketplace_id.clone()),
+            ("marketplaceIds".to_string(), marketplace_id),
+        ];
+        if let Some(start) = start_date_time {
+            params.push(("startDateTime".to_string(), start));
+        }
+        if !seller_skus.is_empty() {
+            params.push(("sellerSkus".to_string(), seller_skus.join(",")));
+        }
+        if let Some(token) = next_token {
+            params.push(("nextToken".to_string(), token));
+        }
+
+        parse_response(client.make_request(endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Pages through `get_inventory_summaries`, following the `nextToken` automatically.
+    ///
+    /// **DEV NOTE:** Amazon only returns a `nextToken` when `seller_skus` is empty, so requesting
+    /// more than 50 SKUs has to be split by the caller.
+    pub fn inventory_summaries(
+        marketplace: CountryMarketplace,
+        details: bool,
+        start_date_time: Option<String>,
+        seller_skus: Vec<String>,
+    ) -> Paginator<InventorySummariesRequest> {
+        Paginator::new(InventorySummariesRequest { marketplace, details, start_date_time, seller_skus }, INVENTORY_INTERVAL)
+    }
+
+    /// Requests that Amazon create a product-only inventory item in the sandbox environment.
+    /// This is a sandbox-only operation; `client` has to be in sandbox mode, see `Client::sandbox`.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    pub async fn create_inventory_item(client: &mut Client, seller_sku: &str, marketplace: CountryMarketplace, product_name: &str) -> Result<(), Errors> {
+        require_sandbox(client)?;
+        let endpoint = "/fba/inventory/v1/items";
+        let body = json!({
+            "sellerSku": seller_sku,
+            "marketplaceId": marketplace.details().0,
+            "productName": product_name,
+        })
+        .to_string();
+
+        check_response(client.make_request_w_body(endpoint, Method::POST, None::<Vec<(String, String)>>, body).await?).await
+    }
+
+    /// Requests that Amazon add items to the sandbox inventory with the given quantities.
+    /// This is a sandbox-only operation; `client` has to be in sandbox mode, see `Client::sandbox`.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    ///
+    /// # Parameters
+    /// - `idempotency_token`: A unique token per request, so a retried request isn't applied twice.
+    /// - `items`: The SKUs and quantities to add.
+    pub async fn add_inventory(client: &mut Client, idempotency_token: &str, items: &[InventoryItem]) -> Result<(), Errors> {
+        require_sandbox(client)?;
+        let endpoint = "/fba/inventory/v1/items/inventory";
+        let body = json!({ "inventoryItems": items }).to_string();
+
+        check_response(
+            client
+                .make_request_w_headers(
+                    endpoint,
+                    Method::POST,
+                    None::<Vec<(String, String)>>,
+                    &[("x-amzn-idempotency-token", idempotency_token)],
+                    Some(body),
+                )
+                .await?,
+        )
+        .await
+    }
+
+    /// Requests that Amazon delete an item from the sandbox inventory.
+    /// This is a sandbox-only operation; `client` has to be in sandbox mode, see `Client::sandbox`.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    pub async fn delete_inventory_item(client: &mut Client, seller_sku: &str, marketplace: CountryMarketplace) -> Result<(), Errors> {
+        require_sandbox(client)?;
+        let endpoint = format!("/fba/inventory/v1/items/{}", encode_path_segment(seller_sku));
+        let params = vec![("marketplaceId".to_string(), marketplace.details().0.to_string())];
+
+        check_response(client.make_request(&endpoint, Method::DELETE, Some(params)).await?).await
+    }
+}
+
+fn require_sandbox(client: &Client) -> Result<(), Errors> {
+    if client.is_sandbox() {
+        Ok(())
+    } else {
+        Err(Errors::CustomError("This operation is only available in the sandbox; enable it with Client::sandbox".to_string()))
+    }
+}
+
+/// A program to check inbound eligibility for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, strum_macros::Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum EligibilityProgram {
+    /// Sending the item to an Amazon fulfillment center.
+    Inbound,
+    /// Commingling the item with other sellers' units of the same product.
+    Commingling,
+}
+
+/// Why an item is ineligible for a program, e.g. `FBA_INB_0004`.
+///
+/// Codes this crate doesn't know yet are kept in `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum IneligibilityReason {
+    FbaInb0004,
+    FbaInb0006,
+    FbaInb0007,
+    FbaInb0008,
+    FbaInb0009,
+    FbaInb0010,
+    FbaInb0011,
+    FbaInb0012,
+    FbaInb0013,
+    FbaInb0014,
+    FbaInb0015,
+    FbaInb0016,
+    FbaInb0017,
+    FbaInb0018,
+    FbaInb0019,
+    FbaInb0034,
+    FbaInb0035,
+    FbaInb0036,
+    FbaInb0037,
+    FbaInb0038,
+    FbaInb0050,
+    FbaInb0051,
+    FbaInb0053,
+    FbaInb0055,
+    FbaInb0056,
+    FbaInb0059,
+    FbaInb0065,
+    FbaInb0066,
+    FbaInb0067,
+    FbaInb0068,
+    FbaInb0095,
+    FbaInb0097,
+    FbaInb0098,
+    FbaInb0099,
+    FbaInb0100,
+    FbaInb0103,
+    FbaInb0104,
+    FbaInb0197,
+    UnknownInbErrorCode,
+    Other(String),
+}
+impl IneligibilityReason {
+    pub fn code(&self) -> &str {
+        match self {
+            IneligibilityReason::FbaInb0004 => "FBA_INB_0004",
+            IneligibilityReason::FbaInb0006 => "FBA_INB_0006",
+            IneligibilityReason::FbaInb0007 => "FBA_INB_0007",
+            IneligibilityReason::FbaInb0008 => "FBA_INB_0008",
+            IneligibilityReason::FbaInb0009 => "FBA_INB_0009",
+            IneligibilityReason::FbaInb0010 => "FBA_INB_0010",
+            IneligibilityReason::FbaInb0011 => "FBA_INB_0011",
+            IneligibilityReason::FbaInb0012 => "FBA_INB_0012",
+            IneligibilityReason::FbaInb0013 => "FBA_INB_0013",
+            IneligibilityReason::FbaInb0014 => "FBA_INB_0014",
+            IneligibilityReason::FbaInb0015 => "FBA_INB_0015",
+            IneligibilityReason::FbaInb0016 => "FBA_INB_0016",
+            IneligibilityReason::FbaInb0017 => "FBA_INB_0017",
+            IneligibilityReason::FbaInb0018 => "FBA_INB_0018",
+            IneligibilityReason::FbaInb0019 => "FBA_INB_0019",
+            IneligibilityReason::FbaInb0034 => "FBA_INB_0034",
+            IneligibilityReason::FbaInb0035 => "FBA_INB_0035",
+            IneligibilityReason::FbaInb0036 => "FBA_INB_0036",
+            IneligibilityReason::FbaInb0037 => "FBA_INB_0037",
+            IneligibilityReason::FbaInb0038 => "FBA_INB_0038",
+            IneligibilityReason::FbaInb0050 => "FBA_INB_0050",
+            IneligibilityReason::FbaInb0051 => "FBA_INB_0051",
+            IneligibilityReason::FbaInb0053 => "FBA_INB_0053",
+            IneligibilityReason::FbaInb0055 => "FBA_INB_0055",
+            IneligibilityReason::FbaInb0056 => "FBA_INB_0056",
+            IneligibilityReason::FbaInb0059 => "FBA_INB_0059",
+            IneligibilityReason::FbaInb0065 => "FBA_INB_0065",
+            IneligibilityReason::FbaInb0066 => "FBA_INB_0066",
+            IneligibilityReason::FbaInb0067 => "FBA_INB_0067",
+            IneligibilityReason::FbaInb0068 => "FBA_INB_0068",
+            IneligibilityReason::FbaInb0095 => "FBA_INB_0095",
+            IneligibilityReason::FbaInb0097 => "FBA_INB_0097",
+            IneligibilityReason::FbaInb0098 => "FBA_INB_0098",
+            IneligibilityReason::FbaInb0099 => "FBA_INB_0099",
+            IneligibilityReason::FbaInb0100 => "FBA_INB_0100",
+            IneligibilityReason::FbaInb0103 => "FBA_INB_0103",
+            IneligibilityReason::FbaInb0104 => "FBA_INB_0104",
+            IneligibilityReason::FbaInb0197 => "FBA_INB_0197",
+            IneligibilityReason::UnknownInbErrorCode => "UNKNOWN_INB_ERROR_CODE",
+            IneligibilityReason::Other(code) => code,
+        }
+    }
+    /// A human-readable description of the reason, as documented by Amazon.
+    pub fn description(&self) -> &'static str {
+        match self {
+            IneligibilityReason::FbaInb0004 => "Missing package dimensions. This product is missing necessary information; dimensions need to be provided in the manufacturer's original packaging.",
+            IneligibilityReason::FbaInb0006 => "The SKU for this product is unknown or cannot be found.",
+            IneligibilityReason::FbaInb0007 => "Product under dangerous goods (hazmat) review. We do not have enough information to determine what the product is or comes with to complete the review.",
+            IneligibilityReason::FbaInb0008 => "Product under dangerous goods (hazmat) review. We require detailed battery information to complete the review.",
+            IneligibilityReason::FbaInb0009 => "Product under dangerous goods (hazmat) review. We do not have enough information to complete the review; a safety data sheet is required.",
+            IneligibilityReason::FbaInb0010 => "Ineligible for inbound. Dangerous goods (hazmat): the product cannot be stored in our fulfillment centers.",
+            IneligibilityReason::FbaInb0011 => "Product under dangerous goods (hazmat) review. The product has been sent for review and is not available for inbound until the review is complete.",
+            IneligibilityReason::FbaInb0012 => "Product under dangerous goods (hazmat) review. The product is not available for inbound until the review is complete.",
+            IneligibilityReason::FbaInb0013 => "Product under dangerous goods (hazmat) review. The product is not available for inbound until the review is complete.",
+            IneligibilityReason::FbaInb0014 => "Product under dangerous goods (hazmat) review. The product is not available for inbound until the review is complete.",
+            IneligibilityReason::FbaInb0015 => "Dangerous goods (hazmat). The product is regulated as a dangerous good and cannot be stored in our fulfillment centers.",
+            IneligibilityReason::FbaInb0016 => "Dangerous goods (hazmat). The product is regulated as a dangerous good and cannot be stored in our fulfillment centers.",
+            IneligibilityReason::FbaInb0017 => "This product does not exist in the destination marketplace catalog. The necessary product information must be provided before it can be inbounded.",
+            IneligibilityReason::FbaInb0018 => "Product missing category. This product must have a category specified before it can be sent to Amazon.",
+            IneligibilityReason::FbaInb0019 => "This product must have a title before it can be sent to Amazon.",
+            IneligibilityReason::FbaInb0034 => "Product cannot be stickerless, commingled. This product must be removed from your shipping plan or labeled.",
+            IneligibilityReason::FbaInb0035 => "Expiration-dated or lot-controlled product needs to be labeled. This product requires labeling to be received at our fulfillment centers.",
+            IneligibilityReason::FbaInb0036 => "Expiration-dated or lot-controlled product needs to be commingled. This product cannot be shipped to Amazon without being commingled.",
+            IneligibilityReason::FbaInb0037 => "This product is not eligible to be shipped to our fulfillment center. You do not have all the required tax documents.",
+            IneligibilityReason::FbaInb0038 => "This product is not eligible to be shipped to our fulfillment center. You do not have all the required tax documents.",
+            IneligibilityReason::FbaInb0050 => "There is currently no fulfillment center in the destination country capable of receiving this product.",
+            IneligibilityReason::FbaInb0051 => "This product has been blocked by FBA and cannot currently be sent to Amazon for fulfillment.",
+            IneligibilityReason::FbaInb0053 => "Product is not eligible in the destination marketplace. This product is not eligible either because the required information is missing or because it is not allowed.",
+            IneligibilityReason::FbaInb0055 => "Product unfulfillable due to media region restrictions. This product has a region code restricted for this marketplace.",
+            IneligibilityReason::FbaInb0056 => "Product is ineligible for inbound. Used non-media goods cannot be shipped to Amazon.",
+            IneligibilityReason::FbaInb0059 => "Unknown exception. This product must be removed from your shipping plan.",
+            IneligibilityReason::FbaInb0065 => "Product cannot be stickerless, commingled. This product must be removed from your shipping plan or labeled.",
+            IneligibilityReason::FbaInb0066 => "Unknown exception. This product must be removed from your shipping plan.",
+            IneligibilityReason::FbaInb0067 => "Product ineligible for freight shipping. This item is ineligible for freight shipping with our global shipping service.",
+            IneligibilityReason::FbaInb0068 => "Account not configured for expiration-dated or lot-controlled products.",
+            IneligibilityReason::FbaInb0095 => "The barcode (UPC/EAN/JAN/ISBN) for this product is associated with more than one product in our fulfillment system.",
+            IneligibilityReason::FbaInb0097 => "Fully regulated dangerous good.",
+            IneligibilityReason::FbaInb0098 => "Merchant is not authorized to send item to destination marketplace.",
+            IneligibilityReason::FbaInb0099 => "Seller account previously terminated.",
+            IneligibilityReason::FbaInb0100 => "You do not have the required tax information to send inventory to fulfillment centers in Mexico.",
+            IneligibilityReason::FbaInb0103 => "This is an expiration-dated or lot-controlled product that cannot be handled at this time.",
+            IneligibilityReason::FbaInb0104 => "Item requires manufacturer barcode. Only new products can be stored in our fulfillment centers without product labels.",
+            IneligibilityReason::FbaInb0197 => "Item requires safety data sheet. Provide a safety data sheet before the product can be sent to Amazon.",
+            IneligibilityReason::UnknownInbErrorCode => "Unknown ineligibility reason.",
+            IneligibilityReason::Other(_) => "Undocumented ineligibility reason.",
+        }
+    }
+}
+impl From<String> for IneligibilityReason {
+    fn from(code: String) -> Self {
+        match code.as_str() {
+            "FBA_INB_0004" => IneligibilityReason::FbaInb0004,
+            "FBA_INB_0006" => IneligibilityReason::FbaInb0006,
+            "FBA_INB_0007" => IneligibilityReason::FbaInb0007,
+            "FBA_INB_0008" => IneligibilityReason::FbaInb0008,
+            "FBA_INB_0009" => IneligibilityReason::FbaInb0009,
+            "FBA_INB_0010" => IneligibilityReason::FbaInb0010,
+            "FBA_INB_0011" => IneligibilityReason::FbaInb0011,
+            "FBA_INB_0012" => IneligibilityReason::FbaInb0012,
+            "FBA_INB_0013" => IneligibilityReason::FbaInb0013,
+            "FBA_INB_0014" => IneligibilityReason::FbaInb0014,
+            "FBA_INB_0015" => IneligibilityReason::FbaInb0015,
+            "FBA_INB_0016" => IneligibilityReason::FbaInb0016,
+            "FBA_INB_0017" => IneligibilityReason::FbaInb0017,
+            "FBA_INB_0018" => IneligibilityReason::FbaInb0018,
+            "FBA_INB_0019" => IneligibilityReason::FbaInb0019,
+            "FBA_INB_0034" => IneligibilityReason::FbaInb0034,
+            "FBA_INB_0035" => IneligibilityReason::FbaInb0035,
+            "FBA_INB_0036" => IneligibilityReason::FbaInb0036,
+            "FBA_INB_0037" => IneligibilityReason::FbaInb0037,
+            "FBA_INB_0038" => IneligibilityReason::FbaInb0038,
+            "FBA_INB_0050" => IneligibilityReason::FbaInb0050,
+            "FBA_INB_0051" => IneligibilityReason::FbaInb0051,
+            "FBA_INB_0053" => IneligibilityReason::FbaInb0053,
+            "FBA_INB_0055" => IneligibilityReason::FbaInb0055,
+            "FBA_INB_0056" => IneligibilityReason::FbaInb0056,
+            "FBA_INB_0059" => IneligibilityReason::FbaInb0059,
+            "FBA_INB_0065" => IneligibilityReason::FbaInb0065,
+            "FBA_INB_0066" => IneligibilityReason::FbaInb0066,
+            "FBA_INB_0067" => IneligibilityReason::FbaInb0067,
+            "FBA_INB_0068" => IneligibilityReason::FbaInb0068,
+            "FBA_INB_0095" => IneligibilityReason::FbaInb0095,
+            "FBA_INB_0097" => IneligibilityReason::FbaInb0097,
+            "FBA_INB_0098" => IneligibilityReason::FbaInb0098,
+            "FBA_INB_0099" => IneligibilityReason::FbaInb0099,
+            "FBA_INB_0100" => IneligibilityReason::FbaInb0100,
+            "FBA_INB_0103" => IneligibilityReason::FbaInb0103,
+            "FBA_INB_0104" => IneligibilityReason::FbaInb0104,
+            "FBA_INB_0197" => IneligibilityReason::FbaInb0197,
+            "UNKNOWN_INB_ERROR_CODE" => IneligibilityReason::UnknownInbErrorCode,
+            _ => IneligibilityReason::Other(code),
+        }
+    }
+}
+impl From<IneligibilityReason> for String {
+    fn from(reason: IneligibilityReason) -> Self {
+        reason.code().to_string()
+    }
+}
+impl std::fmt::Display for IneligibilityReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code(), self.description())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemEligibilityPreview {
+    pub asin: String,
+    #[serde(default)]
+    pub marketplace_id: Option<String>,
+    pub program: EligibilityProgram,
+    pub is_eligible_for_program: bool,
+    #[serde(default)]
+    pub ineligibility_reason_list: Vec<IneligibilityReason>,
+}
+
+impl FbaInboundEligibility {
+    /// Checks whether an item is eligible for a program, and why not.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 1
+    ///
+    /// # Parameters
+    /// - `asin`: The ASIN of the item.
+    /// - `marketplace` (optional): The marketplace to check; required for `Inbound`.
+    /// - `program`: The program to check eligibility for.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns whether the item is eligible and the ineligibility reasons.
+    pub async fn get_item_eligibility_preview(
+        client: &mut Client,
+        asin: &str,
+        marketplace: Option<CountryMarketplace>,
+        program: EligibilityProgram,
+    ) -> Result<ItemEligibilityPreview, Errors> {
+        let endpoint = "/fba/inbound/v1/eligibility/itemPreview";
+        let mut params = vec![("asin".to_string(), asin.to_string()), ("program".to_string(), program.to_string())];
+        if let Some(marketplace) = marketplace {
+            params.push(("marketplaceIds".to_string(), marketplace.details().0.to_string()));
+        }
+
+        parse_payload(client.make_request(endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Checks many ASINs one after the other, spaced by the rate limit.
+    ///
+    /// **DEV NOTE:** The operation has no batch endpoint and allows 1 request per second, so
+    /// checking N ASINs takes about N seconds. A failed ASIN doesn't stop the others.
+    ///
+    /// # Responses
+    /// - Returns one result per distinct ASIN, in the order given.
+    pub async fn get_item_eligibility_previews(
+        client: &mut Client,
+        asins: &[String],
+        marketplace: Option<CountryMarketplace>,
+        program: EligibilityProgram,
+    ) -> Vec<(String, Result<ItemEligibilityPreview, Errors>)> {
+        let mut seen = HashSet::new();
+        let mut results = Vec::new();
+        let mut last_request: Option<Instant> = None;
+        for asin in asins.iter().filter(|a| seen.insert(a.as_str())) {
+            if let Some(last) = last_request {
+                tokio::time::sleep_until(last + ELIGIBILITY_INTERVAL).await;
+            }
+            last_request = Some(Instant::now());
+            let result = Self::get_item_eligibility_preview(client, asin, marketplace, program).await;
+            results.push((asin.clone(), result));
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ineligibility_reasons_round_trip_through_their_code() {
+        let known = [
+            IneligibilityReason::FbaInb0004,
+            IneligibilityReason::FbaInb0006,
+            IneligibilityReason::FbaInb0007,
+            IneligibilityReason::FbaInb0008,
+            IneligibilityReason::FbaInb0009,
+            IneligibilityReason::FbaInb0010,
+            IneligibilityReason::FbaInb0011,
+            IneligibilityReason::FbaInb0012,
+            IneligibilityReason::FbaInb0013,
+            IneligibilityReason::FbaInb0014,
+            IneligibilityReason::FbaInb0015,
+            IneligibilityReason::FbaInb0016,
+            IneligibilityReason::FbaInb0017,
+            IneligibilityReason::FbaInb0018,
+            IneligibilityReason::FbaInb0019,
+            IneligibilityReason::FbaInb0034,
+            IneligibilityReason::FbaInb0035,
+            IneligibilityReason::FbaInb0036,
+            IneligibilityReason::FbaInb0037,
+            IneligibilityReason::FbaInb0038,
+            IneligibilityReason::FbaInb0050,
+            IneligibilityReason::FbaInb0051,
+            IneligibilityReason::FbaInb0053,
+            IneligibilityReason::FbaInb0055,
+            IneligibilityReason::FbaInb0056,
+            IneligibilityReason::FbaInb0059,
+            IneligibilityReason::FbaInb0065,
+            IneligibilityReason::FbaInb0066,
+            IneligibilityReason::FbaInb0067,
+            IneligibilityReason::FbaInb0068,
+            IneligibilityReason::FbaInb0095,
+            IneligibilityReason::FbaInb0097,
+            IneligibilityReason::FbaInb0098,
+            IneligibilityReason::FbaInb0099,
+            IneligibilityReason::FbaInb0100,
+            IneligibilityReason::FbaInb0103,
+            IneligibilityReason::FbaInb0104,
+            IneligibilityReason::FbaInb0197,
+            IneligibilityReason::UnknownInbErrorCode,
+        ];
+        let mut codes = std::collections::HashSet::new();
+        for reason in known {
+            assert!(codes.insert(reason.code().to_string()), "duplicate code {}", reason.code());
+            assert_eq!(IneligibilityReason::from(reason.code().to_string()), reason);
+            assert_ne!(reason.description(), IneligibilityReason::Other(String::new()).description(), "{}", reason.code());
+        }
+
+        let other = IneligibilityReason::from("FBA_INB_9999".to_string());
+        assert_eq!(other, IneligibilityReason::Other("FBA_INB_9999".to_string()));
+        assert_eq!(other.code(), "FBA_INB_9999");
+        assert_eq!(serde_json::to_value(&other).unwrap(), serde_json::json!("FBA_INB_9999"));
+        let parsed: Vec<IneligibilityReason> = serde_json::from_value(serde_json::json!(["FBA_INB_0019", "FBA_INB_9999"])).unwrap();
+        assert_eq!(parsed, vec![IneligibilityReason::FbaInb0019, other]);
+    }
+}
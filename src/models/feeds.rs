@@ -0,0 +1,351 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::error_handling::Errors;
+use crate::general::{check_response, gunzip, parse_response, Client, CountryMarketplace};
+use crate::models::kiosk::ProcessingStatuses;
+use crate::models::processing_report::ProcessingReport;
+use crate::poller::{OperationHandle, PollStatus, Pollable};
+
+pub struct Feeds;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateFeedDocumentResponse {
+    pub feed_document_id: String,
+    /// The pre-signed URL the feed content has to be uploaded to.
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateFeedResponse {
+    pub feed_id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompressionAlgorithm {
+    GZIP,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedDocument {
+    pub feed_document_id: String,
+    pub url: String,
+    pub compression_algorithm: Option<CompressionAlgorithm>,
+}
+
+/// Detailed information about a feed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Feed {
+    pub feed_id: String,
+    pub feed_type: String,
+    pub marketplace_ids: Option<Vec<String>>,
+    pub created_time: String,
+    pub processing_status: ProcessingStatuses,
+    pub processing_start_time: Option<String>,
+    pub processing_end_time: Option<String>,
+    /// The processing report. Present once the feed is `DONE`, and usually when it is `FATAL`.
+    pub result_feed_document_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeedsResponse {
+    pub feeds: Vec<Feed>,
+    pub next_token: Option<String>,
+}
+
+/// The result of `Feeds::submit_feed`: a `DONE` or `FATAL` feed with its processing report.
+#[derive(Debug, Clone)]
+pub struct FeedOutcome {
+    pub feed: Feed,
+    /// The decompressed content of the processing report.
+    pub processing_report: Vec<u8>,
+}
+impl FeedOutcome {
+    /// Whether Amazon rejected the whole feed. The processing report says why.
+    pub fn is_fatal(&self) -> bool {
+        self.feed.processing_status == ProcessingStatuses::FATAL
+    }
+
+    /// Parses the processing report, whether it is JSON, XML or a flat file.
+    pub fn report(&self) -> Result<ProcessingReport, Errors> {
+        ProcessingReport::parse(&self.processing_report)
+    }
+}
+
+/// A created feed, polled through `Feeds::get_feed` until Amazon has processed it.
+///
+/// Resolves to the feed in its final status, `DONE`, `FATAL` or `CANCELLED`; the caller decides
+/// from `processing_status` what a failed feed means.
+#[derive(Debug, Clone)]
+pub struct FeedOperation {
+    pub feed_id: String,
+}
+impl Pollable for FeedOperation {
+    type Output = Feed;
+
+    async fn poll(&mut self, client: &mut Client) -> Result<PollStatus<Feed>, Errors> {
+        let feed = Feeds::get_feed(client, &self.feed_id).await?;
+        match feed.processing_status {
+            ProcessingStatuses::DONE | ProcessingStatuses::FATAL | ProcessingStatuses::CANCELLED => Ok(PollStatus::Done(feed)),
+            status => Ok(PollStatus::Pending(status.to_string())),
+        }
+    }
+}
+
+impl Feeds {
+    /// Creates a feed document for the feed type that you specify. This returns a `feedDocumentId`
+    /// value, encryption details, and a pre-signed URL for uploading the feed contents.
+    ///
+    /// Rate (requests per second): 0.5
+    ///
+    /// Burst: 15
+    ///
+    /// # Parameters
+    /// - `content_type`: The content type of the feed, e.g. `application/json; charset=UTF-8`.
+    ///
+    /// # Responses
+    /// - **201 (Success):** Returns a `CreateFeedDocumentResponse` object.
+    pub async fn create_feed_document(client: &mut Client, content_type: &str) -> Result<CreateFeedDocumentResponse, Errors> {
+        let body = serde_json::json!({ "contentType": content_type });
+
+        parse_response(
+            client
+                .make_request_w_body("/feeds/2021-06-30/documents", Method::POST, None::<Vec<(String, String)>>, body.to_string())
+                .await?,
+        )
+        .await
+    }
+
+    /// Uploads feed content to the URL returned by `create_feed_document`.
+    ///
+    /// # Parameters
+    /// - `document`: The response of `create_feed_document`.
+    /// - `content_type`: Must be the same content type the document was created with.
+    /// - `content`: The raw feed content.
+    pub async fn upload_feed_document(client: &Client, document: &CreateFeedDocumentResponse, content_type: &str, content: Vec<u8>) -> Result<(), Errors> {
+        client.upload_document(&document.url, content_type, content).await
+    }
+
+    /// Creates a feed. Upload the contents of the feed document before calling this operation.
+    ///
+    /// Rate (requests per second): 0.0083
+    ///
+    /// Burst: 15
+    ///
+    /// # Parameters
+    /// - `feed_type`: The feed type, e.g. `JSON_LISTINGS_FEED`.
+    /// - `marketplaces`: The marketplaces that the feed is applied to.
+    /// - `input_feed_document_id`: The document identifier returned by `create_feed_document`.
+    /// - `feed_options` (optional): Additional options to control the feed, as key-value pairs.
+    ///
+    /// # Responses
+    /// - **202 (Success):** Returns a `CreateFeedResponse` object.
+    pub async fn create_feed(
+        client: &mut Client,
+        feed_type: &str,
+        marketplaces: Vec<CountryMarketplace>,
+        input_feed_document_id: &str,
+        feed_options: Option<Value>,
+    ) -> Result<CreateFeedResponse, Errors> {
+        let mut body = serde_json::json!({
+            "feedType": feed_type,
+            "marketplaceIds": marketplaces.iter().map(|m| m.details().0).collect::<Vec<&str>>(),
+            "inputFeedDocumentId": input_feed_document_id,
+        });
+        if let Some(options) = feed_options {
+            body["feedOptions"] = options;
+        }
+
+        parse_response(
+            client
+                .make_request_w_body("/feeds/2021-06-30/feeds", Method::POST, None::<Vec<(String, String)>>, body.to_string())
+                .await?,
+        )
+        .await
+    }
+
+    /// Returns feed details (including the `resultDocumentId`, if available) for the feed that you specify.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 15
+    ///
+    /// # Parameters
+    /// - `feed_id`: The identifier for the feed.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `Feed` object.
+    pub async fn get_feed(client: &mut Client, feed_id: &str) -> Result<Feed, Errors> {
+        let endpoint = format!("/feeds/2021-06-30/feeds/{}", feed_id);
+
+        parse_response(client.make_request(&endpoint, Method::GET, None::<Vec<(String, String)>>).await?).await
+    }
+
+    /// Returns feed details for the feeds that match the filters that you specify.
+    ///
+    /// Rate (requests per second): 0.0222
+    ///
+    /// Burst: 10
+    ///
+    /// # Parameters
+    /// - `feed_types` (optional): A list of feed types used to filter feeds. Required unless `next_token` is provided.
+    /// - `marketplaces` (optional): A list of marketplaces used to filter feeds.
+    /// - `page_size` (optional): The maximum number of feeds to return. Minimum: 1, Maximum: 100, Default: 10.
+    /// - `processing_statuses` (optional): A list of processing statuses used to filter feeds.
+    /// - `created_since` (optional): The earliest feed creation date and time, in ISO 8601 date-time format. Default: 90 days ago.
+    /// - `created_until` (optional): The latest feed creation date and time, in ISO 8601 date-time format. Default: now.
+    /// - `next_token` (optional): A token returned by a previous call. When provided, all other parameters are ignored.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `GetFeedsResponse` object.
+    pub async fn get_feeds(
+        client: &mut Client,
+        feed_types: Option<Vec<String>>,
+        marketplaces: Option<Vec<CountryMarketplace>>,
+        page_size: Option<u32>,
+        processing_statuses: Option<Vec<ProcessingStatuses>>,
+        created_since: Option<String>,
+        created_until: Option<String>,
+        next_token: Option<String>,
+    ) -> Result<GetFeedsResponse, Errors> {
+        if let Some(size) = page_size {
+            if !(1..=100).contains(&size) {
+                return Err(Errors::CustomError("Page size must be between 1 and 100".to_string()));
+            }
+        }
+
+        let mut params: Vec<(String, String)> = Vec::new();
+        if let Some(token) = next_token {
+            params.push(("nextToken".to_string(), token));
+        } else {
+            if let Some(types) = feed_types {
+                params.push(("feedTypes".to_string(), types.join(",")));
+            }
+            if let Some(marketplaces) = marketplaces {
+                params.push(("marketplaceIds".to_string(), marketplaces.iter().map(|m| m.details().0).collect::<Vec<&str>>().join(",")));
+            }
+            if let Some(size) = page_size {
+                params.push(("pageSize".to_string(), size.to_string()));
+            }
+            if let Some(statuses) = processing_statuses {
+                params.push(("processingStatuses".to_string(), statuses.iter().map(|s| s.to_string()).collect::<Vec<String>>().join(",")));
+            }
+            if let Some(date) = created_since {
+                params.push(("createdSince".to_string(), date));
+            }
+            if let Some(date) = created_until {
+                params.push(("createdUntil".to_string(), date));
+            }
+        }
+
+        parse_response(client.make_request("/feeds/2021-06-30/feeds", Method::GET, Some(params)).await?).await
+    }
+
+    /// Cancels the feed that you specify. Only feeds with `processingStatus=IN_QUEUE` can be cancelled.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 15
+    ///
+    /// # Parameters
+    /// - `feed_id`: The identifier for the feed.
+    ///
+    /// # Responses
+    /// - **200 (Success):** The feed was cancelled.
+    pub async fn cancel_feed(client: &mut Client, feed_id: &str) -> Result<(), Errors> {
+        let endpoint = format!("/feeds/2021-06-30/feeds/{}", feed_id);
+
+        check_response(client.make_request(&endpoint, Method::DELETE, None::<Vec<(String, String)>>).await?).await
+    }
+
+    /// Returns the information required for retrieving a feed document's contents.
+    ///
+    /// Rate (requests per second): 0.0222
+    ///
+    /// Burst: 10
+    ///
+    /// # Parameters
+    /// - `feed_document_id`: The identifier of the feed document.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `FeedDocument` object.
+    pub async fn get_feed_document(client: &mut Client, feed_document_id: &str) -> Result<FeedDocument, Errors> {
+        let endpoint = format!("/feeds/2021-06-30/documents/{}", feed_document_id);
+
+        parse_response(client.make_request(&endpoint, Method::GET, None::<Vec<(String, String)>>).await?).await
+    }
+
+    /// Downloads a feed document, decompressing it when Amazon stored it as GZIP.
+    pub async fn download_feed_document(client: &mut Client, feed_document_id: &str) -> Result<Vec<u8>, Errors> {
+        let document = Self::get_feed_document(client, feed_document_id).await?;
+        let content = client.download_document(&document.url).await?;
+        match document.compression_algorithm {
+            Some(CompressionAlgorithm::GZIP) => gunzip(&content),
+            None => Ok(content),
+        }
+    }
+
+    /// Creates a feed document, uploads `content` to it and creates the feed.
+    ///
+    /// # Returns
+    /// An `OperationHandle` that resolves to the `Feed` once Amazon has processed it, also when
+    /// its `processing_status` is `FATAL` or `CANCELLED`.
+    pub async fn create_feed_with_content(
+        client: &mut Client,
+        feed_type: &str,
+        marketplaces: Vec<CountryMarketplace>,
+        content_type: &str,
+        content: Vec<u8>,
+        feed_options: Option<Value>,
+    ) -> Result<OperationHandle<FeedOperation>, Errors> {
+        let document = Self::create_feed_document(client, content_type).await?;
+        Self::upload_feed_document(client, &document, content_type, content).await?;
+        let created = Self::create_feed(client, feed_type, marketplaces, &document.feed_document_id, feed_options).await?;
+
+        Ok(OperationHandle::new(FeedOperation { feed_id: created.feed_id }))
+    }
+
+    /// Uploads the content, creates the feed, waits for Amazon to process it and downloads the
+    /// processing report.
+    ///
+    /// A `FATAL` feed is returned with its processing report too, since that is where Amazon
+    /// explains the failure; check `FeedOutcome::is_fatal`. It is only an error when Amazon
+    /// produced no report for it.
+    ///
+    /// Use `create_feed_with_content` instead to control the polling (timeout, backoff,
+    /// cancellation or progress reporting).
+    ///
+    /// # Parameters
+    /// - `feed_type`: The feed type, e.g. `JSON_LISTINGS_FEED`.
+    /// - `marketplaces`: The marketplaces that the feed is applied to.
+    /// - `content_type`: The content type of the feed, e.g. `application/json; charset=UTF-8`.
+    /// - `content`: The raw feed content.
+    pub async fn submit_feed(
+        client: &mut Client,
+        feed_type: &str,
+        marketplaces: Vec<CountryMarketplace>,
+        content_type: &str,
+        content: Vec<u8>,
+    ) -> Result<FeedOutcome, Errors> {
+        let handle = Self::create_feed_with_content(client, feed_type, marketplaces, content_type, content, None).await?;
+        let feed = handle.wait(client).await?;
+        let document_id = match (&feed.processing_status, &feed.result_feed_document_id) {
+            (ProcessingStatuses::CANCELLED, _) => {
+                return Err(Errors::OperationFailed(format!("Feed {} was cancelled", feed.feed_id)));
+            }
+            (ProcessingStatuses::FATAL, None) => {
+                return Err(Errors::OperationFailed(format!("Feed {} failed without a processing report", feed.feed_id)));
+            }
+            (_, Some(document_id)) => document_id.clone(),
+            (_, None) => return Err(Errors::CustomError(format!("Feed {} finished without a processing report", feed.feed_id))),
+        };
+        let processing_report = Self::download_feed_document(client, &document_id).await?;
+
+        Ok(FeedOutcome { feed, processing_report })
+    }
+}
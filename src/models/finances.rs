@@ -0,0 +1,897 @@
+use std::time::Duration;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use crate::error_handling::Errors;
+use crate::general::{parse_payload, Client};
+use crate::pagination::{Page, PagedRequest, Paginator};
+
+pub struct Finances;
+
+/// listFinancialEventGroups and the listFinancialEvents operations allow 0.5 requests per second.
+const FINANCES_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A currency type and amount.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct Currency {
+    pub currency_code: Option<String>,
+    pub currency_amount: Option<f64>,
+}
+impl Currency {
+    pub fn amount(&self) -> f64 {
+        self.currency_amount.unwrap_or_default()
+    }
+}
+
+/// A charge on the seller's account, e.g. `Principal`, `Tax` or `ShippingCharge`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ChargeComponent {
+    pub charge_type: Option<String>,
+    pub charge_amount: Option<Currency>,
+}
+
+/// A fee associated with the event, e.g. `Commission` or `FBAPerUnitFulfillmentFee`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FeeComponent {
+    pub fee_type: Option<String>,
+    pub fee_amount: Option<Currency>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ChargeInstrument {
+    pub description: Option<String>,
+    pub tail: Option<String>,
+    pub amount: Option<Currency>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct DirectPayment {
+    pub direct_payment_type: Option<String>,
+    pub direct_payment_amount: Option<Currency>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct Promotion {
+    pub promotion_type: Option<String>,
+    pub promotion_id: Option<String>,
+    pub promotion_amount: Option<Currency>,
+}
+
+/// Taxes withheld by Amazon under a tax collection model, e.g. `MarketplaceFacilitator`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TaxWithheldComponent {
+    pub tax_collection_model: Option<String>,
+    pub taxes_withheld: Vec<ChargeComponent>,
+}
+
+/// Information about a financial event group, i.e. a settlement period.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FinancialEventGroup {
+    pub financial_event_group_id: Option<String>,
+    /// `Open` or `Closed`.
+    pub processing_status: Option<String>,
+    pub fund_transfer_status: Option<String>,
+    pub original_total: Option<Currency>,
+    pub converted_total: Option<Currency>,
+    pub fund_transfer_date: Option<String>,
+    pub trace_id: Option<String>,
+    pub account_tail: Option<String>,
+    pub beginning_balance: Option<Currency>,
+    pub financial_event_group_start: Option<String>,
+    pub financial_event_group_end: Option<String>,
+}
+
+/// An item of a shipment, refund, guarantee claim or chargeback.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ShipmentItem {
+    #[serde(rename = "SellerSKU")]
+    pub seller_sku: Option<String>,
+    pub order_item_id: Option<String>,
+    pub order_adjustment_item_id: Option<String>,
+    pub quantity_shipped: Option<i64>,
+    pub item_charge_list: Vec<ChargeComponent>,
+    pub item_charge_adjustment_list: Vec<ChargeComponent>,
+    pub item_fee_list: Vec<FeeComponent>,
+    pub item_fee_adjustment_list: Vec<FeeComponent>,
+    pub item_tax_withheld_list: Vec<TaxWithheldComponent>,
+    pub promotion_list: Vec<Promotion>,
+    pub promotion_adjustment_list: Vec<Promotion>,
+    pub cost_of_points_granted: Option<Currency>,
+    pub cost_of_points_returned: Option<Currency>,
+}
+
+/// A shipment, refund, guarantee claim, or chargeback.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ShipmentEvent {
+    pub amazon_order_id: Option<String>,
+    pub seller_order_id: Option<String>,
+    pub marketplace_name: Option<String>,
+    pub store_name: Option<String>,
+    pub order_charge_list: Vec<ChargeComponent>,
+    pub order_charge_adjustment_list: Vec<ChargeComponent>,
+    pub shipment_fee_list: Vec<FeeComponent>,
+    pub shipment_fee_adjustment_list: Vec<FeeComponent>,
+    pub order_fee_list: Vec<FeeComponent>,
+    pub order_fee_adjustment_list: Vec<FeeComponent>,
+    pub direct_payment_list: Vec<DirectPayment>,
+    pub posted_date: Option<String>,
+    pub shipment_item_list: Vec<ShipmentItem>,
+    pub shipment_item_adjustment_list: Vec<ShipmentItem>,
+}
+/// Refunds use the same structure as shipments, with the amounts in the adjustment lists.
+pub type RefundEvent = ShipmentEvent;
+pub type GuaranteeClaimEvent = ShipmentEvent;
+pub type ChargebackEvent = ShipmentEvent;
+pub type ShipmentSettleEvent = ShipmentEvent;
+
+/// An event related to the seller's Pay with Amazon account.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct PayWithAmazonEvent {
+    pub seller_order_id: Option<String>,
+    pub transaction_posted_date: Option<String>,
+    pub business_object_type: Option<String>,
+    pub sales_channel: Option<String>,
+    pub charge: Option<ChargeComponent>,
+    pub fee_list: Vec<FeeComponent>,
+    pub payment_amount_type: Option<String>,
+    pub amount_description: Option<String>,
+    pub fulfillment_channel: Option<String>,
+    pub store_name: Option<String>,
+}
+
+/// A credit given to a solution provider.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct SolutionProviderCreditEvent {
+    pub provider_transaction_type: Option<String>,
+    pub seller_order_id: Option<String>,
+    pub marketplace_id: Option<String>,
+    pub marketplace_country_code: Option<String>,
+    pub seller_id: Option<String>,
+    pub seller_store_name: Option<String>,
+    pub provider_id: Option<String>,
+    pub provider_store_name: Option<String>,
+    pub transaction_amount: Option<Currency>,
+    pub transaction_creation_date: Option<String>,
+}
+
+/// A retrocharge or retrocharge reversal.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct RetrochargeEvent {
+    pub retrocharge_event_type: Option<String>,
+    pub amazon_order_id: Option<String>,
+    pub posted_date: Option<String>,
+    pub base_tax: Option<Currency>,
+    pub shipping_tax: Option<Currency>,
+    pub marketplace_name: Option<String>,
+    pub retrocharge_tax_withheld_list: Vec<TaxWithheldComponent>,
+}
+
+/// An event related to a rental transaction.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct RentalTransactionEvent {
+    pub amazon_order_id: Option<String>,
+    pub rental_event_type: Option<String>,
+    pub extension_length: Option<i64>,
+    pub posted_date: Option<String>,
+    pub rental_charge_list: Vec<ChargeComponent>,
+    pub rental_fee_list: Vec<FeeComponent>,
+    pub marketplace_name: Option<String>,
+    pub rental_initial_value: Option<Currency>,
+    pub rental_reimbursement: Option<Currency>,
+    pub rental_tax_withheld_list: Vec<TaxWithheldComponent>,
+}
+
+/// A Sponsored Products payment event.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProductAdsPaymentEvent {
+    pub posted_date: Option<String>,
+    /// `Charge` or `Refund`.
+    pub transaction_type: Option<String>,
+    pub invoice_id: Option<String>,
+    pub base_value: Option<Currency>,
+    pub tax_value: Option<Currency>,
+    pub transaction_value: Option<Currency>,
+}
+
+/// A service fee on the seller's account.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ServiceFeeEvent {
+    pub amazon_order_id: Option<String>,
+    pub fee_reason: Option<String>,
+    pub fee_list: Vec<FeeComponent>,
+    #[serde(rename = "SellerSKU")]
+    pub seller_sku: Option<String>,
+    #[serde(rename = "FnSKU")]
+    pub fn_sku: Option<String>,
+    pub fee_description: Option<String>,
+    #[serde(rename = "ASIN")]
+    pub asin: Option<String>,
+    pub store_name: Option<String>,
+}
+
+/// A payment event for a seller deal, e.g. a Lightning Deal fee.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SellerDealPaymentEvent {
+    pub posted_date: Option<String>,
+    pub deal_id: Option<String>,
+    pub deal_description: Option<String>,
+    pub event_type: Option<String>,
+    pub fee_type: Option<String>,
+    pub fee_amount: Option<Currency>,
+    pub tax_amount: Option<Currency>,
+    pub total_amount: Option<Currency>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct DebtRecoveryItem {
+    pub recovery_amount: Option<Currency>,
+    pub original_amount: Option<Currency>,
+    pub group_begin_date: Option<String>,
+    pub group_end_date: Option<String>,
+}
+
+/// A debt payment or debt adjustment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct DebtRecoveryEvent {
+    pub debt_recovery_type: Option<String>,
+    pub recovery_amount: Option<Currency>,
+    pub over_payment_credit: Option<Currency>,
+    pub debt_recovery_item_list: Vec<DebtRecoveryItem>,
+    pub charge_instrument_list: Vec<ChargeInstrument>,
+}
+
+/// A loan advance, loan payment, or loan refund.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct LoanServicingEvent {
+    pub loan_amount: Option<Currency>,
+    pub source_business_event_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct AdjustmentItem {
+    /// Amazon returns the quantity as a string.
+    pub quantity: Option<String>,
+    pub per_unit_amount: Option<Currency>,
+    pub total_amount: Option<Currency>,
+    #[serde(rename = "SellerSKU")]
+    pub seller_sku: Option<String>,
+    #[serde(rename = "FnSKU")]
+    pub fn_sku: Option<String>,
+    pub product_description: Option<String>,
+    #[serde(rename = "ASIN")]
+    pub asin: Option<String>,
+    pub transaction_number: Option<String>,
+}
+
+/// An adjustment to the seller's account, e.g. an FBA inventory reimbursement.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct AdjustmentEvent {
+    pub adjustment_type: Option<String>,
+    pub posted_date: Option<String>,
+    pub store_name: Option<String>,
+    pub adjustment_amount: Option<Currency>,
+    pub adjustment_item_list: Vec<AdjustmentItem>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SafetReimbursementItem {
+    pub item_charge_list: Vec<ChargeComponent>,
+    pub product_description: Option<String>,
+    pub quantity: Option<String>,
+}
+
+/// A SAFE-T claim reimbursement on the seller's account.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct SafetReimbursementEvent {
+    pub posted_date: Option<String>,
+    #[serde(rename = "SAFETClaimId")]
+    pub safet_claim_id: Option<String>,
+    pub reimbursed_amount: Option<Currency>,
+    pub reason_code: Option<String>,
+    #[serde(rename = "SAFETReimbursementItemList")]
+    pub safet_reimbursement_item_list: Vec<SafetReimbursementItem>,
+}
+
+/// A fee payment event for the Early Reviewer Program.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct SellerReviewEnrollmentPaymentEvent {
+    pub posted_date: Option<String>,
+    pub enrollment_id: Option<String>,
+    #[serde(rename = "ParentASIN")]
+    pub parent_asin: Option<String>,
+    pub fee_component: Option<FeeComponent>,
+    pub charge_component: Option<ChargeComponent>,
+    pub total_amount: Option<Currency>,
+}
+
+/// A payment or fee for an FBA inventory liquidation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FbaLiquidationEvent {
+    pub posted_date: Option<String>,
+    pub original_removal_order_id: Option<String>,
+    pub liquidation_proceeds_amount: Option<Currency>,
+    pub liquidation_fee_amount: Option<Currency>,
+}
+
+/// An event related to coupon payments.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct CouponPaymentEvent {
+    pub posted_date: Option<String>,
+    pub coupon_id: Option<String>,
+    pub seller_coupon_description: Option<String>,
+    pub clip_or_redemption_count: Option<i64>,
+    pub payment_event_id: Option<String>,
+    pub fee_component: Option<FeeComponent>,
+    pub charge_component: Option<ChargeComponent>,
+    pub total_amount: Option<Currency>,
+}
+
+/// A fee event related to Amazon Imaging services.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ImagingServicesFeeEvent {
+    #[serde(rename = "ImagingRequestBillingItemID")]
+    pub imaging_request_billing_item_id: Option<String>,
+    #[serde(rename = "ASIN")]
+    pub asin: Option<String>,
+    pub posted_date: Option<String>,
+    pub fee_list: Vec<FeeComponent>,
+}
+
+/// A network commingling transaction, i.e. a swap of commingled inventory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct NetworkComminglingTransactionEvent {
+    pub transaction_type: Option<String>,
+    pub posted_date: Option<String>,
+    #[serde(rename = "NetCoTransactionID")]
+    pub net_co_transaction_id: Option<String>,
+    pub swap_reason: Option<String>,
+    #[serde(rename = "ASIN")]
+    pub asin: Option<String>,
+    pub marketplace_id: Option<String>,
+    pub tax_exclusive_amount: Option<Currency>,
+    pub tax_amount: Option<Currency>,
+}
+
+/// An expense related to an affordability promotion, or its reversal.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct AffordabilityExpenseEvent {
+    pub amazon_order_id: Option<String>,
+    pub posted_date: Option<String>,
+    pub marketplace_id: Option<String>,
+    pub transaction_type: Option<String>,
+    pub base_expense: Option<Currency>,
+    #[serde(rename = "TaxTypeCGST")]
+    pub tax_type_cgst: Option<Currency>,
+    #[serde(rename = "TaxTypeSGST")]
+    pub tax_type_sgst: Option<Currency>,
+    #[serde(rename = "TaxTypeIGST")]
+    pub tax_type_igst: Option<Currency>,
+    pub total_expense: Option<Currency>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct RemovalShipmentItem {
+    pub removal_shipment_item_id: Option<String>,
+    pub tax_collection_model: Option<String>,
+    #[serde(rename = "FulfillmentNetworkSKU")]
+    pub fulfillment_network_sku: Option<String>,
+    pub quantity: Option<i64>,
+    pub revenue: Option<Currency>,
+    pub fee_amount: Option<Currency>,
+    pub tax_amount: Option<Currency>,
+    pub tax_withheld: Option<Currency>,
+}
+
+/// A removal shipment, e.g. a removal order sold to a liquidator.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct RemovalShipmentEvent {
+    pub posted_date: Option<String>,
+    pub merchant_order_id: Option<String>,
+    pub order_id: Option<String>,
+    pub transaction_type: Option<String>,
+    pub store_name: Option<String>,
+    pub removal_shipment_item_list: Vec<RemovalShipmentItem>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct RemovalShipmentItemAdjustment {
+    pub removal_shipment_item_id: Option<String>,
+    pub tax_collection_model: Option<String>,
+    #[serde(rename = "FulfillmentNetworkSKU")]
+    pub fulfillment_network_sku: Option<String>,
+    pub adjusted_quantity: Option<i64>,
+    pub revenue_adjustment: Option<Currency>,
+    pub tax_amount_adjustment: Option<Currency>,
+    pub tax_withheld_adjustment: Option<Currency>,
+}
+
+/// An adjustment to a removal shipment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct RemovalShipmentAdjustmentEvent {
+    pub posted_date: Option<String>,
+    pub adjustment_event_id: Option<String>,
+    pub merchant_order_id: Option<String>,
+    pub order_id: Option<String>,
+    pub transaction_type: Option<String>,
+    pub removal_shipment_item_adjustment_list: Vec<RemovalShipmentItemAdjustment>,
+}
+
+/// A fee for a trial shipment under the FBA New Selection program.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TrialShipmentEvent {
+    pub amazon_order_id: Option<String>,
+    pub financial_event_group_id: Option<String>,
+    pub posted_date: Option<String>,
+    #[serde(rename = "SKU")]
+    pub sku: Option<String>,
+    pub fee_list: Vec<FeeComponent>,
+}
+
+/// A reimbursement of Tax Deducted at Source (India).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TdsReimbursementEvent {
+    pub posted_date: Option<String>,
+    #[serde(rename = "TDSOrderId")]
+    pub tds_order_id: Option<String>,
+    pub reimbursed_amount: Option<Currency>,
+}
+
+/// An ad hoc disbursement, e.g. an instant payout.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct AdhocDisbursementEvent {
+    pub transaction_type: Option<String>,
+    pub posted_date: Option<String>,
+    pub transaction_id: Option<String>,
+    pub transaction_amount: Option<Currency>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TaxWithholdingPeriod {
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+}
+
+/// A tax withholding on the seller's account.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TaxWithholdingEvent {
+    pub posted_date: Option<String>,
+    pub base_amount: Option<Currency>,
+    pub withheld_amount: Option<Currency>,
+    pub tax_withholding_period: Option<TaxWithholdingPeriod>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ChargeRefundTransaction {
+    pub charge_amount: Option<Currency>,
+    pub charge_type: Option<String>,
+}
+
+/// A refund of a charge, e.g. a subscription fee.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ChargeRefundEvent {
+    pub posted_date: Option<String>,
+    pub reason_code: Option<String>,
+    pub reason_code_description: Option<String>,
+    pub charge_refund_transactions: Vec<ChargeRefundTransaction>,
+}
+
+/// A failed ad hoc disbursement.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FailedAdhocDisbursementEvent {
+    pub funds_transfers_type: Option<String>,
+    pub transfer_id: Option<String>,
+    pub disbursement_id: Option<String>,
+    pub payment_disbursement_type: Option<String>,
+    pub status: Option<String>,
+    pub transfer_amount: Option<Currency>,
+    pub posted_date: Option<String>,
+}
+
+/// A charge for a value-added service.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ValueAddedServiceChargeEvent {
+    pub transaction_type: Option<String>,
+    pub posted_date: Option<String>,
+    pub description: Option<String>,
+    pub transaction_amount: Option<Currency>,
+}
+
+/// A charge for FBA capacity reservation.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct CapacityReservationBillingEvent {
+    pub transaction_type: Option<String>,
+    pub posted_date: Option<String>,
+    pub description: Option<String>,
+    pub transaction_amount: Option<Currency>,
+}
+
+/// All the financial events of a page, grouped by type.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FinancialEvents {
+    pub shipment_event_list: Vec<ShipmentEvent>,
+    pub shipment_settle_event_list: Vec<ShipmentSettleEvent>,
+    pub refund_event_list: Vec<RefundEvent>,
+    pub guarantee_claim_event_list: Vec<GuaranteeClaimEvent>,
+    pub chargeback_event_list: Vec<ChargebackEvent>,
+    pub pay_with_amazon_event_list: Vec<PayWithAmazonEvent>,
+    pub service_provider_credit_event_list: Vec<SolutionProviderCreditEvent>,
+    pub retrocharge_event_list: Vec<RetrochargeEvent>,
+    pub rental_transaction_event_list: Vec<RentalTransactionEvent>,
+    pub product_ads_payment_event_list: Vec<ProductAdsPaymentEvent>,
+    pub service_fee_event_list: Vec<ServiceFeeEvent>,
+    pub seller_deal_payment_event_list: Vec<SellerDealPaymentEvent>,
+    pub debt_recovery_event_list: Vec<DebtRecoveryEvent>,
+    pub loan_servicing_event_list: Vec<LoanServicingEvent>,
+    pub adjustment_event_list: Vec<AdjustmentEvent>,
+    #[serde(rename = "SAFETReimbursementEventList")]
+    pub safet_reimbursement_event_list: Vec<SafetReimbursementEvent>,
+    pub seller_review_enrollment_payment_event_list: Vec<SellerReviewEnrollmentPaymentEvent>,
+    #[serde(rename = "FBALiquidationEventList")]
+    pub fba_liquidation_event_list: Vec<FbaLiquidationEvent>,
+    pub coupon_payment_event_list: Vec<CouponPaymentEvent>,
+    pub imaging_services_fee_event_list: Vec<ImagingServicesFeeEvent>,
+    pub network_commingling_transaction_event_list: Vec<NetworkComminglingTransactionEvent>,
+    pub affordability_expense_event_list: Vec<AffordabilityExpenseEvent>,
+    pub affordability_expense_reversal_event_list: Vec<AffordabilityExpenseEvent>,
+    pub removal_shipment_event_list: Vec<RemovalShipmentEvent>,
+    pub removal_shipment_adjustment_event_list: Vec<RemovalShipmentAdjustmentEvent>,
+    pub trial_shipment_event_list: Vec<TrialShipmentEvent>,
+    #[serde(rename = "TDSReimbursementEventList")]
+    pub tds_reimbursement_event_list: Vec<TdsReimbursementEvent>,
+    pub adhoc_disbursement_event_list: Vec<AdhocDisbursementEvent>,
+    pub tax_withholding_event_list: Vec<TaxWithholdingEvent>,
+    pub charge_refund_event_list: Vec<ChargeRefundEvent>,
+    pub failed_adhoc_disbursement_event_list: Vec<FailedAdhocDisbursementEvent>,
+    pub value_added_service_charge_event_list: Vec<ValueAddedServiceChargeEvent>,
+    pub capacity_reservation_billing_event_list: Vec<CapacityReservationBillingEvent>,
+}
+impl FinancialEvents {
+    /// Appends the events of another page to this one.
+    pub fn merge(&mut self, other: FinancialEvents) {
+        self.shipment_event_list.extend(other.shipment_event_list);
+        self.shipment_settle_event_list.extend(other.shipment_settle_event_list);
+        self.refund_event_list.extend(other.refund_event_list);
+        self.guarantee_claim_event_list.extend(other.guarantee_claim_event_list);
+        self.chargeback_event_list.extend(other.chargeback_event_list);
+        self.pay_with_amazon_event_list.extend(other.pay_with_amazon_event_list);
+        self.service_provider_credit_event_list.extend(other.service_provider_credit_event_list);
+        self.retrocharge_event_list.extend(other.retrocharge_event_list);
+        self.rental_transaction_event_list.extend(other.rental_transaction_event_list);
+        self.product_ads_payment_event_list.extend(other.product_ads_payment_event_list);
+        self.service_fee_event_list.extend(other.service_fee_event_list);
+        self.seller_deal_payment_event_list.extend(other.seller_deal_payment_event_list);
+        self.debt_recovery_event_list.extend(other.debt_recovery_event_list);
+        self.loan_servicing_event_list.extend(other.loan_servicing_event_list);
+        self.adjustment_event_list.extend(other.adjustment_event_list);
+        self.safet_reimbursement_event_list.extend(other.safet_reimbursement_event_list);
+        self.seller_review_enrollment_payment_event_list.extend(other.seller_review_enrollment_payment_event_list);
+        self.fba_liquidation_event_list.extend(other.fba_liquidation_event_list);
+        self.coupon_payment_event_list.extend(other.coupon_payment_event_list);
+        self.imaging_services_fee_event_list.extend(other.imaging_services_fee_event_list);
+        self.network_commingling_transaction_event_list.extend(other.network_commingling_transaction_event_list);
+        self.affordability_expense_event_list.extend(other.affordability_expense_event_list);
+        self.affordability_expense_reversal_event_list.extend(other.affordability_expense_reversal_event_list);
+        self.removal_shipment_event_list.extend(other.removal_shipment_event_list);
+        self.removal_shipment_adjustment_event_list.extend(other.removal_shipment_adjustment_event_list);
+        self.trial_shipment_event_list.extend(other.trial_shipment_event_list);
+        self.tds_reimbursement_event_list.extend(other.tds_reimbursement_event_list);
+        self.adhoc_disbursement_event_list.extend(other.adhoc_disbursement_event_list);
+        self.tax_withholding_event_list.extend(other.tax_withholding_event_list);
+        self.charge_refund_event_list.extend(other.charge_refund_event_list);
+        self.failed_adhoc_disbursement_event_list.extend(other.failed_adhoc_disbursement_event_list);
+        self.value_added_service_charge_event_list.extend(other.value_added_service_charge_event_list);
+        self.capacity_reservation_billing_event_list.extend(other.capacity_reservation_billing_event_list);
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ListFinancialEventGroupsPayload {
+    pub next_token: Option<String>,
+    pub financial_event_group_list: Vec<FinancialEventGroup>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ListFinancialEventsPayload {
+    pub next_token: Option<String>,
+    pub financial_events: FinancialEvents,
+}
+
+/// The paged request behind `Finances::financial_event_groups`.
+#[derive(Debug, Clone)]
+pub struct FinancialEventGroupsRequest {
+    pub max_results_per_page: Option<u32>,
+    pub started_before: Option<String>,
+    pub started_after: Option<String>,
+}
+impl PagedRequest for FinancialEventGroupsRequest {
+    type Item = FinancialEventGroup;
+
+    async fn fetch_page(&mut self, client: &mut Client, next_token: Option<String>) -> Result<Page<FinancialEventGroup>, Errors> {
+        let payload = Finances::list_financial_event_groups(
+            client,
+            self.max_results_per_page,
+            self.started_before.clone(),
+            self.started_after.clone(),
+            next_token,
+        )
+        .await?;
+        Ok(Page { items: payload.financial_event_group_list, next_token: payload.next_token })
+    }
+}
+
+/// Which financial events a `FinancialEventsRequest` lists.
+#[derive(Debug, Clone)]
+pub enum FinancialEventsScope {
+    /// All events posted in the date window.
+    All,
+    /// Events of one financial event group.
+    Group(String),
+    /// Events of one order. The date window is ignored.
+    Order(String),
+}
+
+/// The paged request behind `Finances::financial_events`. Every page yields one `FinancialEvents`.
+#[derive(Debug, Clone)]
+pub struct FinancialEventsRequest {
+    pub scope: FinancialEventsScope,
+    pub max_results_per_page: Option<u32>,
+    pub posted_after: Option<String>,
+    pub posted_before: Option<String>,
+}
+impl PagedRequest for FinancialEventsRequest {
+    type Item = FinancialEvents;
+
+    async fn fetch_page(&mut self, client: &mut Client, next_token: Option<String>) -> Result<Page<FinancialEvents>, Errors> {
+        let payload = match &self.scope {
+            FinancialEventsScope::All => {
+                Finances::list_financial_events(client, self.max_results_per_page, self.posted_after.clone(), self.posted_before.clone(), next_token).await?
+            }
+            FinancialEventsScope::Group(group_id) => {
+                Finances::list_financial_events_by_group_id(client, group_id, self.max_results_per_page, self.posted_after.clone(), self.posted_before.clone(), next_token).await?
+            }
+            FinancialEventsScope::Order(order_id) => {
+                Finances::list_financial_events_by_order_id(client, order_id, self.max_results_per_page, next_token).await?
+            }
+        };
+        Ok(Page { items: vec![payload.financial_events], next_token: payload.next_token })
+    }
+}
+
+fn check_page_size(max_results_per_page: Option<u32>) -> Result<(), Errors> {
+    match max_results_per_page {
+        Some(size) if !(1..=100).contains(&size) => Err(Errors::CustomError("MaxResultsPerPage must be between 1 and 100".to_string())),
+        _ => Ok(()),
+    }
+}
+
+fn events_params(
+    max_results_per_page: Option<u32>,
+    posted_after: Option<String>,
+    posted_before: Option<String>,
+    next_token: Option<String>,
+) -> Vec<(String, String)> {
+    let mut params: Vec<(String, String)> = Vec::new();
+    if let Some(size) = max_results_per_page {
+        params.push(("MaxResultsPerPage".to_string(), size.to_string()));
+    }
+    if let Some(date) = posted_after {
+        params.push(("PostedAfter".to_string(), date));
+    }
+    if let Some(date) = posted_before {
+        params.push(("PostedBefore".to_string(), date));
+    }
+    if let Some(token) = next_token {
+        params.push(("NextToken".to_string(), token));
+    }
+    params
+}
+
+impl Finances {
+    /// Returns financial event groups for a given date range. It may take up to 48 hours for
+    /// orders to appear in your financial events.
+    ///
+    /// Rate (requests per second): 0.5
+    ///
+    /// Burst: 30
+    ///
+    /// # Parameters
+    /// - `max_results_per_page` (optional): The maximum number of results to return per page. Minimum: 1, Maximum: 100, Default: 10.
+    /// - `started_before` (optional): Groups opened before this date and time, in ISO 8601 format. Must be after `started_after` and more than two minutes before the time of the request.
+    /// - `started_after` (optional): Groups opened after this date and time, in ISO 8601 format. Must be no later than two minutes before the request was submitted.
+    /// - `next_token` (optional): A string token returned in the response of your previous request.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `ListFinancialEventGroupsPayload`.
+    pub async fn list_financial_event_groups(
+        client: &mut Client,
+        max_results_per_page: Option<u32>,
+        started_before: Option<String>,
+        started_after: Option<String>,
+        next_token: Option<String>,
+    ) -> Result<ListFinancialEventGroupsPayload, Errors> {
+        check_page_size(max_results_per_page)?;
+
+        let mut params: Vec<(String, String)> = Vec::new();
+        if let Some(size) = max_results_per_page {
+            params.push(("MaxResultsPerPage".to_string(), size.to_string()));
+        }
+        if let Some(date) = started_before {
+            params.push(("FinancialEventGroupStartedBefore".to_string(), date));
+        }
+        if let Some(date) = started_after {
+            params.push(("FinancialEventGroupStartedAfter".to_string(), date));
+        }
+        if let Some(token) = next_token {
+            params.push(("NextToken".to_string(), token));
+        }
+
+        parse_payload(client.make_request("/finances/v0/financialEventGroups", Method::GET, Some(params)).await?).await
+    }
+
+    /// Returns financial events for the specified data range. Orders from the last 48 hours might
+    /// not be included.
+    ///
+    /// Rate (requests per second): 0.5
+    ///
+    /// Burst: 30
+    ///
+    /// # Parameters
+    /// - `max_results_per_page` (optional): The maximum number of results to return per page. Minimum: 1, Maximum: 100, Default: 100.
+    /// - `posted_after` (optional): Events posted after (and including) this date, in ISO 8601 format. No earlier than two years before the request.
+    /// - `posted_before` (optional): Events posted before (but not including) this date, in ISO 8601 format. More than two minutes before the request.
+    /// - `next_token` (optional): A string token returned in the response of your previous request.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `ListFinancialEventsPayload`.
+    pub async fn list_financial_events(
+        client: &mut Client,
+        max_results_per_page: Option<u32>,
+        posted_after: Option<String>,
+        posted_before: Option<String>,
+        next_token: Option<String>,
+    ) -> Result<ListFinancialEventsPayload, Errors> {
+        check_page_size(max_results_per_page)?;
+        let params = events_params(max_results_per_page, posted_after, posted_before, next_token);
+
+        parse_payload(client.make_request("/finances/v0/financialEvents", Method::GET, Some(params)).await?).await
+    }
+
+    /// Returns all financial events for the specified financial event group.
+    ///
+    /// Rate (requests per second): 0.5
+    ///
+    /// Burst: 30
+    ///
+    /// # Parameters
+    /// - `event_group_id`: The identifier of the financial event group to which the events belong.
+    /// - `max_results_per_page` (optional): Minimum: 1, Maximum: 100, Default: 100.
+    /// - `posted_after` (optional): Events posted after (and including) this date, in ISO 8601 format.
+    /// - `posted_before` (optional): Events posted before (but not including) this date, in ISO 8601 format.
+    /// - `next_token` (optional): A string token returned in the response of your previous request.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `ListFinancialEventsPayload`.
+    pub async fn list_financial_events_by_group_id(
+        client: &mut Client,
+        event_group_id: &str,
+        max_results_per_page: Option<u32>,
+        posted_after: Option<String>,
+        posted_before: Option<String>,
+        next_token: Option<String>,
+    ) -> Result<ListFinancialEventsPayload, Errors> {
+        check_page_size(max_results_per_page)?;
+        let endpoint = format!("/finances/v0/financialEventGroups/{}/financialEvents", event_group_id);
+        let params = events_params(max_results_per_page, posted_after, posted_before, next_token);
+
+        parse_payload(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Returns all financial events for the specified order.
+    ///
+    /// Rate (requests per second): 0.5
+    ///
+    /// Burst: 30
+    ///
+    /// # Parameters
+    /// - `order_id`: An Amazon-defined order identifier, in 3-7-7 format.
+    /// - `max_results_per_page` (optional): Minimum: 1, Maximum: 100, Default: 100.
+    /// - `next_token` (optional): A string token returned in the response of your previous request.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `ListFinancialEventsPayload`.
+    pub async fn list_financial_events_by_order_id(
+        client: &mut Client,
+        order_id: &str,
+        max_results_per_page: Option<u32>,
+        next_token: Option<String>,
+    ) -> Result<ListFinancialEventsPayload, Errors> {
+        check_page_size(max_results_per_page)?;
+        let endpoint = format!("/finances/v0/orders/{}/financialEvents", order_id);
+        let params = events_params(max_results_per_page, None, None, next_token);
+
+        parse_payload(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Pages through `list_financial_event_groups`, following the `NextToken` automatically.
+    pub fn financial_event_groups(
+        started_after: Option<String>,
+        started_before: Option<String>,
+    ) -> Paginator<FinancialEventGroupsRequest> {
+        Paginator::new(
+            FinancialEventGroupsRequest { max_results_per_page: Some(100), started_before, started_after },
+            FINANCES_INTERVAL,
+        )
+    }
+
+    /// Pages through the financial events of `scope`, following the `NextToken` automatically.
+    pub fn financial_events(
+        scope: FinancialEventsScope,
+        posted_after: Option<String>,
+        posted_before: Option<String>,
+    ) -> Paginator<FinancialEventsRequest> {
+        Paginator::new(
+            FinancialEventsRequest { scope, max_results_per_page: Some(100), posted_after, posted_before },
+            FINANCES_INTERVAL,
+        )
+    }
+
+    /// Fetches every page of financial events of `scope` and merges them into one `FinancialEvents`.
+    pub async fn list_all_financial_events(
+        client: &mut Client,
+        scope: FinancialEventsScope,
+        posted_after: Option<String>,
+        posted_before: Option<String>,
+    ) -> Result<FinancialEvents, Errors> {
+        let mut events = FinancialEvents::default();
+        for page in Self::financial_events(scope, posted_after, posted_before).collect_all(client).await? {
+            events.merge(page);
+        }
+        Ok(events)
+    }
+}
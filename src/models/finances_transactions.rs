@@ -0,0 +1,273 @@
+//! The 2024-06-19 version of the Finances API, which returns one unified `Transaction` model
+//! instead of the v0 financial event lists.
+use std::time::Duration;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use crate::error_handling::Errors;
+use crate::general::{parse_payload, Client, CountryMarketplace};
+use crate::models::finances::Finances;
+use crate::pagination::{Page, PagedRequest, Paginator};
+
+/// listTransactions allows 0.5 requests per second.
+const TRANSACTIONS_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A currency type and amount.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Currency {
+    pub currency_amount: Option<f64>,
+    pub currency_code: Option<String>,
+}
+impl Currency {
+    pub fn amount(&self) -> f64 {
+        self.currency_amount.unwrap_or_default()
+    }
+}
+
+/// The type of a related identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RelatedIdentifierName {
+    OrderId,
+    ShipmentId,
+    FinancialEventGroupId,
+    RefundId,
+    InvoiceId,
+    DisbursementId,
+    TransferId,
+    DeferredTransactionId,
+    ReleaseTransactionId,
+    SettlementId,
+    #[serde(other)]
+    Other,
+}
+
+/// An identifier related to a transaction, e.g. the Amazon order ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedIdentifier {
+    pub related_identifier_name: RelatedIdentifierName,
+    pub related_identifier_value: Option<String>,
+}
+
+/// An identifier related to an item of a transaction, e.g. the order item ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemRelatedIdentifier {
+    /// `ORDER_ADJUSTMENT_ITEM_ID`, `COUPON_ID`, `REMOVAL_SHIPMENT_ITEM_ID` or `TRANSACTION_ID`.
+    pub item_related_identifier_name: String,
+    pub item_related_identifier_value: Option<String>,
+}
+
+/// A part of a transaction amount, e.g. `ProductCharges` or `AmazonFees`. Breakdowns nest.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Breakdown {
+    pub breakdown_type: Option<String>,
+    pub breakdown_amount: Option<Currency>,
+    pub breakdowns: Vec<Breakdown>,
+}
+impl Breakdown {
+    /// This breakdown followed by all of its nested breakdowns, depth first.
+    pub fn flatten(&self) -> Vec<&Breakdown> {
+        let mut all = vec![self];
+        for child in &self.breakdowns {
+            all.extend(child.flatten());
+        }
+        all
+    }
+    /// The nested breakdowns without children, i.e. the ones that carry the actual amounts.
+    pub fn leaves(&self) -> Vec<&Breakdown> {
+        self.flatten().into_iter().filter(|b| b.breakdowns.is_empty()).collect()
+    }
+}
+
+/// Additional information about a transaction or item, depending on its `contextType`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "contextType")]
+pub enum Context {
+    #[serde(rename_all = "camelCase")]
+    ProductContext {
+        asin: Option<String>,
+        sku: Option<String>,
+        quantity_shipped: Option<i64>,
+        fulfillment_network: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    AmazonPayContext {
+        store_name: Option<String>,
+        order_type: Option<String>,
+        channel: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    PaymentsContext {
+        payment_type: Option<String>,
+        payment_method: Option<String>,
+        payment_reference: Option<String>,
+        payment_date: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    DeferredContext {
+        deferral_reason: Option<String>,
+        maturity_date: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    BusinessContext {
+        store_name: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    TimeRangeContext {
+        start_time: Option<String>,
+        end_time: Option<String>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SellingPartnerMetadata {
+    pub selling_partner_id: Option<String>,
+    pub account_type: Option<String>,
+    pub marketplace_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MarketplaceDetails {
+    pub marketplace_id: Option<String>,
+    pub marketplace_name: Option<String>,
+}
+
+/// An item of a transaction, e.g. one order item.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Item {
+    pub description: Option<String>,
+    pub related_identifiers: Vec<ItemRelatedIdentifier>,
+    pub total_amount: Option<Currency>,
+    pub breakdowns: Vec<Breakdown>,
+    pub contexts: Vec<Context>,
+}
+impl Item {
+    pub fn sku(&self) -> Option<&str> {
+        self.contexts.iter().find_map(|c| match c {
+            Context::ProductContext { sku, .. } => sku.as_deref(),
+            _ => None,
+        })
+    }
+}
+
+/// All the information related to a transaction.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Transaction {
+    pub selling_partner_metadata: Option<SellingPartnerMetadata>,
+    pub related_identifiers: Vec<RelatedIdentifier>,
+    /// e.g. `Shipment`, `Refund`, `ServiceFee`, `Adjustment`.
+    pub transaction_type: Option<String>,
+    pub transaction_id: Option<String>,
+    /// `DEFERRED`, `RELEASED` or `DEFERRED_RELEASED`.
+    pub transaction_status: Option<String>,
+    pub description: Option<String>,
+    pub posted_date: Option<String>,
+    pub total_amount: Option<Currency>,
+    pub marketplace_details: Option<MarketplaceDetails>,
+    pub items: Vec<Item>,
+    pub contexts: Vec<Context>,
+    pub breakdowns: Vec<Breakdown>,
+}
+impl Transaction {
+    /// The value of the first related identifier of type `name`.
+    pub fn related_identifier(&self, name: RelatedIdentifierName) -> Option<&str> {
+        self.related_identifiers
+            .iter()
+            .find(|r| r.related_identifier_name == name)
+            .and_then(|r| r.related_identifier_value.as_deref())
+    }
+    pub fn order_id(&self) -> Option<&str> {
+        self.related_identifier(RelatedIdentifierName::OrderId)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListTransactionsPayload {
+    pub next_token: Option<String>,
+    pub transactions: Vec<Transaction>,
+}
+
+/// The paged request behind `Finances::transactions`.
+#[derive(Debug, Clone)]
+pub struct ListTransactionsRequest {
+    pub posted_after: String,
+    pub posted_before: Option<String>,
+    pub marketplace_id: Option<String>,
+}
+impl PagedRequest for ListTransactionsRequest {
+    type Item = Transaction;
+
+    async fn fetch_page(&mut self, client: &mut Client, next_token: Option<String>) -> Result<Page<Transaction>, Errors> {
+        let payload = Finances::list_transactions(client, &self.posted_after, self.posted_before.clone(), self.marketplace_id.clone(), next_token).await?;
+        Ok(Page { items: payload.transactions, next_token: payload.next_token })
+    }
+}
+
+impl Finances {
+    /// Returns transactions for the given parameters. Financial events might not include orders
+    /// from the last 48 hours.
+    ///
+    /// Rate (requests per second): 0.5
+    ///
+    /// Burst: 10
+    ///
+    /// # Parameters
+    /// - `posted_after`: Transactions posted after (and including) this date, in ISO 8601 format. No earlier than two years before the request.
+    /// - `posted_before` (optional): Transactions posted before (but not including) this date, in ISO 8601 format. Must be within 180 days of `posted_after`.
+    /// - `marketplace_id` (optional): Only return transactions of this marketplace.
+    /// - `next_token` (optional): A string token returned in the response of your previous request.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `ListTransactionsPayload`.
+    pub async fn list_transactions(
+        client: &mut Client,
+        posted_after: &str,
+        posted_before: Option<String>,
+        marketplace_id: Option<String>,
+        next_token: Option<String>,
+    ) -> Result<ListTransactionsPayload, Errors> {
+        let mut params: Vec<(String, String)> = vec![("postedAfter".to_string(), posted_after.to_string())];
+        if let Some(date) = posted_before {
+            params.push(("postedBefore".to_string(), date));
+        }
+        if let Some(id) = marketplace_id {
+            params.push(("marketplaceId".to_string(), id));
+        }
+        if let Some(token) = next_token {
+            params.push(("nextToken".to_string(), token));
+        }
+
+        parse_payload(client.make_request("/finances/2024-06-19/transactions", Method::GET, Some(params)).await?).await
+    }
+
+    /// Pages through `list_transactions`, following the `nextToken` automatically.
+    ///
+    /// # Parameters
+    /// - `posted_after`: Transactions posted after (and including) this date, in ISO 8601 format.
+    /// - `posted_before` (optional): Transactions posted before this date, in ISO 8601 format.
+    /// - `marketplace` (optional): Only return transactions of this marketplace.
+    pub fn transactions(
+        posted_after: &str,
+        posted_before: Option<String>,
+        marketplace: Option<CountryMarketplace>,
+    ) -> Paginator<ListTransactionsRequest> {
+        Paginator::new(
+            ListTransactionsRequest {
+                posted_after: posted_after.to_string(),
+                posted_before,
+                marketplace_id: marketplace.map(|m| m.details().0.to_string()),
+            },
+            TRANSACTIONS_INTERVAL,
+        )
+    }
+}
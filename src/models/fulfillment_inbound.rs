@@ -0,0 +1,1029 @@
+//! The v2024-03-20 Fulfillment Inbound API, used to send inventory to Amazon's fulfillment
+//! network.
+//!
+//! Most steps of an inbound plan start an asynchronous operation. The operations here wait for
+//! that operation to finish before returning, so the next step can be called right away.
+use std::time::Duration;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::error_handling::Errors;
+use crate::general::{encode_path_segment, parse_payload, parse_response, Client, CountryMarketplace};
+use crate::poller::{Backoff, OperationHandle, PollStatus, Pollable};
+
+pub struct FulfillmentInbound;
+
+const BASE: &str = "/inbound/fba/2024-03-20";
+/// Labels are still served by the v0 API.
+const BASE_V0: &str = "/fba/inbound/v0";
+/// Inbound operations usually finish within seconds, but generating options can take minutes.
+const OPERATION_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Address {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub company_name: Option<String>,
+    pub address_line1: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_line2: Option<String>,
+    pub city: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_or_province_code: Option<String>,
+    pub country_code: String,
+    pub postal_code: String,
+    #[serde(default)]
+    pub phone_number: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+/// Who prepares or labels the items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Owner {
+    Amazon,
+    Seller,
+    None,
+}
+
+/// An item of an inbound plan, or of a box.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemInput {
+    pub msku: String,
+    pub quantity: u32,
+    pub prep_owner: Owner,
+    pub label_owner: Owner,
+    /// The expiration date of the item, in `YYYY-MM-DD` format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manufacturing_lot_code: Option<String>,
+}
+impl ItemInput {
+    pub fn new(msku: &str, quantity: u32, prep_owner: Owner, label_owner: Owner) -> Self {
+        ItemInput { msku: msku.to_string(), quantity, prep_owner, label_owner, expiration: None, manufacturing_lot_code: None }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInboundPlanRequest {
+    pub destination_marketplaces: Vec<String>,
+    pub items: Vec<ItemInput>,
+    pub source_address: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+impl CreateInboundPlanRequest {
+    pub fn new(marketplaces: &[CountryMarketplace], items: Vec<ItemInput>, source_address: Address) -> Self {
+        CreateInboundPlanRequest {
+            destination_marketplaces: marketplaces.iter().map(|m| m.details().0.to_string()).collect(),
+            items,
+            source_address,
+            name: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInboundPlanResponse {
+    pub inbound_plan_id: String,
+    pub operation_id: String,
+}
+
+/// The result of `FulfillmentInbound::create_inbound_plan`. The plan exists on Amazon's side
+/// even when waiting for it failed, so its IDs are kept to cancel or resume it.
+#[derive(Debug)]
+pub struct CreatedInboundPlan {
+    pub created: CreateInboundPlanResponse,
+    /// How waiting for the creation operation ended.
+    pub operation: Result<InboundOperationStatus, Errors>,
+}
+impl CreatedInboundPlan {
+    pub fn inbound_plan_id(&self) -> &str {
+        &self.created.inbound_plan_id
+    }
+    /// The created plan, or the error of the operation with the plan ID in its message.
+    pub fn into_result(self) -> Result<CreateInboundPlanResponse, Errors> {
+        match self.operation {
+            Ok(_) => Ok(self.created),
+            Err(e) => Err(Errors::OperationFailed(format!("Inbound plan {}: {}", self.created.inbound_plan_id, e))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OperationResponse {
+    operation_id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OperationStatus {
+    Success,
+    Failed,
+    InProgress,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OperationProblem {
+    pub code: String,
+    pub message: String,
+    pub details: Option<String>,
+    /// `WARNING` or `ERROR`.
+    pub severity: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InboundOperationStatus {
+    pub operation: String,
+    pub operation_id: String,
+    #[serde(default)]
+    pub operation_problems: Vec<OperationProblem>,
+    pub operation_status: OperationStatus,
+}
+
+/// An asynchronous inbound operation, polled with `getInboundOperationStatus`.
+pub struct InboundOperation {
+    pub operation_id: String,
+}
+impl Pollable for InboundOperation {
+    type Output = InboundOperationStatus;
+
+    async fn poll(&mut self, client: &mut Client) -> Result<PollStatus<InboundOperationStatus>, Errors> {
+        let status = FulfillmentInbound::get_inbound_operation_status(client, &self.operation_id).await?;
+        match status.operation_status {
+            OperationStatus::Success => Ok(PollStatus::Done(status)),
+            OperationStatus::Failed => Err(Errors::OperationFailed(format!(
+                "Inbound operation {} ({}) failed: {}",
+                status.operation_id,
+                status.operation,
+                status.operation_problems.iter().map(|p| format!("{}: {}", p.code, p.message)).collect::<Vec<String>>().join("; ")
+            ))),
+            OperationStatus::InProgress => Ok(PollStatus::Pending("IN_PROGRESS".to_string())),
+        }
+    }
+}
+
+/// A currency amount, e.g. a fee.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Currency {
+    pub amount: f64,
+    pub code: String,
+}
+
+/// A fee or discount of an option.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Incentive {
+    pub description: String,
+    /// e.g. `Placement Services`.
+    pub target: String,
+    /// `FEE` or `DISCOUNT`.
+    #[serde(rename = "type")]
+    pub incentive_type: String,
+    pub value: Currency,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShippingConfiguration {
+    /// e.g. `GROUND_SMALL_PARCEL`, `FREIGHT_LTL`.
+    pub shipping_mode: Option<String>,
+    /// `AMAZON_PARTNERED_CARRIER` or `USE_YOUR_OWN_CARRIER`.
+    pub shipping_solution: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Pagination {
+    pub next_token: Option<String>,
+}
+
+/// A way to pack the items of a plan: which items go together into which packing group.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PackingOption {
+    pub packing_option_id: String,
+    pub packing_groups: Vec<String>,
+    /// `OFFERED`, `ACCEPTED` or `EXPIRED`.
+    pub status: String,
+    pub fees: Vec<Incentive>,
+    pub discounts: Vec<Incentive>,
+    pub expiration: Option<String>,
+    pub supported_shipping_configurations: Vec<ShippingConfiguration>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListPackingOptionsResponse {
+    pub packing_options: Vec<PackingOption>,
+    pub pagination: Option<Pagination>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrepInstruction {
+    pub fee: Option<Currency>,
+    pub prep_owner: Option<Owner>,
+    pub prep_type: Option<String>,
+}
+
+/// An item of a plan, as returned by Amazon.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Item {
+    pub asin: String,
+    pub fnsku: String,
+    pub msku: String,
+    pub quantity: u32,
+    pub label_owner: Option<Owner>,
+    pub prep_instructions: Vec<PrepInstruction>,
+    pub expiration: Option<String>,
+    pub manufacturing_lot_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListItemsResponse {
+    pub items: Vec<Item>,
+    pub pagination: Option<Pagination>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DimensionUnit {
+    IN,
+    CM,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dimensions {
+    pub length: f64,
+    pub width: f64,
+    pub height: f64,
+    pub unit_of_measurement: DimensionUnit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeightUnit {
+    LB,
+    KG,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Weight {
+    pub unit: WeightUnit,
+    pub value: f64,
+}
+
+/// How Amazon learns what is inside a box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ContentInformationSource {
+    /// The items of each box are given in `BoxInput::items`.
+    BoxContentProvided,
+    /// Amazon processes the box contents manually, for a fee.
+    ManualProcess,
+    /// The box contents are printed as 2D barcodes on the boxes.
+    #[serde(rename = "BARCODE_2D")]
+    Barcode2d,
+}
+
+/// A box, or `quantity` identical boxes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxInput {
+    pub content_information_source: ContentInformationSource,
+    pub dimensions: Dimensions,
+    pub weight: Weight,
+    /// The number of identical boxes.
+    pub quantity: u32,
+    /// The items of one box. Required with `BoxContentProvided`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<ItemInput>,
+}
+
+/// The boxes of one packing group (before placement) or one shipment (after placement).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageGroupingInput {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packing_group_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shipment_id: Option<String>,
+    pub boxes: Vec<BoxInput>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPackingInformationRequest {
+    pub package_groupings: Vec<PackageGroupingInput>,
+}
+
+/// A way to split the plan into shipments to Amazon warehouses.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PlacementOption {
+    pub placement_option_id: String,
+    pub shipment_ids: Vec<String>,
+    /// `OFFERED`, `ACCEPTED` or `EXPIRED`.
+    pub status: String,
+    pub fees: Vec<Incentive>,
+    pub discounts: Vec<Incentive>,
+    pub expiration: Option<String>,
+}
+impl PlacementOption {
+    /// The sum of the fees minus the sum of the discounts.
+    pub fn net_fee(&self) -> f64 {
+        self.fees.iter().map(|f| f.value.amount).sum::<f64>() - self.discounts.iter().map(|d| d.value.amount).sum::<f64>()
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListPlacementOptionsResponse {
+    pub placement_options: Vec<PlacementOption>,
+    pub pagination: Option<Pagination>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactInformation {
+    pub name: String,
+    pub phone_number: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+/// The transportation details of one shipment, used to generate transportation options.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipmentTransportationConfiguration {
+    pub shipment_id: String,
+    /// When the shipment is ready to be picked up, in ISO 8601 format.
+    pub ready_to_ship_window_start: String,
+    pub contact_information: Option<ContactInformation>,
+    /// Freight class and declared value, for less-than-truckload shipments.
+    pub freight_information: Option<Value>,
+    /// The pallets, for less-than-truckload shipments.
+    pub pallets: Vec<Value>,
+}
+impl ShipmentTransportationConfiguration {
+    fn to_body(&self) -> Value {
+        let mut body = json!({
+            "shipmentId": self.shipment_id,
+            "readyToShipWindow": { "start": self.ready_to_ship_window_start },
+        });
+        if let Some(contact) = &self.contact_information {
+            body["contactInformation"] = json!(contact);
+        }
+        if let Some(freight) = &self.freight_information {
+            body["freightInformation"] = freight.clone();
+        }
+        if !self.pallets.is_empty() {
+            body["pallets"] = json!(self.pallets);
+        }
+        body
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Carrier {
+    pub name: Option<String>,
+    pub alpha_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Quote {
+    pub cost: Currency,
+    pub expiration: Option<String>,
+    pub voidable_until: Option<String>,
+}
+
+/// A way to ship one shipment, e.g. small parcel with an Amazon partnered carrier.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TransportationOption {
+    pub transportation_option_id: String,
+    pub shipment_id: String,
+    pub shipping_mode: String,
+    pub shipping_solution: String,
+    pub carrier: Carrier,
+    pub quote: Option<Quote>,
+    /// Conditions that must be met before the option can be confirmed, e.g. `CONFIRMED_DELIVERY_WINDOW`.
+    pub preconditions: Vec<String>,
+    pub carrier_appointment: Option<Value>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListTransportationOptionsResponse {
+    pub transportation_options: Vec<TransportationOption>,
+    pub pagination: Option<Pagination>,
+}
+
+/// A window in which a shipment can be delivered to the warehouse.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DeliveryWindowOption {
+    pub delivery_window_option_id: String,
+    pub start_date: String,
+    pub end_date: String,
+    /// e.g. `AVAILABLE`, `CONGESTED`.
+    pub availability_type: String,
+    pub valid_until: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListDeliveryWindowOptionsResponse {
+    pub delivery_window_options: Vec<DeliveryWindowOption>,
+    pub pagination: Option<Pagination>,
+}
+
+/// The transportation option chosen for a shipment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransportationSelection {
+    pub shipment_id: String,
+    pub transportation_option_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact_information: Option<ContactInformation>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OptionSummary {
+    pub packing_option_id: Option<String>,
+    pub placement_option_id: Option<String>,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShipmentSummary {
+    pub shipment_id: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InboundPlan {
+    pub inbound_plan_id: String,
+    pub name: String,
+    /// `ACTIVE`, `VOIDED`, `SHIPPED` or `ERRORED`.
+    pub status: String,
+    pub created_at: Option<String>,
+    pub last_updated_at: Option<String>,
+    pub marketplace_ids: Vec<String>,
+    pub source_address: Option<Address>,
+    pub packing_options: Vec<OptionSummary>,
+    pub placement_options: Vec<OptionSummary>,
+    pub shipments: Vec<ShipmentSummary>,
+}
+/// A shipment of a confirmed placement option.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Shipment {
+    pub shipment_id: String,
+    /// The `FBA...` identifier printed on labels and used by the v0 API.
+    pub shipment_confirmation_id: Option<String>,
+    pub name: Option<String>,
+    pub placement_option_id: String,
+    pub status: Option<String>,
+    pub amazon_reference_id: Option<String>,
+    pub destination: Option<Value>,
+    pub source: Option<Value>,
+    pub selected_transportation_option_id: Option<String>,
+    pub selected_delivery_window: Option<Value>,
+    pub tracking_details: Option<Value>,
+}
+
+/// A box of a shipment, as returned by Amazon.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShipmentBox {
+    /// The carton ID printed on the box label.
+    pub box_id: Option<String>,
+    pub package_id: String,
+    pub content_information_source: Option<ContentInformationSource>,
+    pub dimensions: Option<Dimensions>,
+    pub weight: Option<Weight>,
+    pub quantity: Option<u32>,
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListShipmentBoxesResponse {
+    pub boxes: Vec<ShipmentBox>,
+    pub pagination: Option<Pagination>,
+}
+
+/// The paper a label is printed on.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+pub enum PageType {
+    PackageLabel_Letter_2,
+    PackageLabel_Letter_4,
+    PackageLabel_Letter_6,
+    PackageLabel_Letter_6_CarrierLeft,
+    PackageLabel_A4_2,
+    PackageLabel_A4_4,
+    PackageLabel_Plain_Paper,
+    PackageLabel_Plain_Paper_CarrierBottom,
+    PackageLabel_Thermal,
+    PackageLabel_Thermal_Unified,
+    PackageLabel_Thermal_NonPCP,
+    PackageLabel_Thermal_No_Carrier_Rotation,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+pub enum LabelType {
+    /// One label per carton, printed with the box contents as a 2D barcode.
+    BARCODE_2D,
+    /// One label per carton ID in `package_labels_to_print`.
+    UNIQUE,
+    /// Pallet labels, for less-than-truckload shipments.
+    PALLET,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LabelDownloadURL {
+    #[serde(rename = "DownloadURL")]
+    pub download_url: String,
+}
+
+fn page_params(page_size: Option<u32>, pagination_token: Option<String>) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    if let Some(size) = page_size {
+        params.push(("pageSize".to_string(), size.to_string()));
+    }
+    if let Some(token) = pagination_token {
+        params.push(("paginationToken".to_string(), token));
+    }
+    params
+}
+
+impl FulfillmentInbound {
+    /// Waits for an inbound operation to finish.
+    ///
+    /// # Responses
+    /// - Returns the final `InboundOperationStatus`, or `Errors::OperationFailed` with the
+    ///   operation's problems.
+    pub async fn wait_for_operation(client: &mut Client, operation_id: &str) -> Result<InboundOperationStatus, Errors> {
+        OperationHandle::new(InboundOperation { operation_id: operation_id.to_string() })
+            .backoff(Backoff::new(Duration::from_secs(1), Duration::from_secs(15), 1.5))
+            .timeout(OPERATION_TIMEOUT)
+            .wait(client)
+            .await
+    }
+
+    async fn post_and_wait(client: &mut Client, endpoint: &str, body: Option<Value>) -> Result<InboundOperationStatus, Errors> {
+        let body = body.map(|b| b.to_string()).unwrap_or_default();
+        let response: OperationResponse = parse_response(
+            client.make_request_w_body(endpoint, Method::POST, None::<Vec<(String, String)>>, body).await?,
+        )
+        .await?;
+        Self::wait_for_operation(client, &response.operation_id).await
+    }
+
+    /// Creates an inbound plan and waits until it is created.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    ///
+    /// # Parameters
+    /// - `request`: The marketplaces, items and source address of the plan.
+    ///
+    /// # Responses
+    /// - **202 (Accepted):** Returns the `inboundPlanId` together with how the creation operation
+    ///   ended. Only a plan that wasn't created at all is returned as `Err`.
+    pub async fn create_inbound_plan(client: &mut Client, request: CreateInboundPlanRequest) -> Result<CreatedInboundPlan, Errors> {
+        let endpoint = format!("{}/inboundPlans", BASE);
+        let created: CreateInboundPlanResponse = parse_response(
+            client
+                .make_request_w_body(&endpoint, Method::POST, None::<Vec<(String, String)>>, serde_json::to_string(&request)?)
+                .await?,
+        )
+        .await?;
+        let operation = Self::wait_for_operation(client, &created.operation_id).await;
+        Ok(CreatedInboundPlan { created, operation })
+    }
+
+    /// Fetches the top level information about an inbound plan.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 6
+    ///
+    /// # Parameters
+    /// - `inbound_plan_id`: Identifier of an inbound plan.
+    pub async fn get_inbound_plan(client: &mut Client, inbound_plan_id: &str) -> Result<InboundPlan, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}", BASE, inbound_plan_id);
+
+        parse_response(client.make_request(&endpoint, Method::GET, None::<Vec<(String, String)>>).await?).await
+    }
+
+    /// Cancels an inbound plan and waits until it is cancelled.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    pub async fn cancel_inbound_plan(client: &mut Client, inbound_plan_id: &str) -> Result<InboundOperationStatus, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/cancellation", BASE, inbound_plan_id);
+        let response: OperationResponse =
+            parse_response(client.make_request(&endpoint, Method::PUT, None::<Vec<(String, String)>>).await?).await?;
+
+        Self::wait_for_operation(client, &response.operation_id).await
+    }
+
+    /// Gets the status of the processing of an asynchronous API call.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 6
+    ///
+    /// # Parameters
+    /// - `operation_id`: Identifier of an asynchronous operation.
+    pub async fn get_inbound_operation_status(client: &mut Client, operation_id: &str) -> Result<InboundOperationStatus, Errors> {
+        let endpoint = format!("{}/operations/{}", BASE, operation_id);
+
+        parse_response(client.make_request(&endpoint, Method::GET, None::<Vec<(String, String)>>).await?).await
+    }
+
+    /// Generates the packing options of an inbound plan and waits until they are available.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    pub async fn generate_packing_options(client: &mut Client, inbound_plan_id: &str) -> Result<InboundOperationStatus, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/packingOptions", BASE, inbound_plan_id);
+
+        Self::post_and_wait(client, &endpoint, None).await
+    }
+
+    /// Retrieves a list of all packing options for an inbound plan.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 6
+    ///
+    /// # Parameters
+    /// - `inbound_plan_id`: Identifier of an inbound plan.
+    /// - `page_size` (optional): The number of packing options to return, 1 to 20.
+    /// - `pagination_token` (optional): The token from the previous page.
+    pub async fn list_packing_options(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        page_size: Option<u32>,
+        pagination_token: Option<String>,
+    ) -> Result<ListPackingOptionsResponse, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/packingOptions", BASE, inbound_plan_id);
+        let params = page_params(page_size, pagination_token);
+
+        parse_response(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Retrieves the items of a packing group.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 6
+    ///
+    /// # Parameters
+    /// - `inbound_plan_id`: Identifier of an inbound plan.
+    /// - `packing_group_id`: Identifier of a packing group, from a `PackingOption`.
+    /// - `page_size` (optional): The number of items to return, 1 to 100.
+    /// - `pagination_token` (optional): The token from the previous page.
+    pub async fn list_packing_group_items(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        packing_group_id: &str,
+        page_size: Option<u32>,
+        pagination_token: Option<String>,
+    ) -> Result<ListItemsResponse, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/packingGroups/{}/items", BASE, inbound_plan_id, packing_group_id);
+        let params = page_params(page_size, pagination_token);
+
+        parse_response(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Retrieves the items of an inbound plan.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 6
+    pub async fn list_inbound_plan_items(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        page_size: Option<u32>,
+        pagination_token: Option<String>,
+    ) -> Result<ListItemsResponse, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/items", BASE, inbound_plan_id);
+        let params = page_params(page_size, pagination_token);
+
+        parse_response(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Confirms the packing option of an inbound plan and waits for the confirmation.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    pub async fn confirm_packing_option(client: &mut Client, inbound_plan_id: &str, packing_option_id: &str) -> Result<InboundOperationStatus, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/packingOptions/{}/confirmation", BASE, inbound_plan_id, packing_option_id);
+
+        Self::post_and_wait(client, &endpoint, None).await
+    }
+
+    /// Sets the box dimensions, weights and contents of an inbound plan, per packing group or
+    /// per shipment, and waits until they are accepted.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    pub async fn set_packing_information(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        request: &SetPackingInformationRequest,
+    ) -> Result<InboundOperationStatus, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/packingInformation", BASE, inbound_plan_id);
+
+        Self::post_and_wait(client, &endpoint, Some(json!(request))).await
+    }
+
+    /// Generates the placement options of an inbound plan and waits until they are available.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    ///
+    /// # Parameters
+    /// - `custom_placement` (optional): Warehouse and item assignments, only for sellers eligible for custom placement.
+    pub async fn generate_placement_options(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        custom_placement: Option<Vec<Value>>,
+    ) -> Result<InboundOperationStatus, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/placementOptions", BASE, inbound_plan_id);
+        let body = match custom_placement {
+            Some(placement) => json!({ "customPlacement": placement }),
+            None => json!({}),
+        };
+
+        Self::post_and_wait(client, &endpoint, Some(body)).await
+    }
+
+    /// Retrieves the placement options of an inbound plan.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 6
+    pub async fn list_placement_options(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        page_size: Option<u32>,
+        pagination_token: Option<String>,
+    ) -> Result<ListPlacementOptionsResponse, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/placementOptions", BASE, inbound_plan_id);
+        let params = page_params(page_size, pagination_token);
+
+        parse_response(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Confirms the placement option of an inbound plan, which creates its shipments, and waits
+    /// for the confirmation.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    pub async fn confirm_placement_option(client: &mut Client, inbound_plan_id: &str, placement_option_id: &str) -> Result<InboundOperationStatus, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/placementOptions/{}/confirmation", BASE, inbound_plan_id, placement_option_id);
+
+        Self::post_and_wait(client, &endpoint, None).await
+    }
+
+    /// Generates the transportation options of the shipments of a placement option and waits
+    /// until they are available.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    pub async fn generate_transportation_options(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        placement_option_id: &str,
+        configurations: &[ShipmentTransportationConfiguration],
+    ) -> Result<InboundOperationStatus, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/transportationOptions", BASE, inbound_plan_id);
+        let body = json!({
+            "placementOptionId": placement_option_id,
+            "shipmentTransportationConfigurations": configurations.iter().map(|c| c.to_body()).collect::<Vec<Value>>(),
+        });
+
+        Self::post_and_wait(client, &endpoint, Some(body)).await
+    }
+
+    /// Retrieves the transportation options of an inbound plan, for a placement option or a
+    /// single shipment.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 6
+    ///
+    /// # Parameters
+    /// - `placement_option_id` (optional): Only the options of this placement option. Either this or `shipment_id` is required.
+    /// - `shipment_id` (optional): Only the options of this shipment.
+    pub async fn list_transportation_options(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        placement_option_id: Option<&str>,
+        shipment_id: Option<&str>,
+        page_size: Option<u32>,
+        pagination_token: Option<String>,
+    ) -> Result<ListTransportationOptionsResponse, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/transportationOptions", BASE, inbound_plan_id);
+        let mut params = page_params(page_size, pagination_token);
+        if let Some(id) = placement_option_id {
+            params.push(("placementOptionId".to_string(), id.to_string()));
+        }
+        if let Some(id) = shipment_id {
+            params.push(("shipmentId".to_string(), id.to_string()));
+        }
+
+        parse_response(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Generates the delivery window options of a shipment and waits until they are available.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    pub async fn generate_delivery_window_options(client: &mut Client, inbound_plan_id: &str, shipment_id: &str) -> Result<InboundOperationStatus, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/shipments/{}/deliveryWindowOptions", BASE, inbound_plan_id, shipment_id);
+
+        Self::post_and_wait(client, &endpoint, None).await
+    }
+
+    /// Retrieves the delivery window options of a shipment.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 6
+    pub async fn list_delivery_window_options(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        shipment_id: &str,
+        page_size: Option<u32>,
+        pagination_token: Option<String>,
+    ) -> Result<ListDeliveryWindowOptionsResponse, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/shipments/{}/deliveryWindowOptions", BASE, inbound_plan_id, shipment_id);
+        let params = page_params(page_size, pagination_token);
+
+        parse_response(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Confirms the delivery window of a shipment and waits for the confirmation. Required
+    /// before confirming transportation for non-partnered carriers.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    pub async fn confirm_delivery_window_options(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        shipment_id: &str,
+        delivery_window_option_id: &str,
+    ) -> Result<InboundOperationStatus, Errors> {
+        let endpoint = format!(
+            "{}/inboundPlans/{}/shipments/{}/deliveryWindowOptions/{}/confirmation",
+            BASE, inbound_plan_id, shipment_id, encode_path_segment(delivery_window_option_id)
+        );
+
+        Self::post_and_wait(client, &endpoint, None).await
+    }
+
+    /// Fetches a shipment of an inbound plan.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 6
+    pub async fn get_shipment(client: &mut Client, inbound_plan_id: &str, shipment_id: &str) -> Result<Shipment, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/shipments/{}", BASE, inbound_plan_id, shipment_id);
+
+        parse_response(client.make_request(&endpoint, Method::GET, None::<Vec<(String, String)>>).await?).await
+    }
+
+    /// Retrieves the items of a shipment.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 6
+    pub async fn list_shipment_items(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        shipment_id: &str,
+        page_size: Option<u32>,
+        pagination_token: Option<String>,
+    ) -> Result<ListItemsResponse, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/shipments/{}/items", BASE, inbound_plan_id, shipment_id);
+        let params = page_params(page_size, pagination_token);
+
+        parse_response(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Retrieves the boxes of a shipment, with their carton IDs.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 6
+    ///
+    /// # Parameters
+    /// - `page_size` (optional): The number of boxes to return, 1 to 1000.
+    /// - `pagination_token` (optional): The token from the previous page.
+    pub async fn list_shipment_boxes(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        shipment_id: &str,
+        page_size: Option<u32>,
+        pagination_token: Option<String>,
+    ) -> Result<ListShipmentBoxesResponse, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/shipments/{}/boxes", BASE, inbound_plan_id, shipment_id);
+        let params = page_params(page_size, pagination_token);
+
+        parse_response(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Returns a URL to the package or pallet labels of a shipment, as a PDF.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 30
+    ///
+    /// # Parameters
+    /// - `shipment_confirmation_id`: The `shipmentConfirmationId` of the shipment.
+    /// - `page_type`: The paper to print on.
+    /// - `label_type`: The type of labels.
+    /// - `number_of_packages` (optional): The number of packages, for `BARCODE_2D` labels.
+    /// - `package_labels_to_print`: The carton IDs to print, for `UNIQUE` labels.
+    /// - `number_of_pallets` (optional): The number of pallets, for `PALLET` labels.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns the `DownloadURL` of the labels.
+    pub async fn get_labels(
+        client: &mut Client,
+        shipment_confirmation_id: &str,
+        page_type: PageType,
+        label_type: LabelType,
+        number_of_packages: Option<u32>,
+        package_labels_to_print: &[String],
+        number_of_pallets: Option<u32>,
+    ) -> Result<LabelDownloadURL, Errors> {
+        let endpoint = format!("{}/shipments/{}/labels", BASE_V0, shipment_confirmation_id);
+        let mut params = vec![
+            ("PageType".to_string(), page_type.to_string()),
+            ("LabelType".to_string(), label_type.to_string()),
+        ];
+        if let Some(n) = number_of_packages {
+            params.push(("NumberOfPackages".to_string(), n.to_string()));
+        }
+        if !package_labels_to_print.is_empty() {
+            params.push(("PackageLabelsToPrint".to_string(), package_labels_to_print.join(",")));
+        }
+        if let Some(n) = number_of_pallets {
+            params.push(("NumberOfPallets".to_string(), n.to_string()));
+        }
+
+        parse_payload(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Confirms the transportation option of each shipment and waits for the confirmation.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    pub async fn confirm_transportation_options(
+        client: &mut Client,
+        inbound_plan_id: &str,
+        selections: &[TransportationSelection],
+    ) -> Result<InboundOperationStatus, Errors> {
+        let endpoint = format!("{}/inboundPlans/{}/transportationOptions/confirmation", BASE, inbound_plan_id);
+
+        Self::post_and_wait(client, &endpoint, Some(json!({ "transportationSelections": selections }))).await
+    }
+}
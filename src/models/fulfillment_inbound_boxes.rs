@@ -0,0 +1,341 @@
+//! Box content and label helpers for inbound plans.
+//!
+//! Boxes are described by SKU and quantity; `build_package_grouping` fills in the owners,
+//! expiration and lot code from the plan's items and checks that every unit is packed exactly
+//! once before the payload is sent with `FulfillmentInbound::set_packing_information`.
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crate::error_handling::Errors;
+use crate::general::Client;
+use crate::models::fulfillment_inbound::{
+    BoxInput, ContentInformationSource, Dimensions, FulfillmentInbound, Item, ItemInput, LabelType, Owner,
+    PackageGroupingInput, PageType, Weight,
+};
+
+/// The maximum number of carton IDs in one `getLabels` call.
+const MAX_LABELS_PER_REQUEST: usize = 999;
+
+/// One box, or `quantity` identical boxes, and the SKUs packed in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxContent {
+    pub dimensions: Dimensions,
+    pub weight: Weight,
+    /// The SKUs and units in one box.
+    pub items: Vec<(String, u32)>,
+    /// The number of identical boxes.
+    pub quantity: u32,
+}
+impl BoxContent {
+    pub fn new(dimensions: Dimensions, weight: Weight) -> Self {
+        BoxContent { dimensions, weight, items: Vec::new(), quantity: 1 }
+    }
+    pub fn item(mut self, msku: &str, quantity: u32) -> Self {
+        self.items.push((msku.to_string(), quantity));
+        self
+    }
+    pub fn copies(mut self, quantity: u32) -> Self {
+        self.quantity = quantity;
+        self
+    }
+}
+
+/// What the boxes of a packing information payload belong to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Grouping {
+    /// A packing group, before the placement option is confirmed.
+    PackingGroup(String),
+    /// A shipment, after the placement option is confirmed.
+    Shipment(String),
+}
+
+/// A problem with the box contents of a packing group or shipment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoxContentIssue {
+    /// The box at `index` holds no items.
+    EmptyBox { index: usize },
+    /// The box at `index` has no copies, or lists `msku` with no units.
+    ZeroQuantity { index: usize, msku: Option<String> },
+    /// `msku` is packed but is not an item of the plan.
+    UnknownSku { msku: String },
+    /// The units of `msku` packed across all boxes differ from the plan.
+    QuantityMismatch { msku: String, expected: u32, packed: u32 },
+    /// The units of `msku`, packed or planned, add up to more than `u32::MAX`.
+    Overflow { msku: String },
+}
+impl fmt::Display for BoxContentIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoxContentIssue::EmptyBox { index } => write!(f, "box {} holds no items", index),
+            BoxContentIssue::ZeroQuantity { index, msku: Some(msku) } => write!(f, "box {} lists {} with no units", index, msku),
+            BoxContentIssue::ZeroQuantity { index, msku: None } => write!(f, "box {} has a quantity of 0", index),
+            BoxContentIssue::UnknownSku { msku } => write!(f, "{} is not an item of the plan", msku),
+            BoxContentIssue::QuantityMismatch { msku, expected, packed } => {
+                write!(f, "{} units of {} are packed but the plan has {}", packed, msku, expected)
+            }
+            BoxContentIssue::Overflow { msku } => write!(f, "the units of {} add up to too many to count", msku),
+        }
+    }
+}
+
+/// Checks `boxes` against the items of a packing group or shipment.
+///
+/// Every SKU of `expected` must be packed with exactly its planned quantity, counting each box
+/// `quantity` times. Returns no issues when the boxes are valid.
+pub fn validate_box_contents(boxes: &[BoxContent], expected: &[Item]) -> Vec<BoxContentIssue> {
+    let mut issues = Vec::new();
+    let mut overflowed: BTreeSet<&str> = BTreeSet::new();
+    let mut planned: BTreeMap<&str, u32> = BTreeMap::new();
+    for item in expected {
+        let total = planned.entry(item.msku.as_str()).or_default();
+        match total.checked_add(item.quantity) {
+            Some(sum) => *total = sum,
+            None => {
+                overflowed.insert(item.msku.as_str());
+            }
+        }
+    }
+    let mut packed: BTreeMap<&str, u32> = BTreeMap::new();
+    for (index, content) in boxes.iter().enumerate() {
+        if content.items.is_empty() {
+            issues.push(BoxContentIssue::EmptyBox { index });
+        }
+        if content.quantity == 0 {
+            issues.push(BoxContentIssue::ZeroQuantity { index, msku: None });
+        }
+        for (msku, quantity) in &content.items {
+            if *quantity == 0 {
+                issues.push(BoxContentIssue::ZeroQuantity { index, msku: Some(msku.clone()) });
+            }
+            let total = packed.entry(msku.as_str()).or_default();
+            match quantity.checked_mul(content.quantity).and_then(|units| total.checked_add(units)) {
+                Some(sum) => *total = sum,
+                None => {
+                    overflowed.insert(msku.as_str());
+                }
+            }
+        }
+    }
+    for msku in packed.keys() {
+        if !planned.contains_key(msku) {
+            issues.push(BoxContentIssue::UnknownSku { msku: msku.to_string() });
+        }
+    }
+    for msku in &overflowed {
+        issues.push(BoxContentIssue::Overflow { msku: msku.to_string() });
+    }
+    for (msku, expected) in &planned {
+        if overflowed.contains(msku) {
+            continue;
+        }
+        let packed = packed.get(msku).copied().unwrap_or(0);
+        if packed != *expected {
+            issues.push(BoxContentIssue::QuantityMismatch { msku: msku.to_string(), expected: *expected, packed });
+        }
+    }
+    issues
+}
+
+/// Builds the `set_packing_information` payload for one packing group or shipment, with the box
+/// contents provided.
+///
+/// # Responses
+/// - Returns `Errors::CustomError` listing every `BoxContentIssue` when the boxes don't match
+///   `expected`.
+pub fn build_package_grouping(grouping: Grouping, boxes: &[BoxContent], expected: &[Item]) -> Result<PackageGroupingInput, Errors> {
+    let issues = validate_box_contents(boxes, expected);
+    if !issues.is_empty() {
+        return Err(Errors::CustomError(format!(
+            "Invalid box contents: {}",
+            issues.iter().map(|i| i.to_string()).collect::<Vec<String>>().join("; ")
+        )));
+    }
+    let boxes = boxes
+        .iter()
+        .map(|content| BoxInput {
+            content_information_source: ContentInformationSource::BoxContentProvided,
+            dimensions: content.dimensions,
+            weight: content.weight,
+            quantity: content.quantity,
+            items: content
+                .items
+                .iter()
+                .map(|(msku, quantity)| item_input(msku, *quantity, expected))
+                .collect(),
+        })
+        .collect();
+    let (packing_group_id, shipment_id) = match grouping {
+        Grouping::PackingGroup(id) => (Some(id), None),
+        Grouping::Shipment(id) => (None, Some(id)),
+    };
+    Ok(PackageGroupingInput { packing_group_id, shipment_id, boxes })
+}
+
+/// The box item for `msku`, with the owners, expiration and lot code of the planned item.
+fn item_input(msku: &str, quantity: u32, expected: &[Item]) -> ItemInput {
+    let mut input = ItemInput::new(msku, quantity, Owner::None, Owner::None);
+    if let Some(item) = expected.iter().find(|i| i.msku == msku) {
+        input.label_owner = item.label_owner.unwrap_or(Owner::None);
+        input.prep_owner = item.prep_instructions.iter().find_map(|p| p.prep_owner).unwrap_or(Owner::None);
+        input.expiration = item.expiration.clone();
+        input.manufacturing_lot_code = item.manufacturing_lot_code.clone();
+    }
+    input
+}
+
+/// Fetches all the items of a packing group.
+pub async fn packing_group_items(client: &mut Client, inbound_plan_id: &str, packing_group_id: &str) -> Result<Vec<Item>, Errors> {
+    let mut items = Vec::new();
+    let mut token = None;
+    loop {
+        let page = FulfillmentInbound::list_packing_group_items(client, inbound_plan_id, packing_group_id, Some(100), token).await?;
+        items.extend(page.items);
+        token = page.pagination.and_then(|p| p.next_token);
+        if token.is_none() {
+            return Ok(items);
+        }
+    }
+}
+
+/// Fetches all the items of a shipment.
+pub async fn shipment_items(client: &mut Client, inbound_plan_id: &str, shipment_id: &str) -> Result<Vec<Item>, Errors> {
+    let mut items = Vec::new();
+    let mut token = None;
+    loop {
+        let page = FulfillmentInbound::list_shipment_items(client, inbound_plan_id, shipment_id, Some(100), token).await?;
+        items.extend(page.items);
+        token = page.pagination.and_then(|p| p.next_token);
+        if token.is_none() {
+            return Ok(items);
+        }
+    }
+}
+
+/// Fetches the carton IDs of all the boxes of a shipment.
+pub async fn carton_ids(client: &mut Client, inbound_plan_id: &str, shipment_id: &str) -> Result<Vec<String>, Errors> {
+    let mut ids = Vec::new();
+    let mut token = None;
+    loop {
+        let page = FulfillmentInbound::list_shipment_boxes(client, inbound_plan_id, shipment_id, Some(1000), token).await?;
+        ids.extend(page.boxes.into_iter().filter_map(|b| b.box_id));
+        token = page.pagination.and_then(|p| p.next_token);
+        if token.is_none() {
+            return Ok(ids);
+        }
+    }
+}
+
+/// Gets a label PDF with `FulfillmentInbound::get_labels` and writes it to `path`.
+///
+/// `BARCODE_2D` labels need `number_of_packages`, `UNIQUE` labels the carton IDs in
+/// `package_labels_to_print`.
+pub async fn download_labels(
+    client: &mut Client,
+    shipment_confirmation_id: &str,
+    page_type: PageType,
+    label_type: LabelType,
+    number_of_packages: Option<u32>,
+    package_labels_to_print: &[String],
+    path: &Path,
+) -> Result<(), Errors> {
+    match label_type {
+        LabelType::BARCODE_2D if number_of_packages.unwrap_or_default() == 0 => {
+            return Err(Errors::CustomError("BARCODE_2D labels need a number of packages".to_string()));
+        }
+        LabelType::UNIQUE if package_labels_to_print.is_empty() => {
+            return Err(Errors::CustomError("UNIQUE labels need the carton IDs to print".to_string()));
+        }
+        _ => {}
+    }
+    let url = FulfillmentInbound::get_labels(client, shipment_confirmation_id, page_type, label_type, number_of_packages, package_labels_to_print, None).await?;
+    let pdf = client.download_document(&url.download_url).await?;
+    std::fs::write(path, pdf)?;
+    Ok(())
+}
+
+/// Downloads one `UNIQUE` label per box of a shipment into `dir`, named after the shipment
+/// confirmation ID.
+///
+/// **DEV NOTE:** `getLabels` prints at most 999 cartons per call, so larger shipments are saved
+/// as `<id>_1.pdf`, `<id>_2.pdf` and so on.
+///
+/// # Responses
+/// - Returns the paths of the written PDFs.
+pub async fn download_shipment_labels(
+    client: &mut Client,
+    inbound_plan_id: &str,
+    shipment_id: &str,
+    page_type: PageType,
+    dir: &Path,
+) -> Result<Vec<PathBuf>, Errors> {
+    let shipment = FulfillmentInbound::get_shipment(client, inbound_plan_id, shipment_id).await?;
+    let confirmation_id = shipment
+        .shipment_confirmation_id
+        .ok_or_else(|| Errors::CustomError(format!("Shipment {} has no shipmentConfirmationId yet", shipment_id)))?;
+    let cartons = carton_ids(client, inbound_plan_id, shipment_id).await?;
+    if cartons.is_empty() {
+        return Err(Errors::CustomError(format!("Shipment {} has no boxes", shipment_id)));
+    }
+    let chunks: Vec<&[String]> = cartons.chunks(MAX_LABELS_PER_REQUEST).collect();
+    let mut paths = Vec::new();
+    for (n, chunk) in chunks.iter().enumerate() {
+        let name = if chunks.len() == 1 { format!("{}.pdf", confirmation_id) } else { format!("{}_{}.pdf", confirmation_id, n + 1) };
+        let path = dir.join(name);
+        download_labels(client, &confirmation_id, page_type, LabelType::UNIQUE, None, chunk, &path).await?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fulfillment_inbound::{DimensionUnit, WeightUnit};
+
+    fn carton() -> BoxContent {
+        BoxContent::new(
+            Dimensions { length: 10.0, width: 10.0, height: 10.0, unit_of_measurement: DimensionUnit::IN },
+            Weight { unit: WeightUnit::LB, value: 5.0 },
+        )
+    }
+
+    fn item(msku: &str, quantity: u32) -> Item {
+        Item { msku: msku.to_string(), quantity, ..Default::default() }
+    }
+
+    #[test]
+    fn matching_boxes_have_no_issues() {
+        let boxes = [carton().item("A", 10).copies(3), carton().item("A", 5).item("B", 2)];
+        assert!(validate_box_contents(&boxes, &[item("A", 35), item("B", 2)]).is_empty());
+    }
+
+    #[test]
+    fn reports_empty_zero_unknown_and_mismatched_boxes() {
+        let boxes = [carton(), carton().item("A", 0), carton().item("A", 4).copies(0), carton().item("C", 1).item("A", 6)];
+        let issues = validate_box_contents(&boxes, &[item("A", 10)]);
+        assert_eq!(
+            issues,
+            [
+                BoxContentIssue::EmptyBox { index: 0 },
+                BoxContentIssue::ZeroQuantity { index: 1, msku: Some("A".to_string()) },
+                BoxContentIssue::ZeroQuantity { index: 2, msku: None },
+                BoxContentIssue::UnknownSku { msku: "C".to_string() },
+                BoxContentIssue::QuantityMismatch { msku: "A".to_string(), expected: 10, packed: 6 },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_overflow_instead_of_panicking() {
+        let boxes = [carton().item("A", u32::MAX).copies(2), carton().item("B", u32::MAX), carton().item("B", 1)];
+        let issues = validate_box_contents(&boxes, &[item("A", 1), item("B", 1), item("C", u32::MAX), item("C", 1)]);
+        assert_eq!(
+            issues,
+            [
+                BoxContentIssue::Overflow { msku: "A".to_string() },
+                BoxContentIssue::Overflow { msku: "B".to_string() },
+                BoxContentIssue::Overflow { msku: "C".to_string() },
+            ]
+        );
+    }
+}
@@ -0,0 +1,805 @@
+//! The v2020-07-01 Fulfillment Outbound API, for Multi-Channel Fulfillment (MCF) of orders from
+//! other sales channels out of FBA inventory.
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::error_handling::Errors;
+use crate::general::{check_response, encode_path_segment, parse_payload, Client, CountryMarketplace};
+
+pub struct FulfillmentOutbound;
+
+const BASE: &str = "/fba/outbound/2020-07-01";
+
+/// A destination or return address.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Address {
+    pub name: String,
+    pub address_line1: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_line2: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_line3: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub district_or_county: Option<String>,
+    /// The state or region, e.g. `WA`.
+    #[serde(default)]
+    pub state_or_region: String,
+    #[serde(default)]
+    pub postal_code: String,
+    /// The two digit country code, in ISO 3166-1 alpha-2 format.
+    #[serde(default)]
+    pub country_code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, strum_macros::Display)]
+pub enum ShippingSpeedCategory {
+    Standard,
+    Expedited,
+    Priority,
+    ScheduledDelivery,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+pub enum FulfillmentAction {
+    /// Ship as soon as the order is created.
+    Ship,
+    /// Hold the order until it is updated to `Ship`.
+    Hold,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+pub enum FulfillmentPolicy {
+    /// Cancel the whole order if any item is unfulfillable.
+    FillOrKill,
+    /// Ship the fulfillable items and keep the rest pending.
+    FillAll,
+    /// Ship the fulfillable items and cancel the rest.
+    FillAllAvailable,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Money {
+    pub currency_code: String,
+    /// A decimal number, e.g. `"12.99"`.
+    pub value: String,
+}
+impl Money {
+    pub fn new(currency_code: &str, value: &str) -> Self {
+        Money { currency_code: currency_code.to_string(), value: value.to_string() }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Weight {
+    /// `KG`, `KILOGRAMS`, `LB` or `POUNDS`.
+    pub unit: String,
+    pub value: String,
+}
+
+/// The time range in which a `ScheduledDelivery` order must be delivered.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryWindow {
+    pub start_date: String,
+    pub end_date: String,
+}
+
+/// Cash on delivery settings, Japan only.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodSettings {
+    pub is_cod_required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cod_charge: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cod_charge_tax: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shipping_charge: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shipping_charge_tax: Option<Money>,
+}
+
+/// A fulfillment feature the order must use, e.g. `BLANK_BOX` or `BLOCK_AMZL`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureSettings {
+    pub feature_name: String,
+    /// `ALL` or `NONE`.
+    pub feature_fulfillment_policy: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateFulfillmentOrderItem {
+    pub seller_sku: String,
+    /// An identifier of the item, unique within the order.
+    pub seller_fulfillment_order_item_id: String,
+    pub quantity: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gift_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub displayable_comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fulfillment_network_sku: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_unit_declared_value: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_unit_price: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_unit_tax: Option<Money>,
+}
+impl CreateFulfillmentOrderItem {
+    pub fn new(seller_sku: &str, seller_fulfillment_order_item_id: &str, quantity: u32) -> Self {
+        CreateFulfillmentOrderItem {
+            seller_sku: seller_sku.to_string(),
+            seller_fulfillment_order_item_id: seller_fulfillment_order_item_id.to_string(),
+            quantity,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateFulfillmentOrderRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marketplace_id: Option<String>,
+    /// The seller's identifier of the order, unique per seller.
+    pub seller_fulfillment_order_id: String,
+    /// The order identifier printed on the packing slip.
+    pub displayable_order_id: String,
+    /// The order date printed on the packing slip, in ISO 8601 format.
+    pub displayable_order_date: String,
+    /// The comment printed on the packing slip.
+    pub displayable_order_comment: String,
+    pub shipping_speed_category: ShippingSpeedCategory,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivery_window: Option<DeliveryWindow>,
+    pub destination_address: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fulfillment_action: Option<FulfillmentAction>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fulfillment_policy: Option<FulfillmentPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cod_settings: Option<CodSettings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ship_from_country_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notification_emails: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feature_constraints: Vec<FeatureSettings>,
+    pub items: Vec<CreateFulfillmentOrderItem>,
+}
+impl CreateFulfillmentOrderRequest {
+    pub fn new(
+        seller_fulfillment_order_id: &str,
+        displayable_order_date: &str,
+        shipping_speed_category: ShippingSpeedCategory,
+        destination_address: Address,
+        items: Vec<CreateFulfillmentOrderItem>,
+    ) -> Self {
+        CreateFulfillmentOrderRequest {
+            marketplace_id: None,
+            seller_fulfillment_order_id: seller_fulfillment_order_id.to_string(),
+            displayable_order_id: seller_fulfillment_order_id.to_string(),
+            displayable_order_date: displayable_order_date.to_string(),
+            displayable_order_comment: "Thank you for your order.".to_string(),
+            shipping_speed_category,
+            delivery_window: None,
+            destination_address,
+            fulfillment_action: None,
+            fulfillment_policy: None,
+            cod_settings: None,
+            ship_from_country_code: None,
+            notification_emails: Vec::new(),
+            feature_constraints: Vec::new(),
+            items,
+        }
+    }
+
+    /// The preview request for the same address and items, at the order's shipping speed.
+    pub fn to_preview_request(&self) -> GetFulfillmentPreviewRequest {
+        GetFulfillmentPreviewRequest {
+            marketplace_id: self.marketplace_id.clone(),
+            address: self.destination_address.clone(),
+            items: self
+                .items
+                .iter()
+                .map(|i| GetFulfillmentPreviewItem {
+                    seller_sku: i.seller_sku.clone(),
+                    quantity: i.quantity,
+                    per_unit_declared_value: i.per_unit_declared_value.clone(),
+                    seller_fulfillment_order_item_id: i.seller_fulfillment_order_item_id.clone(),
+                })
+                .collect(),
+            shipping_speed_categories: vec![self.shipping_speed_category],
+            include_cod_fulfillment_preview: None,
+            include_delivery_windows: None,
+            feature_constraints: self.feature_constraints.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFulfillmentPreviewItem {
+    pub seller_sku: String,
+    pub quantity: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_unit_declared_value: Option<Money>,
+    pub seller_fulfillment_order_item_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFulfillmentPreviewRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marketplace_id: Option<String>,
+    pub address: Address,
+    pub items: Vec<GetFulfillmentPreviewItem>,
+    /// The speeds to preview; all of them when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shipping_speed_categories: Vec<ShippingSpeedCategory>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_cod_fulfillment_preview: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_delivery_windows: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feature_constraints: Vec<FeatureSettings>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Fee {
+    pub name: String,
+    pub amount: Money,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FulfillmentPreviewItem {
+    pub seller_sku: String,
+    pub quantity: u32,
+    pub seller_fulfillment_order_item_id: String,
+    pub estimated_shipping_weight: Option<Weight>,
+    pub shipping_weight_calculation_method: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FulfillmentPreviewShipment {
+    pub earliest_ship_date: Option<String>,
+    pub latest_ship_date: Option<String>,
+    pub earliest_arrival_date: Option<String>,
+    pub latest_arrival_date: Option<String>,
+    pub shipping_notes: Vec<String>,
+    pub fulfillment_preview_items: Vec<FulfillmentPreviewItem>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UnfulfillablePreviewItem {
+    pub seller_sku: String,
+    pub quantity: u32,
+    pub seller_fulfillment_order_item_id: String,
+    /// e.g. `InvalidSKU`, `InventoryUnavailable`.
+    pub item_unfulfillable_reasons: Vec<String>,
+}
+
+/// Whether and how an order could be fulfilled at one shipping speed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FulfillmentPreview {
+    pub shipping_speed_category: ShippingSpeedCategory,
+    #[serde(default)]
+    pub scheduled_delivery_info: Option<Value>,
+    pub is_fulfillable: bool,
+    #[serde(default)]
+    pub is_cod_capable: bool,
+    #[serde(default)]
+    pub estimated_shipping_weight: Option<Weight>,
+    #[serde(default)]
+    pub estimated_fees: Vec<Fee>,
+    #[serde(default)]
+    pub fulfillment_preview_shipments: Vec<FulfillmentPreviewShipment>,
+    #[serde(default)]
+    pub unfulfillable_preview_items: Vec<UnfulfillablePreviewItem>,
+    /// e.g. `InvalidAddress`, `InvalidShippingSpeedCategory`.
+    #[serde(default)]
+    pub order_unfulfillable_reasons: Vec<String>,
+    #[serde(default)]
+    pub marketplace_id: String,
+    #[serde(default)]
+    pub feature_constraints: Vec<FeatureSettings>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GetFulfillmentPreviewResult {
+    pub fulfillment_previews: Vec<FulfillmentPreview>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFulfillmentOrderItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seller_sku: Option<String>,
+    pub seller_fulfillment_order_item_id: String,
+    pub quantity: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gift_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub displayable_comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fulfillment_network_sku: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order_item_disposition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_unit_declared_value: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_unit_price: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_unit_tax: Option<Money>,
+}
+
+/// The fields of an order to change; the ones left `None` keep their value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFulfillmentOrderRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marketplace_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub displayable_order_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub displayable_order_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub displayable_order_comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shipping_speed_category: Option<ShippingSpeedCategory>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_address: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fulfillment_action: Option<FulfillmentAction>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fulfillment_policy: Option<FulfillmentPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ship_from_country_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notification_emails: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feature_constraints: Vec<FeatureSettings>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<UpdateFulfillmentOrderItem>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FulfillmentOrder {
+    pub seller_fulfillment_order_id: String,
+    pub marketplace_id: String,
+    pub displayable_order_id: String,
+    pub displayable_order_date: String,
+    pub displayable_order_comment: String,
+    pub shipping_speed_category: Option<ShippingSpeedCategory>,
+    pub delivery_window: Option<DeliveryWindow>,
+    pub destination_address: Address,
+    pub fulfillment_action: Option<FulfillmentAction>,
+    pub fulfillment_policy: Option<FulfillmentPolicy>,
+    pub cod_settings: Option<CodSettings>,
+    pub received_date: String,
+    /// e.g. `New`, `Received`, `Planning`, `Processing`, `Cancelled`, `Complete`, `Unfulfillable`.
+    pub fulfillment_order_status: String,
+    pub status_updated_date: String,
+    pub notification_emails: Vec<String>,
+    pub feature_constraints: Vec<FeatureSettings>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FulfillmentOrderItem {
+    pub seller_sku: String,
+    pub seller_fulfillment_order_item_id: String,
+    pub quantity: u32,
+    pub gift_message: Option<String>,
+    pub displayable_comment: Option<String>,
+    pub fulfillment_network_sku: Option<String>,
+    pub order_item_disposition: Option<String>,
+    pub cancelled_quantity: u32,
+    pub unfulfillable_quantity: u32,
+    pub estimated_ship_date: Option<String>,
+    pub estimated_arrival_date: Option<String>,
+    pub per_unit_price: Option<Money>,
+    pub per_unit_tax: Option<Money>,
+    pub per_unit_declared_value: Option<Money>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FulfillmentShipmentItem {
+    pub seller_sku: String,
+    pub seller_fulfillment_order_item_id: String,
+    pub quantity: u32,
+    pub package_number: Option<i64>,
+    pub serial_number: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FulfillmentShipmentPackage {
+    /// The identifier to pass to `get_package_tracking_details`.
+    pub package_number: i64,
+    pub carrier_code: String,
+    pub tracking_number: Option<String>,
+    pub estimated_arrival_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FulfillmentShipment {
+    pub amazon_shipment_id: String,
+    pub fulfillment_center_id: String,
+    /// `PENDING`, `SHIPPED`, `CANCELLED_BY_FULFILLER` or `CANCELLED_BY_SELLER`.
+    pub fulfillment_shipment_status: String,
+    pub shipping_date: Option<String>,
+    pub estimated_arrival_date: Option<String>,
+    pub shipping_notes: Vec<String>,
+    pub fulfillment_shipment_item: Vec<FulfillmentShipmentItem>,
+    pub fulfillment_shipment_package: Vec<FulfillmentShipmentPackage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReturnItem {
+    pub seller_return_item_id: String,
+    pub seller_fulfillment_order_item_id: String,
+    pub amazon_shipment_id: String,
+    pub seller_return_reason_code: String,
+    pub return_comment: Option<String>,
+    pub amazon_return_reason_code: Option<String>,
+    /// `New` or `Processed`.
+    pub status: String,
+    pub status_changed_date: String,
+    pub return_authorization_id: Option<String>,
+    pub return_received_condition: Option<String>,
+    pub fulfillment_center_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReturnAuthorization {
+    pub return_authorization_id: String,
+    pub fulfillment_center_id: String,
+    pub return_to_address: Address,
+    pub amazon_rma_id: String,
+    #[serde(rename = "rmaPageURL")]
+    pub rma_page_url: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GetFulfillmentOrderResult {
+    pub fulfillment_order: FulfillmentOrder,
+    pub fulfillment_order_items: Vec<FulfillmentOrderItem>,
+    pub fulfillment_shipments: Vec<FulfillmentShipment>,
+    pub return_items: Vec<ReturnItem>,
+    pub return_authorizations: Vec<ReturnAuthorization>,
+    pub payment_information: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListAllFulfillmentOrdersResult {
+    pub next_token: Option<String>,
+    pub fulfillment_orders: Vec<FulfillmentOrder>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TrackingAddress {
+    pub city: String,
+    pub state: String,
+    pub country: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TrackingEvent {
+    pub event_date: String,
+    pub event_address: TrackingAddress,
+    /// e.g. `EVENT_101` (carrier notified to pick up package).
+    pub event_code: String,
+    pub event_description: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PackageTrackingDetails {
+    pub package_number: i64,
+    pub tracking_number: Option<String>,
+    pub customer_tracking_link: Option<String>,
+    pub carrier_code: Option<String>,
+    pub carrier_phone_number: Option<String>,
+    #[serde(rename = "carrierURL")]
+    pub carrier_url: Option<String>,
+    pub ship_date: Option<String>,
+    pub estimated_arrival_date: Option<String>,
+    pub ship_to_address: Option<TrackingAddress>,
+    /// e.g. `IN_TRANSIT`, `DELIVERED`, `RETURNED`.
+    pub current_status: Option<String>,
+    pub current_status_description: Option<String>,
+    pub signed_for_by: Option<String>,
+    pub additional_location_info: Option<String>,
+    pub tracking_events: Vec<TrackingEvent>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReasonCodeDetails {
+    pub return_reason_code: String,
+    pub description: String,
+    pub translated_description: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListReturnReasonCodesResult {
+    pub reason_code_details: Vec<ReasonCodeDetails>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReturnItem {
+    /// An identifier of the return item, unique per seller.
+    pub seller_return_item_id: String,
+    pub seller_fulfillment_order_item_id: String,
+    pub amazon_shipment_id: String,
+    /// A code from `list_return_reason_codes`.
+    pub return_reason_code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InvalidItemReason {
+    /// e.g. `InvalidValues`, `DuplicateRequest`, `NoCompletedShipItems`, `NoReturnableQuantity`.
+    pub invalid_item_reason_code: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InvalidReturnItem {
+    pub seller_return_item_id: String,
+    pub seller_fulfillment_order_item_id: String,
+    pub invalid_item_reason: InvalidItemReason,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CreateFulfillmentReturnResult {
+    pub return_items: Vec<ReturnItem>,
+    pub invalid_return_items: Vec<InvalidReturnItem>,
+    pub return_authorizations: Vec<ReturnAuthorization>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Feature {
+    /// e.g. `BLANK_BOX`, `BLOCK_AMZL`, `SIGNATURE_CONFIRMATION`.
+    pub feature_name: String,
+    pub feature_description: String,
+    pub seller_eligible: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GetFeaturesResult {
+    pub features: Vec<Feature>,
+}
+
+impl FulfillmentOutbound {
+    /// Returns a list of fulfillment order previews based on shipping criteria that you specify.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 30
+    ///
+    /// # Parameters
+    /// - `request`: The destination address, items and shipping speeds to preview.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns one `FulfillmentPreview` per shipping speed.
+    pub async fn get_fulfillment_preview(client: &mut Client, request: &GetFulfillmentPreviewRequest) -> Result<GetFulfillmentPreviewResult, Errors> {
+        let endpoint = format!("{}/fulfillmentOrders/preview", BASE);
+
+        parse_payload(
+            client
+                .make_request_w_body(&endpoint, Method::POST, None::<Vec<(String, String)>>, serde_json::to_string(request)?)
+                .await?,
+        )
+        .await
+    }
+
+    /// Requests that Amazon ship items from the seller's inventory in Amazon's fulfillment
+    /// network to a destination address.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 30
+    ///
+    /// # Responses
+    /// - **200 (Success):** The order was created; fetch it with `get_fulfillment_order`.
+    pub async fn create_fulfillment_order(client: &mut Client, request: &CreateFulfillmentOrderRequest) -> Result<(), Errors> {
+        let endpoint = format!("{}/fulfillmentOrders", BASE);
+
+        check_response(
+            client
+                .make_request_w_body(&endpoint, Method::POST, None::<Vec<(String, String)>>, serde_json::to_string(request)?)
+                .await?,
+        )
+        .await
+    }
+
+    /// Updates and/or requests shipment for a fulfillment order with an order hold on it.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 30
+    ///
+    /// # Parameters
+    /// - `seller_fulfillment_order_id`: The identifier assigned when the order was created.
+    /// - `request`: The fields to change.
+    pub async fn update_fulfillment_order(
+        client: &mut Client,
+        seller_fulfillment_order_id: &str,
+        request: &UpdateFulfillmentOrderRequest,
+    ) -> Result<(), Errors> {
+        let endpoint = format!("{}/fulfillmentOrders/{}", BASE, encode_path_segment(seller_fulfillment_order_id));
+
+        check_response(
+            client
+                .make_request_w_body(&endpoint, Method::PUT, None::<Vec<(String, String)>>, serde_json::to_string(request)?)
+                .await?,
+        )
+        .await
+    }
+
+    /// Requests that Amazon stop attempting to fulfill the fulfillment order.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 30
+    pub async fn cancel_fulfillment_order(client: &mut Client, seller_fulfillment_order_id: &str) -> Result<(), Errors> {
+        let endpoint = format!("{}/fulfillmentOrders/{}/cancel", BASE, encode_path_segment(seller_fulfillment_order_id));
+
+        check_response(client.make_request(&endpoint, Method::PUT, None::<Vec<(String, String)>>).await?).await
+    }
+
+    /// Returns the fulfillment order indicated by the specified order identifier, with its
+    /// items, shipments and returns.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 30
+    pub async fn get_fulfillment_order(client: &mut Client, seller_fulfillment_order_id: &str) -> Result<GetFulfillmentOrderResult, Errors> {
+        let endpoint = format!("{}/fulfillmentOrders/{}", BASE, encode_path_segment(seller_fulfillment_order_id));
+
+        parse_payload(client.make_request(&endpoint, Method::GET, None::<Vec<(String, String)>>).await?).await
+    }
+
+    /// Returns a list of fulfillment orders fulfilled after (or at) a specified date-time, or
+    /// indicated by the next token parameter.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 30
+    ///
+    /// # Parameters
+    /// - `query_start_date` (optional): Orders that changed at or after this date-time, in ISO 8601 format.
+    /// - `next_token` (optional): The token from the previous page.
+    pub async fn list_all_fulfillment_orders(
+        client: &mut Client,
+        query_start_date: Option<&str>,
+        next_token: Option<String>,
+    ) -> Result<ListAllFulfillmentOrdersResult, Errors> {
+        let endpoint = format!("{}/fulfillmentOrders", BASE);
+        let mut params = Vec::new();
+        if let Some(date) = query_start_date {
+            params.push(("queryStartDate".to_string(), date.to_string()));
+        }
+        if let Some(token) = next_token {
+            params.push(("nextToken".to_string(), token));
+        }
+
+        parse_payload(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Returns delivery tracking information for a package in an outbound shipment for a
+    /// Multi-Channel Fulfillment order.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 30
+    ///
+    /// # Parameters
+    /// - `package_number`: The `package_number` of a `FulfillmentShipmentPackage`.
+    pub async fn get_package_tracking_details(client: &mut Client, package_number: i64) -> Result<PackageTrackingDetails, Errors> {
+        let endpoint = format!("{}/tracking", BASE);
+        let params = vec![("packageNumber".to_string(), package_number.to_string())];
+
+        parse_payload(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Returns a list of return reason codes for a seller SKU in a given marketplace.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 30
+    ///
+    /// # Parameters
+    /// - `seller_sku`: The seller SKU to get return reason codes for.
+    /// - `marketplace` (optional): The marketplace, required when `seller_fulfillment_order_id` is not given.
+    /// - `seller_fulfillment_order_id` (optional): The order the item was fulfilled with.
+    /// - `language`: The language of the translated descriptions, e.g. `en_US`.
+    pub async fn list_return_reason_codes(
+        client: &mut Client,
+        seller_sku: &str,
+        marketplace: Option<CountryMarketplace>,
+        seller_fulfillment_order_id: Option<&str>,
+        language: &str,
+    ) -> Result<ListReturnReasonCodesResult, Errors> {
+        let endpoint = format!("{}/returnReasonCodes", BASE);
+        let mut params = vec![
+            ("sellerSku".to_string(), seller_sku.to_string()),
+            ("language".to_string(), language.to_string()),
+        ];
+        if let Some(marketplace) = marketplace {
+            params.push(("marketplaceId".to_string(), marketplace.details().0.to_string()));
+        }
+        if let Some(id) = seller_fulfillment_order_id {
+            params.push(("sellerFulfillmentOrderId".to_string(), id.to_string()));
+        }
+
+        parse_payload(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Creates a fulfillment return.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 30
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns the accepted and rejected items and the return authorizations.
+    pub async fn create_fulfillment_return(
+        client: &mut Client,
+        seller_fulfillment_order_id: &str,
+        items: &[CreateReturnItem],
+    ) -> Result<CreateFulfillmentReturnResult, Errors> {
+        let endpoint = format!("{}/fulfillmentOrders/{}/return", BASE, encode_path_segment(seller_fulfillment_order_id));
+        let body = json!({ "items": items }).to_string();
+
+        parse_payload(client.make_request_w_body(&endpoint, Method::PUT, None::<Vec<(String, String)>>, body).await?).await
+    }
+
+    /// Returns a list of features available for Multi-Channel Fulfillment orders in the
+    /// marketplace you specify, and whether the seller is eligible for each.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 30
+    pub async fn get_features(client: &mut Client, marketplace: CountryMarketplace) -> Result<GetFeaturesResult, Errors> {
+        let endpoint = format!("{}/features", BASE);
+        let params = vec![("marketplaceId".to_string(), marketplace.details().0.to_string())];
+
+        parse_payload(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+}
@@ -0,0 +1,321 @@
+//! Imports Multi-Channel Fulfillment orders from a CSV of order lines.
+//!
+//! The CSV has a header row and one row per order line:
+//!
+//! `order_id,order_date,name,address_line1,address_line2,address_line3,city,district_or_county,state_or_region,postal_code,country_code,phone,sku,quantity,shipping_speed,comment,notification_email`
+//!
+//! Only `order_id`, `name`, `address_line1`, `state_or_region`, `postal_code`, `country_code`,
+//! `sku`, `quantity` and `shipping_speed` are required. Rows with the same `order_id` become one
+//! order, with the address and shipping speed of its first row. Each order is previewed before it
+//! is created, and the outcome of every row is written to a result CSV.
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use crate::error_handling::Errors;
+use crate::general::Client;
+use crate::models::fulfillment_outbound::{
+    Address, CreateFulfillmentOrderItem, CreateFulfillmentOrderRequest, FulfillmentOutbound, ShippingSpeedCategory,
+};
+
+/// One row of the import CSV.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrderLine {
+    pub order_id: String,
+    /// The order date in ISO 8601 format; the import time when empty.
+    pub order_date: String,
+    pub name: String,
+    pub address_line1: String,
+    pub address_line2: String,
+    pub address_line3: String,
+    pub city: String,
+    pub district_or_county: String,
+    pub state_or_region: String,
+    pub postal_code: String,
+    pub country_code: String,
+    pub phone: String,
+    pub sku: String,
+    /// Kept as text so a bad quantity fails its order instead of the whole file.
+    pub quantity: String,
+    /// `Standard`, `Expedited` or `Priority`, case-insensitive.
+    pub shipping_speed: String,
+    pub comment: String,
+    pub notification_email: String,
+}
+impl OrderLine {
+    fn address(&self) -> Address {
+        let optional = |s: &str| if s.trim().is_empty() { None } else { Some(s.trim().to_string()) };
+        Address {
+            name: self.name.trim().to_string(),
+            address_line1: self.address_line1.trim().to_string(),
+            address_line2: optional(&self.address_line2),
+            address_line3: optional(&self.address_line3),
+            city: optional(&self.city),
+            district_or_county: optional(&self.district_or_county),
+            state_or_region: self.state_or_region.trim().to_string(),
+            postal_code: self.postal_code.trim().to_string(),
+            country_code: self.country_code.trim().to_uppercase(),
+            phone: optional(&self.phone),
+        }
+    }
+}
+
+/// Parses a shipping speed case-insensitively, e.g. `standard` or `Scheduled Delivery`.
+pub fn parse_shipping_speed(speed: &str) -> Option<ShippingSpeedCategory> {
+    match speed.trim().to_lowercase().replace([' ', '_'], "").as_str() {
+        "standard" => Some(ShippingSpeedCategory::Standard),
+        "expedited" => Some(ShippingSpeedCategory::Expedited),
+        "priority" => Some(ShippingSpeedCategory::Priority),
+        "scheduleddelivery" => Some(ShippingSpeedCategory::ScheduledDelivery),
+        _ => None,
+    }
+}
+
+/// The rows of one order and the request built from them, or why it couldn't be built.
+#[derive(Debug)]
+pub struct ImportOrder {
+    pub order_id: String,
+    /// The rows of the order with their 1-based line numbers, not counting the header.
+    pub lines: Vec<(usize, OrderLine)>,
+    pub request: Result<CreateFulfillmentOrderRequest, String>,
+}
+
+/// Reads the order lines of a CSV.
+pub fn read_order_lines<R: Read>(reader: R) -> Result<Vec<OrderLine>, Errors> {
+    let mut csv = csv::ReaderBuilder::new().trim(csv::Trim::Headers).flexible(true).from_reader(reader);
+    let mut lines = Vec::new();
+    for line in csv.deserialize() {
+        lines.push(line?);
+    }
+    Ok(lines)
+}
+
+/// Groups order lines by `order_id`, in the order the orders first appear.
+pub fn group_orders(lines: Vec<OrderLine>) -> Vec<ImportOrder> {
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut grouped: Vec<(String, Vec<(usize, OrderLine)>)> = Vec::new();
+    for (n, line) in lines.into_iter().enumerate() {
+        let order_id = line.order_id.trim().to_string();
+        let position = *index.entry(order_id.clone()).or_insert_with(|| {
+            grouped.push((order_id.clone(), Vec::new()));
+            grouped.len() - 1
+        });
+        grouped[position].1.push((n + 1, line));
+    }
+    grouped
+        .into_iter()
+        .map(|(order_id, lines)| {
+            let request = build_request(&order_id, &lines);
+            ImportOrder { order_id, lines, request }
+        })
+        .collect()
+}
+
+fn build_request(order_id: &str, lines: &[(usize, OrderLine)]) -> Result<CreateFulfillmentOrderRequest, String> {
+    if order_id.is_empty() {
+        return Err("order_id is empty".to_string());
+    }
+    let (_, first) = &lines[0];
+    let speed = parse_shipping_speed(&first.shipping_speed)
+        .ok_or_else(|| format!("Unknown shipping speed '{}'", first.shipping_speed))?;
+    if speed == ShippingSpeedCategory::ScheduledDelivery {
+        return Err("ScheduledDelivery needs a delivery window, which the import doesn't support".to_string());
+    }
+    let address = first.address();
+    for (field, value) in [
+        ("name", &address.name),
+        ("address_line1", &address.address_line1),
+        ("state_or_region", &address.state_or_region),
+        ("postal_code", &address.postal_code),
+        ("country_code", &address.country_code),
+    ] {
+        if value.is_empty() {
+            return Err(format!("{} is empty", field));
+        }
+    }
+
+    let mut items = Vec::new();
+    for (n, (row, line)) in lines.iter().enumerate() {
+        if line.address() != address || parse_shipping_speed(&line.shipping_speed) != Some(speed) {
+            return Err(format!("Row {} has a different address or shipping speed than row {}", row, lines[0].0));
+        }
+        let sku = line.sku.trim();
+        if sku.is_empty() {
+            return Err(format!("Row {} has no sku", row));
+        }
+        let quantity = match line.quantity.trim().parse::<u32>() {
+            Ok(q) if q > 0 => q,
+            _ => return Err(format!("Row {} has an invalid quantity '{}'", row, line.quantity)),
+        };
+        let mut item = CreateFulfillmentOrderItem::new(sku, &format!("{}-{}", order_id, n + 1), quantity);
+        if !line.comment.trim().is_empty() {
+            item.displayable_comment = Some(line.comment.trim().to_string());
+        }
+        items.push(item);
+    }
+
+    let order_date = if first.order_date.trim().is_empty() {
+        Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+    } else {
+        first.order_date.trim().to_string()
+    };
+    let mut request = CreateFulfillmentOrderRequest::new(order_id, &order_date, speed, address, items);
+    request.notification_emails = lines
+        .iter()
+        .map(|(_, l)| l.notification_email.trim().to_string())
+        .filter(|e| !e.is_empty())
+        .fold(Vec::new(), |mut emails, e| {
+            if !emails.contains(&e) {
+                emails.push(e);
+            }
+            emails
+        });
+    Ok(request)
+}
+
+/// The outcome of one order.
+#[derive(Debug)]
+pub struct ImportResult {
+    pub order: ImportOrder,
+    /// The `sellerFulfillmentOrderId` of the created order.
+    pub outcome: Result<String, Errors>,
+}
+
+/// Previews an order and creates it if it is fulfillable at its shipping speed.
+pub async fn preview_and_create(client: &mut Client, request: &CreateFulfillmentOrderRequest) -> Result<String, Errors> {
+    let previews = FulfillmentOutbound::get_fulfillment_preview(client, &request.to_preview_request()).await?;
+    let preview = previews
+        .fulfillment_previews
+        .iter()
+        .find(|p| p.shipping_speed_category == request.shipping_speed_category)
+        .ok_or_else(|| Errors::CustomError(format!("No preview for {} shipping", request.shipping_speed_category)))?;
+    if !preview.is_fulfillable {
+        let mut reasons = preview.order_unfulfillable_reasons.clone();
+        for item in &preview.unfulfillable_preview_items {
+            reasons.push(format!("{}: {}", item.seller_sku, item.item_unfulfillable_reasons.join(", ")));
+        }
+        return Err(Errors::CustomError(format!("Not fulfillable: {}", reasons.join("; "))));
+    }
+    FulfillmentOutbound::create_fulfillment_order(client, request).await?;
+    Ok(request.seller_fulfillment_order_id.clone())
+}
+
+/// Previews and creates each order in turn. A failed order doesn't stop the others.
+pub async fn import_orders(client: &mut Client, orders: Vec<ImportOrder>) -> Vec<ImportResult> {
+    let mut results = Vec::new();
+    for order in orders {
+        let outcome = match &order.request {
+            Ok(request) => preview_and_create(client, request).await,
+            Err(e) => Err(Errors::CustomError(e.clone())),
+        };
+        results.push(ImportResult { order, outcome });
+    }
+    results
+}
+
+#[derive(Serialize)]
+struct ResultRow<'a> {
+    row: usize,
+    order_id: &'a str,
+    sku: &'a str,
+    quantity: &'a str,
+    status: &'a str,
+    fulfillment_order_id: &'a str,
+    error: String,
+}
+
+/// Writes one result row per order line: `row,order_id,sku,quantity,status,fulfillment_order_id,error`,
+/// with `status` either `CREATED` or `FAILED`.
+pub fn write_results<W: Write>(results: &[ImportResult], writer: W) -> Result<(), Errors> {
+    let mut csv = csv::Writer::from_writer(writer);
+    let mut rows: Vec<ResultRow> = Vec::new();
+    for result in results {
+        for (row, line) in &result.order.lines {
+            let (status, fulfillment_order_id, error) = match &result.outcome {
+                Ok(id) => ("CREATED", id.as_str(), String::new()),
+                Err(e) => ("FAILED", "", e.to_string()),
+            };
+            rows.push(ResultRow { row: *row, order_id: &result.order.order_id, sku: &line.sku, quantity: &line.quantity, status, fulfillment_order_id, error });
+        }
+    }
+    rows.sort_by_key(|r| r.row);
+    for row in rows {
+        csv.serialize(row)?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+/// The number of orders created and failed by `import_csv`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub failed: usize,
+}
+
+/// Reads order lines from `input`, previews and creates the orders, and writes the result CSV
+/// to `output`.
+pub async fn import_csv<R: Read, W: Write>(client: &mut Client, input: R, output: W) -> Result<ImportSummary, Errors> {
+    let orders = group_orders(read_order_lines(input)?);
+    let results = import_orders(client, orders).await;
+    write_results(&results, output)?;
+    let created = results.iter().filter(|r| r.outcome.is_ok()).count();
+    Ok(ImportSummary { created, failed: results.len() - created })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(order_id: &str, sku: &str, quantity: &str) -> OrderLine {
+        OrderLine {
+            order_id: order_id.to_string(),
+            order_date: "2026-10-19T10:00:00Z".to_string(),
+            name: "Jane Doe".to_string(),
+            address_line1: "1 Main St".to_string(),
+            city: "Seattle".to_string(),
+            state_or_region: "WA".to_string(),
+            postal_code: "98101".to_string(),
+            country_code: "us".to_string(),
+            sku: sku.to_string(),
+            quantity: quantity.to_string(),
+            shipping_speed: "standard".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn groups_rows_by_order_in_first_seen_order() {
+        let mut with_email = line("A", "SKU-2", "1");
+        with_email.notification_email = "jane@example.com".to_string();
+        let orders = group_orders(vec![line("A", "SKU-1", "2"), line("B", "SKU-1", "1"), with_email]);
+
+        assert_eq!(orders.iter().map(|o| o.order_id.as_str()).collect::<Vec<_>>(), ["A", "B"]);
+        assert_eq!(orders[0].lines.iter().map(|(n, _)| *n).collect::<Vec<_>>(), [1, 3]);
+        let request = orders[0].request.as_ref().unwrap();
+        assert_eq!(request.items.len(), 2);
+        assert_eq!(request.items[0].seller_fulfillment_order_item_id, "A-1");
+        assert_eq!(request.items[1].quantity, 1);
+        assert_eq!(request.destination_address.country_code, "US");
+        assert_eq!(request.notification_emails, ["jane@example.com"]);
+    }
+
+    #[test]
+    fn rejects_invalid_rows() {
+        let mut other_address = line("A", "SKU-2", "1");
+        other_address.address_line1 = "2 Main St".to_string();
+        let mut scheduled = line("C", "SKU-1", "1");
+        scheduled.shipping_speed = "Scheduled Delivery".to_string();
+        let mut unknown_speed = line("D", "SKU-1", "1");
+        unknown_speed.shipping_speed = "overnight".to_string();
+        let orders = group_orders(vec![line("A", "SKU-1", "1"), other_address, line("B", "SKU-1", "0"), scheduled, unknown_speed, line("", "SKU-1", "1")]);
+
+        let errors: Vec<&str> = orders.iter().map(|o| o.request.as_ref().unwrap_err().as_str()).collect();
+        assert_eq!(errors[0], "Row 2 has a different address or shipping speed than row 1");
+        assert_eq!(errors[1], "Row 3 has an invalid quantity '0'");
+        assert!(errors[2].starts_with("ScheduledDelivery needs a delivery window"));
+        assert_eq!(errors[3], "Unknown shipping speed 'overnight'");
+        assert_eq!(errors[4], "order_id is empty");
+    }
+}
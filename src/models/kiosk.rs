@@ -1,26 +1,93 @@
+use std::fmt;
 use reqwest::{Method, Response};
+use serde::Deserialize;
+use serde_json::json;
 use crate::error_handling::Errors;
-use crate::general::Client;
-#[allow(dead_code)]
+use crate::general::{parse_response, Client};
+use crate::poller::{OperationHandle, PollStatus, Pollable};
 
 pub struct Kiosk;
-#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 pub enum ProcessingStatuses {
     CANCELLED,
     DONE,
     FATAL,
+    #[serde(rename = "IN_PROGRESS")]
     InProgress,
+    #[serde(rename = "IN_QUEUE")]
     InQueue,
 }
-#[allow(dead_code)]
-impl ProcessingStatuses {
-    pub fn to_string(&self) -> String {
-        match self {
-            ProcessingStatuses::CANCELLED => "CANCELLED".to_string(),
-            ProcessingStatuses::DONE => "DONE".to_string(),
-            ProcessingStatuses::FATAL => "FATAL".to_string(),
-            ProcessingStatuses::InProgress => "InProgress".to_string(),
-            ProcessingStatuses::InQueue => "InQueue".to_string(),
+impl fmt::Display for ProcessingStatuses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            ProcessingStatuses::CANCELLED => "CANCELLED",
+            ProcessingStatuses::DONE => "DONE",
+            ProcessingStatuses::FATAL => "FATAL",
+            ProcessingStatuses::InProgress => "IN_PROGRESS",
+            ProcessingStatuses::InQueue => "IN_QUEUE",
+        };
+        f.write_str(status)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateQueryResponse {
+    pub query_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPagination {
+    pub next_token: Option<String>,
+}
+
+/// Detailed information about a Data Kiosk query.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Query {
+    pub query_id: String,
+    pub query: String,
+    pub created_time: String,
+    pub processing_status: ProcessingStatuses,
+    pub processing_start_time: Option<String>,
+    pub processing_end_time: Option<String>,
+    /// Present when the query finished with data. Pass it to `Kiosk::get_document`.
+    pub data_document_id: Option<String>,
+    /// Present when the query failed.
+    pub error_document_id: Option<String>,
+    pub pagination: Option<QueryPagination>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDocumentResponse {
+    pub document_id: String,
+    pub document_url: String,
+}
+
+/// A submitted Data Kiosk query, polled through `Kiosk::get_query` until it is `DONE`.
+#[derive(Debug, Clone)]
+pub struct KioskQueryOperation {
+    pub query_id: String,
+}
+impl Pollable for KioskQueryOperation {
+    type Output = Query;
+
+    async fn poll(&mut self, client: &mut Client) -> Result<PollStatus<Query>, Errors> {
+        let query: Query = parse_response(Kiosk::get_query(client, self.query_id.clone()).await?).await?;
+        match query.processing_status {
+            ProcessingStatuses::DONE => Ok(PollStatus::Done(query)),
+            ProcessingStatuses::FATAL => Err(Errors::OperationFailed(format!(
+                "Data Kiosk query {} failed, error document: {}",
+                query.query_id,
+                query.error_document_id.unwrap_or_default()
+            ))),
+            ProcessingStatuses::CANCELLED => Err(Errors::OperationFailed(format!(
+                "Data Kiosk query {} was cancelled",
+                query.query_id
+            ))),
+            status => Ok(PollStatus::Pending(status.to_string())),
         }
     }
 }
@@ -67,10 +134,10 @@ impl Kiosk {
     ///   - Headers:
     ///     - `x-amzn-RateLimit-Limit` (`String`): Your rate limit (requests per second) for this operation.
     ///     - `x-amzn-RequestId` (`String`): Unique request reference identifier.
-    async fn get_queries(client: &mut Client, processing_status: Option<Vec<ProcessingStatuses>>, page_size: Option<u64>, created_since: Option<String>, created_until: Option<String>, pagination_token: Option<String> ) -> Result<Response, Errors> {
+    pub async fn get_queries(client: &mut Client, processing_status: Option<Vec<ProcessingStatuses>>, page_size: Option<u64>, created_since: Option<String>, created_until: Option<String>, pagination_token: Option<String> ) -> Result<Response, Errors> {
         let mut parameters = vec![];
         if let Some(processing_statuses) = processing_status {
-            parameters.push(("processingStatuses", processing_statuses.iter().map(|b| b.to_string()).collect()))
+            parameters.push(("processingStatuses", processing_statuses.iter().map(|b| b.to_string()).collect::<Vec<String>>().join(",")))
         }
         if let Some(page_size) = page_size {
             parameters.push(("pageSize", page_size.to_string()))
@@ -101,15 +168,30 @@ impl Kiosk {
     ///
     /// # Responses
     /// - **202 (Success):** Returns a `CreateQueryResponse` object.
-    async fn create_query(
+    pub async fn create_query(
         client: &mut Client,
         body: String,
     ) -> Result<Response, Errors> {
         client
-            .make_request("/dataKiosk/2023-11-15/queries", Method::POST, Some([("body", body)]))
+            .make_request_w_body("/dataKiosk/2023-11-15/queries", Method::POST, None::<Vec<(String, String)>>, body)
             .await
     }
 
+    /// Creates a Data Kiosk query and returns a handle that waits for it to finish.
+    ///
+    /// # Parameters
+    /// - `query`: The GraphQL query to submit.
+    ///
+    /// # Returns
+    /// An `OperationHandle` resolving to the finished `Query`. Its `data_document_id` can be
+    /// passed to `get_document`; a query that finishes without data has none.
+    pub async fn submit_query(client: &mut Client, query: &str) -> Result<OperationHandle<KioskQueryOperation>, Errors> {
+        let body = json!({ "query": query }).to_string();
+        let created: CreateQueryResponse = parse_response(Self::create_query(client, body).await?).await?;
+
+        Ok(OperationHandle::new(KioskQueryOperation { query_id: created.query_id }))
+    }
+
 
 
     /// Returns query details for the specified queryId.
@@ -123,7 +205,7 @@ impl Kiosk {
     ///
     /// # Responses
     /// - **200 (Success):** Returns query details in a `Query` object.
-    async fn get_query(client: &mut Client, query_id: String) -> Result<Response, Errors> {
+    pub async fn get_query(client: &mut Client, query_id: String) -> Result<Response, Errors> {
         let endpoint = format!("/dataKiosk/2023-11-15/queries/{}", query_id);
 
         client
@@ -142,7 +224,7 @@ impl Kiosk {
     ///
     /// # Responses
     /// - **204 (Success):** Indicates successful cancellation with no content.
-    async fn cancel_query(client: &mut Client, query_id: String) -> Result<Response, Errors> {
+    pub async fn cancel_query(client: &mut Client, query_id: String) -> Result<Response, Errors> {
         let endpoint = format!("/dataKiosk/2023-11-15/queries/{}", query_id);
 
         client
@@ -161,7 +243,7 @@ impl Kiosk {
     ///
     /// # Responses
     /// - **200 (Success):** Returns document details in a `GetDocumentResponse` object.
-    async fn get_document(client: &mut Client, document_id: String) -> Result<Response, Errors> {
+    pub async fn get_document(client: &mut Client, document_id: String) -> Result<Response, Errors> {
         let endpoint = format!("/dataKiosk/2023-11-15/documents/{}", document_id);
 
         client
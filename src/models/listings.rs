@@ -1,5 +1,6 @@
 use reqwest::{Method, Response};
-use serde_json::{json, Value};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use crate::general::{Client, CountryMarketplace};
 use strum_macros;
 use crate::error_handling::Errors;
@@ -17,6 +18,40 @@ pub enum IncludedData {
     relationships,
     productTypes,
 }
+/// Listing attributes keyed by attribute name, as defined by the product type definition.
+pub type Attributes = Map<String, Value>;
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+pub enum PatchOp {
+    add,
+    replace,
+    merge,
+    delete,
+}
+
+/// A single JSON Patch operation on a listings item, e.g. replacing `/attributes/purchasable_offer`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchOperation {
+    pub op: PatchOp,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Vec<Value>>,
+}
+impl PatchOperation {
+    pub fn new(op: PatchOp, path: &str, value: Option<Vec<Value>>) -> Self {
+        PatchOperation { op, path: path.to_string(), value }
+    }
+}
+
+/// The request body for `patch_listings_item`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingsItemPatchRequest {
+    pub product_type: String,
+    pub patches: Vec<PatchOperation>,
+}
+
 impl Listings {
     pub async fn get_listings_item(
         client: &mut Client,
@@ -61,19 +96,15 @@ impl Listings {
 
     ) -> Result<Response, Errors> {
         let item_data: Value = Self::get_listings_item(client, seller_id, sku ,CountryMarketplace::SaudiArabia, vec![IncludedData::productTypes, IncludedData::attributes]).await?.json().await?;
-        let item_type = &item_data["productTypes"][0]["productType"];
+        let item_type = item_data["productTypes"][0]["productType"].as_str().unwrap_or_default();
         let mut offers = item_data["attributes"]["purchasable_offer"].clone();
 
         offers[0]["our_price"][0]["schedule"][0]["value_with_tax"] = Value::from(new_price);
-        let final_body = json!({
-            "productType": item_type,
-            "patches": [{
-                    "op":"replace",
-                  "path":"/attributes/purchasable_offer",
-                  "value":offers
-            }]
-        });
-        Self::patch_listings_item(client, seller_id, sku, country_marketplace, validation_preview, &*final_body.to_string()).await
+        let final_body = ListingsItemPatchRequest {
+            product_type: item_type.to_string(),
+            patches: vec![PatchOperation::new(PatchOp::replace, "/attributes/purchasable_offer", offers.as_array().cloned())],
+        };
+        Self::patch_listings_item(client, seller_id, sku, country_marketplace, validation_preview, &serde_json::to_string(&final_body)?).await
 
     }
         /// Search for and return a list of selling partner listings items
@@ -0,0 +1,250 @@
+//! Builder for the `JSON_LISTINGS_FEED` feed type, used to change many listings in one feed
+//! instead of calling `Listings::patch_listings_item` for every SKU.
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::error_handling::Errors;
+use crate::general::{Client, CountryMarketplace};
+use crate::models::feeds::{FeedOperation, FeedOutcome, Feeds};
+use crate::models::listings::{Attributes, PatchOperation};
+use crate::models::processing_report::{IssueSeverity, ProcessingIssue, ProcessingReport};
+use crate::poller::OperationHandle;
+
+pub const LISTINGS_FEED_TYPE: &str = "JSON_LISTINGS_FEED";
+pub const LISTINGS_FEED_CONTENT_TYPE: &str = "application/json; charset=UTF-8";
+/// Amazon rejects `JSON_LISTINGS_FEED` feeds with more messages than this.
+pub const MAX_MESSAGES_PER_FEED: usize = 10_000;
+/// Amazon rejects feed documents larger than 10 MB.
+pub const MAX_FEED_SIZE_BYTES: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum OperationType {
+    /// Replaces the whole listing with the provided attributes.
+    Update,
+    /// Applies JSON Patch operations to the listing.
+    Patch,
+    /// Deletes the listing.
+    Delete,
+    /// Replaces only the provided attributes.
+    PartialUpdate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingsFeedHeader {
+    pub seller_id: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_locale: Option<String>,
+}
+
+/// A single message of a `JSON_LISTINGS_FEED`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingsFeedMessage {
+    pub message_id: u32,
+    pub sku: String,
+    pub operation_type: OperationType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_type: Option<String>,
+    /// `LISTING`, `LISTING_PRODUCT_ONLY` or `LISTING_OFFER_ONLY`. Only used by `UPDATE`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requirements: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<Attributes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patches: Option<Vec<PatchOperation>>,
+}
+
+/// A `JSON_LISTINGS_FEED` document that is within Amazon's size and message-count limits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListingsFeed {
+    pub header: ListingsFeedHeader,
+    pub messages: Vec<ListingsFeedMessage>,
+}
+impl ListingsFeed {
+    /// The serialized feed document, ready to be uploaded.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Errors> {
+        Ok(serde_json::to_vec(self)?)
+    }
+    /// The SKU the message with `message_id` was built for.
+    pub fn sku_for_message(&self, message_id: u32) -> Option<&str> {
+        self.messages.iter().find(|m| m.message_id == message_id).map(|m| m.sku.as_str())
+    }
+    /// Maps every message ID of the feed to its SKU, to correlate the processing report.
+    pub fn message_skus(&self) -> HashMap<u32, String> {
+        self.messages.iter().map(|m| (m.message_id, m.sku.clone())).collect()
+    }
+
+    /// Uploads the feed and returns a handle that resolves once Amazon has processed it.
+    pub async fn create(&self, client: &mut Client, marketplaces: Vec<CountryMarketplace>) -> Result<OperationHandle<FeedOperation>, Errors> {
+        Feeds::create_feed_with_content(client, LISTINGS_FEED_TYPE, marketplaces, LISTINGS_FEED_CONTENT_TYPE, self.to_bytes()?, None).await
+    }
+    /// Uploads the feed, waits for Amazon to process it and downloads the processing report.
+    pub async fn submit(&self, client: &mut Client, marketplaces: Vec<CountryMarketplace>) -> Result<FeedOutcome, Errors> {
+        Feeds::submit_feed(client, LISTINGS_FEED_TYPE, marketplaces, LISTINGS_FEED_CONTENT_TYPE, self.to_bytes()?).await
+    }
+}
+
+/// Collects listings changes into `JSON_LISTINGS_FEED` documents.
+///
+/// Message IDs are assigned in the order the messages are added, starting at 1.
+pub struct ListingsFeedBuilder {
+    header: ListingsFeedHeader,
+    messages: Vec<ListingsFeedMessage>,
+}
+impl ListingsFeedBuilder {
+    pub fn new(seller_id: &str) -> Self {
+        ListingsFeedBuilder {
+            header: ListingsFeedHeader { seller_id: seller_id.to_string(), version: "2.0".to_string(), issue_locale: None },
+            messages: Vec::new(),
+        }
+    }
+    /// The locale issues in the processing report are localized to, e.g. `en_US`.
+    pub fn issue_locale(mut self, locale: &str) -> Self {
+        self.header.issue_locale = Some(locale.to_string());
+        self
+    }
+
+    fn push(&mut self, sku: &str, operation_type: OperationType, product_type: Option<&str>) -> &mut ListingsFeedMessage {
+        self.messages.push(ListingsFeedMessage {
+            message_id: self.messages.len() as u32 + 1,
+            sku: sku.to_string(),
+            operation_type,
+            product_type: product_type.map(str::to_string),
+            requirements: None,
+            attributes: None,
+            patches: None,
+        });
+        self.messages.last_mut().unwrap()
+    }
+    /// Creates or fully replaces a listing.
+    pub fn update(&mut self, sku: &str, product_type: &str, requirements: Option<&str>, attributes: Attributes) -> &mut Self {
+        let message = self.push(sku, OperationType::Update, Some(product_type));
+        message.requirements = requirements.map(str::to_string);
+        message.attributes = Some(attributes);
+        self
+    }
+    /// Replaces only the given attributes of an existing listing.
+    pub fn partial_update(&mut self, sku: &str, product_type: &str, attributes: Attributes) -> &mut Self {
+        self.push(sku, OperationType::PartialUpdate, Some(product_type)).attributes = Some(attributes);
+        self
+    }
+    /// Applies JSON Patch operations to an existing listing.
+    pub fn patch(&mut self, sku: &str, product_type: &str, patches: Vec<PatchOperation>) -> &mut Self {
+        self.push(sku, OperationType::Patch, Some(product_type)).patches = Some(patches);
+        self
+    }
+    pub fn delete(&mut self, sku: &str) -> &mut Self {
+        self.push(sku, OperationType::Delete, None);
+        self
+    }
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    fn validate(feed: &ListingsFeed) -> Result<(), Errors> {
+        if feed.messages.is_empty() {
+            return Err(Errors::CustomError("A listings feed needs at least one message".to_string()));
+        }
+        if feed.messages.len() > MAX_MESSAGES_PER_FEED {
+            return Err(Errors::CustomError(format!(
+                "A listings feed can contain at most {} messages, got {}",
+                MAX_MESSAGES_PER_FEED,
+                feed.messages.len()
+            )));
+        }
+        let size = feed.to_bytes()?.len();
+        if size > MAX_FEED_SIZE_BYTES {
+            return Err(Errors::CustomError(format!(
+                "A listings feed can be at most {} bytes, got {}",
+                MAX_FEED_SIZE_BYTES, size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Builds a single feed, failing if it is empty or over Amazon's limits.
+    pub fn build(self) -> Result<ListingsFeed, Errors> {
+        let feed = ListingsFeed { header: self.header, messages: self.messages };
+        Self::validate(&feed)?;
+        Ok(feed)
+    }
+
+    /// Builds as many feeds as needed to stay within Amazon's limits. Message IDs are renumbered
+    /// from 1 in every feed, so use each feed's own `message_skus` to correlate its report.
+    pub fn build_split(self) -> Result<Vec<ListingsFeed>, Errors> {
+        let header_size = serde_json::to_vec(&ListingsFeed { header: self.header.clone(), messages: Vec::new() })?.len();
+        let mut feeds = Vec::new();
+        let mut current: Vec<ListingsFeedMessage> = Vec::new();
+        let mut current_size = header_size;
+
+        for mut message in self.messages {
+            // Messages are separated by a comma in the serialized array.
+            let size = serde_json::to_vec(&message)?.len() + 1;
+            if header_size + size > MAX_FEED_SIZE_BYTES {
+                return Err(Errors::CustomError(format!("The message for SKU {} alone is over the feed size limit", message.sku)));
+            }
+            if current.len() == MAX_MESSAGES_PER_FEED || current_size + size > MAX_FEED_SIZE_BYTES {
+                feeds.push(ListingsFeed { header: self.header.clone(), messages: std::mem::take(&mut current) });
+                current_size = header_size;
+            }
+            message.message_id = current.len() as u32 + 1;
+            current_size += size;
+            current.push(message);
+        }
+        if !current.is_empty() {
+            feeds.push(ListingsFeed { header: self.header, messages: current });
+        }
+        if feeds.is_empty() {
+            return Err(Errors::CustomError("A listings feed needs at least one message".to_string()));
+        }
+        Ok(feeds)
+    }
+}
+
+/// The outcome of one message of a listings feed, matched back to its SKU.
+#[derive(Debug, Clone)]
+pub struct ListingsMessageResult {
+    pub message_id: u32,
+    pub sku: String,
+    /// `false` when the report lists at least one `ERROR` for the message.
+    pub accepted: bool,
+    pub issues: Vec<ProcessingIssue>,
+}
+
+impl ListingsFeed {
+    /// Parses the processing report of this feed, with the SKU of every issue filled in.
+    pub fn processing_report(&self, processing_report: &[u8]) -> Result<ProcessingReport, Errors> {
+        Ok(ProcessingReport::from_json(processing_report)?.with_message_skus(&self.message_skus()))
+    }
+
+    /// Matches the issues of the processing report back to the messages of this feed.
+    ///
+    /// Every message of the feed is returned, in order, including those without issues.
+    pub fn correlate(&self, processing_report: &[u8]) -> Result<Vec<ListingsMessageResult>, Errors> {
+        let report = self.processing_report(processing_report)?;
+        let mut by_message: HashMap<u32, Vec<ProcessingIssue>> = HashMap::new();
+        for issue in report.issues {
+            by_message.entry(issue.message_id).or_default().push(issue);
+        }
+
+        Ok(self
+            .messages
+            .iter()
+            .map(|m| {
+                let issues = by_message.remove(&m.message_id).unwrap_or_default();
+                ListingsMessageResult {
+                    message_id: m.message_id,
+                    sku: m.sku.clone(),
+                    accepted: !issues.iter().any(|i| i.severity == IssueSeverity::Error),
+                    issues,
+                }
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,523 @@
+//! The Merchant Fulfillment API, for buying Amazon shipping labels for seller-fulfilled orders.
+use std::path::{Path, PathBuf};
+use base64::Engine;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::error_handling::Errors;
+use crate::general::{gunzip, parse_payload, Client};
+
+pub struct MerchantFulfillment;
+
+const BASE: &str = "/mfn/v0";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Address {
+    pub name: String,
+    pub address_line1: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_line2: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_line3: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub district_or_county: Option<String>,
+    #[serde(default)]
+    pub email: String,
+    #[serde(default)]
+    pub city: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_or_province_code: Option<String>,
+    #[serde(default)]
+    pub postal_code: String,
+    #[serde(default)]
+    pub country_code: String,
+    #[serde(default)]
+    pub phone: String,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeightUnit {
+    oz,
+    g,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Weight {
+    pub value: f64,
+    pub unit: WeightUnit,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LengthUnit {
+    inches,
+    centimeters,
+}
+
+/// The dimensions of a package, or a predefined package such as `USPS_Box_Small`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PackageDimensions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<LengthUnit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub predefined_package_dimensions: Option<String>,
+}
+impl PackageDimensions {
+    pub fn new(length: f64, width: f64, height: f64, unit: LengthUnit) -> Self {
+        PackageDimensions { length: Some(length), width: Some(width), height: Some(height), unit: Some(unit), predefined_package_dimensions: None }
+    }
+    pub fn predefined(name: &str) -> Self {
+        PackageDimensions { predefined_package_dimensions: Some(name.to_string()), ..Default::default() }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CurrencyAmount {
+    pub currency_code: String,
+    pub amount: f64,
+}
+
+/// A value the carrier requires from the seller, e.g. for hazmat or customs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AdditionalSellerInput {
+    /// `STRING`, `BOOLEAN`, `INTEGER`, `TIMESTAMP`, `ADDRESS`, `WEIGHT`, `DIMENSION` or `CURRENCY`.
+    pub data_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_as_string: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_as_boolean: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_as_integer: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_as_timestamp: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_as_address: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_as_weight: Option<Weight>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_as_dimension: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_as_currency: Option<CurrencyAmount>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AdditionalSellerInputs {
+    pub additional_input_field_name: String,
+    pub additional_seller_input: AdditionalSellerInput,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Item {
+    pub order_item_id: String,
+    pub quantity: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_weight: Option<Weight>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transparency_code_list: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub item_level_seller_inputs_list: Vec<AdditionalSellerInputs>,
+}
+impl Item {
+    pub fn new(order_item_id: &str, quantity: u32) -> Self {
+        Item { order_item_id: order_item_id.to_string(), quantity, ..Default::default() }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeliveryExperienceType {
+    DeliveryConfirmationWithAdultSignature,
+    DeliveryConfirmationWithSignature,
+    DeliveryConfirmationWithoutSignature,
+    NoTracking,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CarrierWillPickUpOption {
+    CarrierWillPickUp,
+    ShipperWillDropOff,
+    NoPreference,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LabelFormat {
+    PDF,
+    PNG,
+    ZPL203,
+    ZPL300,
+    ShippingServiceDefault,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ShippingServiceOptions {
+    pub delivery_experience: DeliveryExperienceType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub declared_value: Option<CurrencyAmount>,
+    pub carrier_will_pick_up: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carrier_will_pick_up_option: Option<CarrierWillPickUpOption>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label_format: Option<LabelFormat>,
+}
+impl ShippingServiceOptions {
+    pub fn new(delivery_experience: DeliveryExperienceType, carrier_will_pick_up: bool) -> Self {
+        ShippingServiceOptions { delivery_experience, declared_value: None, carrier_will_pick_up, carrier_will_pick_up_option: None, label_format: None }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct LabelCustomization {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_text_for_label: Option<String>,
+    /// `AmazonOrderId` to print the order ID on the label.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub standard_id_for_label: Option<String>,
+}
+
+/// The shipment to get services for, or to buy a label for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ShipmentRequestDetails {
+    pub amazon_order_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seller_order_id: Option<String>,
+    pub item_list: Vec<Item>,
+    pub ship_from_address: Address,
+    pub package_dimensions: PackageDimensions,
+    pub weight: Weight,
+    /// The date by which the package must arrive, in ISO 8601 format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub must_arrive_by_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ship_date: Option<String>,
+    pub shipping_service_options: ShippingServiceOptions,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label_customization: Option<LabelCustomization>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ShippingOfferingFilter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_packing_slip_with_label: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_complex_shipping_options: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carrier_will_pick_up: Option<CarrierWillPickUpOption>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivery_experience: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct AvailableFormatOptionsForLabel {
+    pub include_packing_slip_with_label: Option<bool>,
+    pub label_format: Option<LabelFormat>,
+}
+
+/// A shipping service offered for a shipment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ShippingService {
+    pub shipping_service_name: String,
+    pub carrier_name: String,
+    pub shipping_service_id: String,
+    pub shipping_service_offer_id: String,
+    pub ship_date: String,
+    #[serde(default)]
+    pub earliest_estimated_delivery_date: Option<String>,
+    #[serde(default)]
+    pub latest_estimated_delivery_date: Option<String>,
+    pub rate: CurrencyAmount,
+    pub shipping_service_options: ShippingServiceOptions,
+    #[serde(default)]
+    pub available_shipping_service_options: Option<Value>,
+    #[serde(default)]
+    pub available_label_formats: Vec<LabelFormat>,
+    #[serde(default)]
+    pub available_format_options_for_label: Vec<AvailableFormatOptionsForLabel>,
+    #[serde(default)]
+    pub requires_additional_seller_inputs: bool,
+    #[serde(default)]
+    pub benefits: Option<Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct RejectedShippingService {
+    pub carrier_name: String,
+    pub shipping_service_name: String,
+    pub shipping_service_id: String,
+    pub rejection_reason_code: String,
+    pub rejection_reason_message: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct CarrierName {
+    pub carrier_name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct GetEligibleShipmentServicesResult {
+    pub shipping_service_list: Vec<ShippingService>,
+    pub rejected_shipping_service_list: Vec<RejectedShippingService>,
+    pub temporarily_unavailable_carrier_list: Vec<CarrierName>,
+    pub terms_and_conditions_not_accepted_carrier_list: Vec<CarrierName>,
+}
+
+/// The label file, base64-encoded and gzipped.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FileContents {
+    pub contents: String,
+    /// `application/pdf`, `application/zpl` or `image/png`.
+    pub file_type: String,
+    /// An MD5 hash of the contents.
+    pub checksum: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct LabelDimensions {
+    pub length: f64,
+    pub width: f64,
+    pub unit: Option<LengthUnit>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct Label {
+    pub custom_text_for_label: Option<String>,
+    pub dimensions: LabelDimensions,
+    pub file_contents: FileContents,
+    pub label_format: Option<LabelFormat>,
+    pub standard_id_for_label: Option<String>,
+}
+impl Label {
+    /// The file extension of the label: `pdf`, `png` or `zpl`.
+    pub fn extension(&self) -> &'static str {
+        match self.file_contents.file_type.as_str() {
+            "application/pdf" => "pdf",
+            "image/png" => "png",
+            "application/zpl" => "zpl",
+            _ => match self.label_format {
+                Some(LabelFormat::PDF) => "pdf",
+                Some(LabelFormat::ZPL203) | Some(LabelFormat::ZPL300) => "zpl",
+                _ => "png",
+            },
+        }
+    }
+
+    /// Decodes the label file: base64, then gunzip.
+    pub fn decode(&self) -> Result<Vec<u8>, Errors> {
+        let compressed = base64::engine::general_purpose::STANDARD.decode(self.file_contents.contents.trim())?;
+        // Labels are documented as gzipped, but keep the raw bytes if they are not.
+        if compressed.starts_with(&[0x1f, 0x8b]) {
+            gunzip(&compressed)
+        } else {
+            Ok(compressed)
+        }
+    }
+
+    /// Decodes the label and writes it to `dir`, named `file_stem` with the extension of its
+    /// file type.
+    ///
+    /// # Responses
+    /// - Returns the path of the written file.
+    pub fn save(&self, dir: &Path, file_stem: &str) -> Result<PathBuf, Errors> {
+        let path = dir.join(format!("{}.{}", file_stem, self.extension()));
+        std::fs::write(&path, self.decode()?)?;
+        Ok(path)
+    }
+}
+
+/// A purchased label and its shipment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct Shipment {
+    pub shipment_id: String,
+    pub amazon_order_id: String,
+    pub seller_order_id: Option<String>,
+    pub item_list: Vec<Item>,
+    pub ship_from_address: Address,
+    pub ship_to_address: Address,
+    pub package_dimensions: PackageDimensions,
+    pub weight: Option<Weight>,
+    pub insurance: Option<CurrencyAmount>,
+    pub shipping_service: Option<ShippingService>,
+    pub label: Label,
+    /// `Purchased`, `RefundPending`, `RefundRejected` or `RefundApplied`.
+    pub status: String,
+    pub tracking_id: Option<String>,
+    pub created_date: Option<String>,
+    pub last_updated_date: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CreateShipmentRequest {
+    pub shipment_request_details: ShipmentRequestDetails,
+    pub shipping_service_id: String,
+    /// Identifies a particular offer of a service; should be passed when the service has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shipping_service_offer_id: Option<String>,
+    /// `None` or `LQHazmat`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hazmat_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label_format_option: Option<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shipment_level_seller_inputs_list: Vec<AdditionalSellerInputs>,
+}
+impl CreateShipmentRequest {
+    pub fn new(shipment_request_details: ShipmentRequestDetails, service: &ShippingService) -> Self {
+        CreateShipmentRequest {
+            shipment_request_details,
+            shipping_service_id: service.shipping_service_id.clone(),
+            shipping_service_offer_id: Some(service.shipping_service_offer_id.clone()),
+            hazmat_type: None,
+            label_format_option: None,
+            shipment_level_seller_inputs_list: Vec::new(),
+        }
+    }
+}
+
+/// A field the carrier requires before a label can be bought.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct AdditionalInputs {
+    pub additional_input_field_name: String,
+    pub seller_input_definition: Value,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ItemLevelFields {
+    pub asin: String,
+    pub additional_inputs: Vec<AdditionalInputs>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct GetAdditionalSellerInputsResult {
+    pub shipment_level_fields: Vec<AdditionalInputs>,
+    pub item_level_fields_list: Vec<ItemLevelFields>,
+}
+
+impl MerchantFulfillment {
+    /// Returns a list of shipping service offers that satisfy the specified shipment request
+    /// details.
+    ///
+    /// Rate (requests per second): 6
+    ///
+    /// Burst: 12
+    ///
+    /// # Parameters
+    /// - `details`: The order, items, package and ship-from address.
+    /// - `filter` (optional): Filters the offers, e.g. to include complex shipping options.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns the offered services and the rejected or unavailable ones.
+    pub async fn get_eligible_shipment_services(
+        client: &mut Client,
+        details: &ShipmentRequestDetails,
+        filter: Option<&ShippingOfferingFilter>,
+    ) -> Result<GetEligibleShipmentServicesResult, Errors> {
+        let endpoint = format!("{}/eligibleShippingServices", BASE);
+        let mut body = json!({ "ShipmentRequestDetails": details });
+        if let Some(filter) = filter {
+            body["ShippingOfferingFilter"] = json!(filter);
+        }
+
+        parse_payload(client.make_request_w_body(&endpoint, Method::POST, None::<Vec<(String, String)>>, body.to_string()).await?).await
+    }
+
+    /// Returns the shipment information for an existing shipment.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 1
+    pub async fn get_shipment(client: &mut Client, shipment_id: &str) -> Result<Shipment, Errors> {
+        let endpoint = format!("{}/shipments/{}", BASE, shipment_id);
+
+        parse_payload(client.make_request(&endpoint, Method::GET, None::<Vec<(String, String)>>).await?).await
+    }
+
+    /// Cancels the shipment indicated by the specified shipment identifier, and requests a
+    /// refund of the label.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 1
+    pub async fn cancel_shipment(client: &mut Client, shipment_id: &str) -> Result<Shipment, Errors> {
+        let endpoint = format!("{}/shipments/{}", BASE, shipment_id);
+
+        parse_payload(client.make_request(&endpoint, Method::DELETE, None::<Vec<(String, String)>>).await?).await
+    }
+
+    /// Creates a shipment with the information provided, buying its label.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 2
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns the shipment with its label; save it with `Label::save`.
+    pub async fn create_shipment(client: &mut Client, request: &CreateShipmentRequest) -> Result<Shipment, Errors> {
+        let endpoint = format!("{}/shipments", BASE);
+
+        parse_payload(
+            client
+                .make_request_w_body(&endpoint, Method::POST, None::<Vec<(String, String)>>, serde_json::to_string(request)?)
+                .await?,
+        )
+        .await
+    }
+
+    /// Gets a list of additional seller inputs required for a ship method. This is generally
+    /// used for international shipping.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 1
+    ///
+    /// # Parameters
+    /// - `shipping_service_id`: The service to get the required inputs of.
+    /// - `ship_from_address`: The address the package ships from.
+    /// - `order_id`: The Amazon order ID.
+    pub async fn get_additional_seller_inputs(
+        client: &mut Client,
+        shipping_service_id: &str,
+        ship_from_address: &Address,
+        order_id: &str,
+    ) -> Result<GetAdditionalSellerInputsResult, Errors> {
+        let endpoint = format!("{}/additionalSellerInputs", BASE);
+        let body = json!({
+            "ShippingServiceId": shipping_service_id,
+            "ShipFromAddress": ship_from_address,
+            "OrderId": order_id,
+        })
+        .to_string();
+
+        parse_payload(client.make_request_w_body(&endpoint, Method::POST, None::<Vec<(String, String)>>, body).await?).await
+    }
+}
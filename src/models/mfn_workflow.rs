@@ -0,0 +1,577 @@
+//! An end-to-end workflow for seller-fulfilled (MFN) orders: pull the unshipped orders, buy the
+//! cheapest Merchant Fulfillment label that meets the promised delivery date, save the label and
+//! confirm the shipment with its tracking number.
+//!
+//! Progress is persisted as JSON after every step, so a run can be interrupted and re-run. A
+//! `Purchasing` marker is written before every label purchase; an order whose marker has no
+//! shipment ID is never bought again automatically, since the purchase may have gone through.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+use crate::error_handling::Errors;
+use crate::general::{Client, CountryMarketplace};
+use crate::models::merchant_fulfillment::{
+    Address, CreateShipmentRequest, DeliveryExperienceType, Item, Label, LabelFormat, MerchantFulfillment, PackageDimensions, Shipment,
+    ShipmentRequestDetails, ShippingService, ShippingServiceOptions, Weight,
+};
+use crate::models::orders::{ConfirmShipmentOrderItem, GetOrdersQuery, Order, OrderItem, Orders, PackageDetail, ORDER_ITEMS_INTERVAL};
+
+pub struct MfnWorkflowConfig {
+    pub marketplace: CountryMarketplace,
+    /// Only orders created after this date-time, in ISO 8601 format.
+    pub created_after: String,
+    pub ship_from: Address,
+    pub delivery_experience: DeliveryExperienceType,
+    pub carrier_will_pick_up: bool,
+    pub label_format: Option<LabelFormat>,
+    /// Where labels are saved, one file per order named after the order ID.
+    pub label_dir: PathBuf,
+    /// The JSON file the progress is persisted in.
+    pub state_path: PathBuf,
+}
+impl MfnWorkflowConfig {
+    pub fn new(marketplace: CountryMarketplace, created_after: &str, ship_from: Address, label_dir: &Path, state_path: &Path) -> Self {
+        MfnWorkflowConfig {
+            marketplace,
+            created_after: created_after.to_string(),
+            ship_from,
+            delivery_experience: DeliveryExperienceType::DeliveryConfirmationWithoutSignature,
+            carrier_will_pick_up: false,
+            label_format: None,
+            label_dir: label_dir.to_path_buf(),
+            state_path: state_path.to_path_buf(),
+        }
+    }
+}
+
+/// How far an order has got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stage {
+    /// A label purchase was started. Without a `shipment_id` its outcome is unknown.
+    Purchasing,
+    LabelPurchased,
+    LabelSaved,
+    Confirmed,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrderProgress {
+    pub stage: Option<Stage>,
+    pub shipping_service_id: Option<String>,
+    pub shipment_id: Option<String>,
+    pub tracking_id: Option<String>,
+    pub carrier_name: Option<String>,
+    pub shipping_service_name: Option<String>,
+    pub label_path: Option<PathBuf>,
+    pub last_error: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// The next thing to do for an order, decided from its persisted progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    AlreadyConfirmed,
+    /// A purchase with an unknown outcome blocks the order until it is resolved.
+    NeedsAttention(String),
+    /// The progress can't be continued from.
+    Broken(String),
+    Buy,
+    FetchLabel(String),
+    Confirm { shipment_id: String, tracking_id: String, carrier: String },
+}
+
+impl OrderProgress {
+    fn next_step(&self) -> Step {
+        match (self.stage, &self.shipment_id) {
+            (None, _) => Step::Buy,
+            (Some(Stage::Confirmed), _) => Step::AlreadyConfirmed,
+            (Some(Stage::Purchasing), None) => Step::NeedsAttention(format!(
+                "A label purchase was started without recording a shipment{}; check Seller Central before buying again",
+                self.last_error.as_ref().map(|e| format!(" ({})", e)).unwrap_or_default()
+            )),
+            (Some(Stage::Purchasing | Stage::LabelPurchased), Some(shipment_id)) => Step::FetchLabel(shipment_id.clone()),
+            (Some(Stage::LabelPurchased), None) => Step::Broken("A label was recorded as purchased without a shipment ID".to_string()),
+            (Some(Stage::LabelSaved), shipment_id) => match (shipment_id, &self.tracking_id, &self.carrier_name) {
+                (Some(shipment_id), Some(tracking_id), Some(carrier)) => {
+                    Step::Confirm { shipment_id: shipment_id.clone(), tracking_id: tracking_id.clone(), carrier: carrier.clone() }
+                }
+                _ => Step::Broken("The shipment has no tracking ID or carrier to confirm with".to_string()),
+            },
+        }
+    }
+
+    fn start_purchase(&mut self, shipping_service_id: &str) {
+        self.stage = Some(Stage::Purchasing);
+        self.shipping_service_id = Some(shipping_service_id.to_string());
+        self.last_error = None;
+    }
+
+    /// Records a failed `create_shipment`. A rejected request clears the marker so the order is
+    /// retried; otherwise the purchase may have gone through and the marker stays.
+    fn purchase_failed(&mut self, error: &Errors) -> OrderOutcome {
+        if purchase_rejected(error) {
+            let error = format!("createShipment was rejected: {}", error);
+            self.stage = None;
+            self.last_error = Some(error.clone());
+            OrderOutcome::Failed(error)
+        } else {
+            let error = format!("createShipment failed: {}", error);
+            self.last_error = Some(error.clone());
+            OrderOutcome::NeedsAttention(error)
+        }
+    }
+
+    fn record_shipment(&mut self, shipment: &Shipment) {
+        self.stage = Some(Stage::LabelPurchased);
+        self.shipment_id = Some(shipment.shipment_id.clone());
+        if shipment.tracking_id.is_some() {
+            self.tracking_id = shipment.tracking_id.clone();
+        }
+        if let Some(service) = &shipment.shipping_service {
+            self.carrier_name = Some(service.carrier_name.clone());
+            self.shipping_service_name = Some(service.shipping_service_name.clone());
+        }
+    }
+
+    fn label_saved(&mut self, path: PathBuf) {
+        self.stage = Some(Stage::LabelSaved);
+        self.label_path = Some(path);
+        self.last_error = None;
+    }
+
+    fn confirmed(&mut self) {
+        self.stage = Some(Stage::Confirmed);
+        self.last_error = None;
+    }
+}
+
+/// The persisted progress of every order the workflow has touched.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkflowState {
+    pub orders: BTreeMap<String, OrderProgress>,
+}
+impl WorkflowState {
+    /// Loads the state from `path`, or an empty state when the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, Errors> {
+        if !path.exists() {
+            return Ok(WorkflowState::default());
+        }
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    /// Writes the state to a temporary file and renames it over `path`, so a crash never leaves
+    /// a half-written state behind.
+    pub fn save(&self, path: &Path) -> Result<(), Errors> {
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// The orders whose label purchase was started but not recorded. They need to be checked
+    /// in Seller Central and resolved with `resolve_purchase`.
+    pub fn needs_attention(&self) -> impl Iterator<Item = &str> {
+        self.orders
+            .iter()
+            .filter(|(_, p)| p.stage == Some(Stage::Purchasing) && p.shipment_id.is_none())
+            .map(|(id, _)| id.as_str())
+    }
+
+    /// Resolves an unrecorded purchase: with the `shipment_id` that was bought, the workflow
+    /// continues with that label; with `None`, the marker is cleared and a new label may be
+    /// bought on the next run.
+    pub fn resolve_purchase(&mut self, order_id: &str, shipment_id: Option<&str>) {
+        match shipment_id {
+            Some(shipment_id) => {
+                let progress = self.orders.entry(order_id.to_string()).or_default();
+                progress.stage = Some(Stage::LabelPurchased);
+                progress.shipment_id = Some(shipment_id.to_string());
+                progress.last_error = None;
+            }
+            None => {
+                self.orders.remove(order_id);
+            }
+        }
+    }
+}
+
+/// What happened to an order in a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderOutcome {
+    Confirmed { shipment_id: String, tracking_id: Option<String> },
+    /// The order was confirmed in an earlier run.
+    AlreadyConfirmed,
+    /// A label purchase may have gone through without being recorded; see `WorkflowState::resolve_purchase`.
+    NeedsAttention(String),
+    /// The order was left alone, e.g. no package was given for it.
+    Skipped(String),
+    /// A step failed; the order is retried from that step on the next run.
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowReport {
+    pub outcomes: Vec<(String, OrderOutcome)>,
+}
+
+/// Chooses the cheapest service that is estimated to arrive by `deliver_by`, an ISO 8601
+/// date-time; ties go to the earliest delivery.
+///
+/// Services that require additional seller inputs are never chosen, since the workflow can't
+/// provide them. With a deadline, services without a delivery estimate never qualify.
+pub fn choose_service<'a>(services: &'a [ShippingService], deliver_by: Option<&str>) -> Option<&'a ShippingService> {
+    let parse = |date: Option<&str>| date.and_then(|d| DateTime::parse_from_rfc3339(d).ok()).map(|d| d.with_timezone(&Utc));
+    let deadline = parse(deliver_by);
+    services
+        .iter()
+        .filter(|s| !s.requires_additional_seller_inputs)
+        .filter(|s| match deadline {
+            Some(deadline) => parse(s.latest_estimated_delivery_date.as_deref()).is_some_and(|d| d <= deadline),
+            None => true,
+        })
+        .min_by(|a, b| {
+            a.rate
+                .amount
+                .partial_cmp(&b.rate.amount)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| parse(a.latest_estimated_delivery_date.as_deref()).cmp(&parse(b.latest_estimated_delivery_date.as_deref())))
+        })
+}
+
+/// Whether a failed `create_shipment` definitely bought nothing: Amazon answered with a client
+/// error. Transport errors, timeouts and server errors leave the outcome unknown.
+fn purchase_rejected(error: &Errors) -> bool {
+    matches!(error, Errors::ApiError(status, _) if (400..500).contains(status) && *status != 408)
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Runs the workflow over the unshipped MFN orders.
+///
+/// `package_for` gives the package dimensions and weight of an order; orders it returns `None`
+/// for are skipped.
+pub struct MfnWorkflow<F>
+where
+    F: FnMut(&Order, &[OrderItem]) -> Option<(PackageDimensions, Weight)>,
+{
+    config: MfnWorkflowConfig,
+    state: WorkflowState,
+    package_for: F,
+    next_order_items_call: Option<Instant>,
+}
+impl<F> MfnWorkflow<F>
+where
+    F: FnMut(&Order, &[OrderItem]) -> Option<(PackageDimensions, Weight)>,
+{
+    /// Creates the workflow, loading the progress of earlier runs from `config.state_path`.
+    pub fn new(config: MfnWorkflowConfig, package_for: F) -> Result<Self, Errors> {
+        let state = WorkflowState::load(&config.state_path)?;
+        Ok(MfnWorkflow { config, state, package_for, next_order_items_call: None })
+    }
+    pub fn state(&self) -> &WorkflowState {
+        &self.state
+    }
+    /// Resolves an unrecorded purchase and persists it; see `WorkflowState::resolve_purchase`.
+    pub fn resolve_purchase(&mut self, order_id: &str, shipment_id: Option<&str>) -> Result<(), Errors> {
+        self.state.resolve_purchase(order_id, shipment_id);
+        self.state.save(&self.config.state_path)
+    }
+
+    /// Pulls the unshipped MFN orders and takes each as far as it can go.
+    ///
+    /// A failed API call only fails its order. Failing to persist the state stops the run, so a
+    /// label is never bought without its marker on disk.
+    pub async fn run(&mut self, client: &mut Client) -> Result<WorkflowReport, Errors> {
+        std::fs::create_dir_all(&self.config.label_dir)?;
+        let query = GetOrdersQuery::unshipped_mfn(self.config.marketplace, &self.config.created_after);
+        let orders = Orders::orders(query).collect_all(client).await?;
+        let mut report = WorkflowReport::default();
+        for order in orders {
+            let outcome = self.process_order(client, &order).await?;
+            report.outcomes.push((order.amazon_order_id, outcome));
+        }
+        Ok(report)
+    }
+
+    /// Applies `change` to the progress of an order and persists the state.
+    fn update<T>(&mut self, order_id: &str, change: impl FnOnce(&mut OrderProgress) -> T) -> Result<T, Errors> {
+        let progress = self.state.orders.entry(order_id.to_string()).or_default();
+        let result = change(progress);
+        progress.updated_at = Some(now());
+        self.state.save(&self.config.state_path)?;
+        Ok(result)
+    }
+
+    fn fail(&mut self, order_id: &str, error: String) -> Result<OrderOutcome, Errors> {
+        self.update(order_id, |p| p.last_error = Some(error.clone()))?;
+        Ok(OrderOutcome::Failed(error))
+    }
+
+    fn save_label(&mut self, order_id: &str, label: &Label) -> Result<Option<OrderOutcome>, Errors> {
+        match label.save(&self.config.label_dir, order_id) {
+            Ok(path) => {
+                self.update(order_id, |p| p.label_saved(path))?;
+                Ok(None)
+            }
+            Err(e) => Ok(Some(self.fail(order_id, format!("Saving the label failed: {}", e))?)),
+        }
+    }
+
+    /// Fetches the items of an order, spacing the calls so a long run stays within the rate
+    /// limit of getOrderItems.
+    async fn order_items(&mut self, client: &mut Client, order_id: &str) -> Result<Vec<OrderItem>, Errors> {
+        if let Some(next) = self.next_order_items_call {
+            tokio::time::sleep_until(next).await;
+        }
+        let items = Orders::get_all_order_items(client, order_id).await;
+        self.next_order_items_call = Some(Instant::now() + ORDER_ITEMS_INTERVAL);
+        items
+    }
+
+    /// Takes one order from its persisted stage to `Confirmed`, one step at a time.
+    pub async fn process_order(&mut self, client: &mut Client, order: &Order) -> Result<OrderOutcome, Errors> {
+        let order_id = order.amazon_order_id.as_str();
+        let mut items: Option<Vec<OrderItem>> = None;
+        loop {
+            let step = self.state.orders.get(order_id).map(OrderProgress::next_step).unwrap_or(Step::Buy);
+            if matches!(step, Step::Buy | Step::Confirm { .. }) && items.is_none() {
+                match self.order_items(client, order_id).await {
+                    Ok(fetched) => items = Some(fetched),
+                    Err(e) => return self.fail(order_id, format!("Getting the order items failed: {}", e)),
+                }
+            }
+            let order_items = items.as_deref().unwrap_or_default();
+
+            match step {
+                Step::AlreadyConfirmed => return Ok(OrderOutcome::AlreadyConfirmed),
+                Step::NeedsAttention(reason) => return Ok(OrderOutcome::NeedsAttention(reason)),
+                Step::Broken(reason) => return self.fail(order_id, reason),
+                Step::Buy => {
+                    let Some((package_dimensions, weight)) = (self.package_for)(order, order_items) else {
+                        return Ok(OrderOutcome::Skipped("No package dimensions and weight were given".to_string()));
+                    };
+                    let mut options = ShippingServiceOptions::new(self.config.delivery_experience, self.config.carrier_will_pick_up);
+                    options.label_format = self.config.label_format;
+                    let details = ShipmentRequestDetails {
+                        amazon_order_id: order_id.to_string(),
+                        seller_order_id: order.seller_order_id.clone(),
+                        item_list: order_items
+                            .iter()
+                            .filter(|i| i.quantity_unshipped() > 0)
+                            .map(|i| Item::new(&i.order_item_id, i.quantity_unshipped()))
+                            .collect(),
+                        ship_from_address: self.config.ship_from.clone(),
+                        package_dimensions,
+                        weight,
+                        must_arrive_by_date: order.latest_delivery_date.clone(),
+                        ship_date: None,
+                        shipping_service_options: options,
+                        label_customization: None,
+                    };
+                    let services = match MerchantFulfillment::get_eligible_shipment_services(client, &details, None).await {
+                        Ok(services) => services,
+                        Err(e) => return self.fail(order_id, format!("Getting eligible services failed: {}", e)),
+                    };
+                    let Some(service) = choose_service(&services.shipping_service_list, order.latest_delivery_date.as_deref()) else {
+                        return self.fail(order_id, "No eligible service meets the promised delivery date".to_string());
+                    };
+
+                    // The marker must be on disk before the purchase; `update` fails the run otherwise.
+                    self.update(order_id, |p| p.start_purchase(&service.shipping_service_id))?;
+                    let shipment = match MerchantFulfillment::create_shipment(client, &CreateShipmentRequest::new(details, service)).await {
+                        Ok(shipment) => shipment,
+                        Err(e) => return self.update(order_id, |p| p.purchase_failed(&e)),
+                    };
+                    self.update(order_id, |p| p.record_shipment(&shipment))?;
+                    if let Some(outcome) = self.save_label(order_id, &shipment.label)? {
+                        return Ok(outcome);
+                    }
+                }
+                Step::FetchLabel(shipment_id) => {
+                    let shipment = match MerchantFulfillment::get_shipment(client, &shipment_id).await {
+                        Ok(shipment) => shipment,
+                        Err(e) => return self.fail(order_id, format!("Getting shipment {} failed: {}", shipment_id, e)),
+                    };
+                    self.update(order_id, |p| p.record_shipment(&shipment))?;
+                    if let Some(outcome) = self.save_label(order_id, &shipment.label)? {
+                        return Ok(outcome);
+                    }
+                }
+                Step::Confirm { shipment_id, tracking_id, carrier } => {
+                    let shipping_method = self.state.orders.get(order_id).and_then(|p| p.shipping_service_name.clone());
+                    let package_detail = PackageDetail {
+                        package_reference_id: "1".to_string(),
+                        carrier_code: carrier.clone(),
+                        carrier_name: Some(carrier),
+                        shipping_method,
+                        tracking_number: tracking_id.clone(),
+                        ship_date: now(),
+                        ship_from_supply_source_id: None,
+                        order_items: order_items
+                            .iter()
+                            .filter(|i| i.quantity_unshipped() > 0)
+                            .map(|i| ConfirmShipmentOrderItem { order_item_id: i.order_item_id.clone(), quantity: i.quantity_unshipped(), transparency_codes: Vec::new() })
+                            .collect(),
+                    };
+                    if let Err(e) = Orders::confirm_shipment(client, order_id, self.config.marketplace, &package_detail).await {
+                        return self.fail(order_id, format!("Confirming the shipment failed: {}", e));
+                    }
+                    self.update(order_id, |p| p.confirmed())?;
+                    return Ok(OrderOutcome::Confirmed { shipment_id, tracking_id: Some(tracking_id) });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(id: &str, amount: f64, latest_delivery: Option<&str>) -> ShippingService {
+        serde_json::from_value(serde_json::json!({
+            "ShippingServiceName": format!("Service {}", id),
+            "CarrierName": "USPS",
+            "ShippingServiceId": id,
+            "ShippingServiceOfferId": format!("offer-{}", id),
+            "ShipDate": "2026-10-19T00:00:00Z",
+            "LatestEstimatedDeliveryDate": latest_delivery,
+            "Rate": { "CurrencyCode": "USD", "Amount": amount },
+            "ShippingServiceOptions": { "DeliveryExperience": "DeliveryConfirmationWithoutSignature", "CarrierWillPickUp": false },
+        }))
+        .unwrap()
+    }
+
+    fn chosen(services: &[ShippingService], deliver_by: Option<&str>) -> Option<String> {
+        choose_service(services, deliver_by).map(|s| s.shipping_service_id.clone())
+    }
+
+    #[test]
+    fn choose_service_takes_the_cheapest_that_meets_the_deadline() {
+        let services = [
+            service("late", 5.0, Some("2026-10-25T00:00:00Z")),
+            service("fast", 9.0, Some("2026-10-21T00:00:00Z")),
+            service("on-time", 7.0, Some("2026-10-22T00:00:00Z")),
+            service("no-estimate", 1.0, None),
+        ];
+        assert_eq!(chosen(&services, Some("2026-10-22T23:59:59Z")).as_deref(), Some("on-time"));
+        assert_eq!(chosen(&services, Some("2026-10-22T00:00:00Z")).as_deref(), Some("on-time"));
+        assert_eq!(chosen(&services, Some("2026-10-20T00:00:00Z")), None);
+        assert_eq!(chosen(&services, None).as_deref(), Some("no-estimate"));
+    }
+
+    #[test]
+    fn choose_service_skips_services_that_need_seller_inputs() {
+        let mut needs_inputs = service("inputs", 1.0, Some("2026-10-21T00:00:00Z"));
+        needs_inputs.requires_additional_seller_inputs = true;
+        let services = [needs_inputs, service("plain", 3.0, Some("2026-10-21T00:00:00Z"))];
+        assert_eq!(chosen(&services, Some("2026-10-22T00:00:00Z")).as_deref(), Some("plain"));
+        assert_eq!(chosen(&services[..1], None), None);
+    }
+
+    #[test]
+    fn choose_service_breaks_ties_by_earliest_delivery() {
+        let services = [
+            service("later", 4.0, Some("2026-10-22T00:00:00Z")),
+            service("earlier", 4.0, Some("2026-10-21T00:00:00Z")),
+        ];
+        assert_eq!(chosen(&services, Some("2026-10-23T00:00:00Z")).as_deref(), Some("earlier"));
+        assert_eq!(chosen(&services, None).as_deref(), Some("earlier"));
+    }
+
+    fn shipment() -> Shipment {
+        let mut shipment = Shipment { shipment_id: "shp-1".to_string(), tracking_id: Some("TRK1".to_string()), ..Default::default() };
+        shipment.shipping_service = Some(service("svc", 4.0, None));
+        shipment
+    }
+
+    #[test]
+    fn stages_go_from_purchase_to_confirmation() {
+        let mut progress = OrderProgress::default();
+        assert_eq!(progress.next_step(), Step::Buy);
+
+        progress.start_purchase("svc");
+        progress.record_shipment(&shipment());
+        assert_eq!(progress.next_step(), Step::FetchLabel("shp-1".to_string()));
+
+        progress.label_saved(PathBuf::from("labels/111.pdf"));
+        assert_eq!(
+            progress.next_step(),
+            Step::Confirm { shipment_id: "shp-1".to_string(), tracking_id: "TRK1".to_string(), carrier: "USPS".to_string() }
+        );
+
+        progress.confirmed();
+        assert_eq!(progress.next_step(), Step::AlreadyConfirmed);
+    }
+
+    #[test]
+    fn interrupted_purchase_is_never_bought_again() {
+        let mut progress = OrderProgress::default();
+        progress.start_purchase("svc");
+        assert!(matches!(progress.next_step(), Step::NeedsAttention(_)));
+
+        let outcome = progress.purchase_failed(&Errors::ApiError(503, "unavailable".to_string()));
+        assert!(matches!(outcome, OrderOutcome::NeedsAttention(_)));
+        assert!(matches!(progress.next_step(), Step::NeedsAttention(_)));
+
+        let outcome = progress.purchase_failed(&Errors::CustomError("connection reset".to_string()));
+        assert!(matches!(outcome, OrderOutcome::NeedsAttention(_)));
+        assert!(matches!(progress.next_step(), Step::NeedsAttention(_)));
+    }
+
+    #[test]
+    fn rejected_purchase_is_retried() {
+        let mut progress = OrderProgress::default();
+        progress.start_purchase("svc");
+        let outcome = progress.purchase_failed(&Errors::ApiError(400, "InvalidInput".to_string()));
+        assert!(matches!(outcome, OrderOutcome::Failed(_)));
+        assert_eq!(progress.next_step(), Step::Buy);
+
+        progress.start_purchase("svc");
+        progress.purchase_failed(&Errors::ApiError(408, "timeout".to_string()));
+        assert!(matches!(progress.next_step(), Step::NeedsAttention(_)));
+    }
+
+    #[test]
+    fn label_saved_without_tracking_is_broken() {
+        let mut progress = OrderProgress::default();
+        progress.start_purchase("svc");
+        progress.record_shipment(&Shipment { shipment_id: "shp-1".to_string(), ..Default::default() });
+        progress.label_saved(PathBuf::from("labels/111.pdf"));
+        assert!(matches!(progress.next_step(), Step::Broken(_)));
+    }
+
+    #[test]
+    fn resolve_purchase_unblocks_the_order() {
+        let mut state = WorkflowState::default();
+        state.orders.entry("111".to_string()).or_default().start_purchase("svc");
+        state.orders.entry("222".to_string()).or_default().start_purchase("svc");
+        assert_eq!(state.needs_attention().collect::<Vec<_>>(), ["111", "222"]);
+
+        state.resolve_purchase("111", Some("shp-1"));
+        state.resolve_purchase("222", None);
+        assert_eq!(state.needs_attention().count(), 0);
+        assert_eq!(state.orders["111"].next_step(), Step::FetchLabel("shp-1".to_string()));
+        assert!(!state.orders.contains_key("222"));
+    }
+
+    #[test]
+    fn state_survives_a_save_and_load() {
+        let path = std::env::temp_dir().join(format!("mfn-workflow-{}.json", std::process::id()));
+        let mut state = WorkflowState::default();
+        state.orders.entry("111".to_string()).or_default().start_purchase("svc");
+        state.save(&path).unwrap();
+
+        let loaded = WorkflowState::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.orders["111"].stage, Some(Stage::Purchasing));
+        assert_eq!(loaded.orders["111"].shipping_service_id.as_deref(), Some("svc"));
+        assert!(matches!(loaded.orders["111"].next_step(), Step::NeedsAttention(_)));
+    }
+}
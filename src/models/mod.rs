@@ -1,25 +1,36 @@
 pub mod kiosk;
-mod easy_ship;
-mod fba;
-mod feeds;
-mod finances;
-mod fulfillment_inbound;
-mod fulfillment_outbound;
+pub mod easy_ship;
+pub mod fba;
+pub mod feeds;
+pub mod finances;
+pub mod finances_transactions;
+pub mod fulfillment_inbound;
+pub mod fulfillment_inbound_boxes;
+pub mod fulfillment_outbound;
+pub mod fulfillment_outbound_import;
 mod invoices;
 pub mod listings;
-mod merchant_fulfillment;
+pub mod listings_feed;
+pub mod merchant_fulfillment;
+pub mod mfn_workflow;
 mod messaging;
-mod notifications;
-mod orders;
-mod product_fees;
+pub mod notifications;
+pub mod notification_payloads;
+pub mod notification_consumer;
+pub mod notification_guard;
+pub mod orders;
+pub mod product_fees;
+pub mod processing_report;
 pub mod product_pricing;
+pub mod product_pricing_competitive;
+pub mod profit_and_loss;
 mod replenishment;
 mod reports;
 mod sales;
 pub mod sellers;
 mod services;
 mod shipment_invoicing;
-mod shipping;
+pub mod shipping;
 mod solicitations;
 mod supply_sources;
 mod tokens;
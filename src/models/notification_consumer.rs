@@ -0,0 +1,575 @@
+//! A long-running consumer for notifications delivered to an Amazon SQS queue, or to a local
+//! SQS-compatible stand-in such as ElasticMQ.
+//!
+//! Messages are received with long polling, parsed with `parse_notification` and handed to a
+//! `NotificationHandler`. A message is deleted only after its handler succeeded; while handlers
+//! run, the visibility timeout of every message of the batch not yet processed is extended so no
+//! other consumer receives it.
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+use hmac::{Hmac, Mac};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tokio::time::Instant;
+use crate::error_handling::Errors;
+use crate::general::Client;
+use crate::models::notification_payloads::{parse_notification, Notification};
+use crate::models::notifications::{Destination, DestinationResourceSpecification, NotificationType, Notifications, Subscription};
+use crate::poller::CancellationToken;
+
+/// AWS credentials used to sign SQS requests. Local stand-ins accept any values.
+#[derive(Debug, Clone)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+impl AwsCredentials {
+    pub fn new(access_key_id: &str, secret_access_key: &str) -> Self {
+        AwsCredentials { access_key_id: access_key_id.to_string(), secret_access_key: secret_access_key.to_string(), session_token: None }
+    }
+}
+
+/// A message as received from SQS.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct SqsMessage {
+    pub message_id: String,
+    pub receipt_handle: String,
+    pub body: String,
+    pub attributes: HashMap<String, String>,
+}
+impl SqsMessage {
+    /// How many times the message has been received, including this time.
+    pub fn receive_count(&self) -> u32 {
+        self.attributes.get("ApproximateReceiveCount").and_then(|c| c.parse().ok()).unwrap_or(1)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct ReceiveMessageResult {
+    messages: Vec<SqsMessage>,
+}
+
+/// A minimal client for the SQS JSON protocol, signed with AWS Signature Version 4.
+#[derive(Debug, Clone)]
+pub struct SqsClient {
+    endpoint: Url,
+    region: String,
+    credentials: AwsCredentials,
+    http: reqwest::Client,
+}
+impl SqsClient {
+    /// `endpoint` is e.g. `https://sqs.us-east-1.amazonaws.com` or `http://localhost:9324`.
+    pub fn new(endpoint: &str, region: &str, credentials: AwsCredentials) -> Result<Self, Errors> {
+        Ok(SqsClient { endpoint: Url::parse(endpoint)?, region: region.to_string(), credentials, http: reqwest::Client::new() })
+    }
+
+    async fn call<T: serde::de::DeserializeOwned>(&self, action: &str, body: Value) -> Result<T, Errors> {
+        let body = body.to_string();
+        let target = format!("AmazonSQS.{}", action);
+        let headers = self.sign(&target, &body, chrono::Utc::now())?;
+
+        let mut request = self.http.post(self.endpoint.clone()).body(body);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            return Err(Errors::ApiError(status.as_u16(), text));
+        }
+        if text.trim().is_empty() {
+            return Ok(serde_json::from_str("{}")?);
+        }
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// The headers of a signed request with the given `X-Amz-Target` and body.
+    fn sign(&self, target: &str, body: &str, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<(String, String)>, Errors> {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let host = match (self.endpoint.host_str(), self.endpoint.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => return Err(Errors::CustomError("The SQS endpoint has no host".to_string())),
+        };
+
+        let mut headers = vec![
+            ("content-type".to_string(), "application/x-amz-json-1.0".to_string()),
+            ("host".to_string(), host),
+            ("x-amz-date".to_string(), amz_date),
+            ("x-amz-target".to_string(), target.to_string()),
+        ];
+        if let Some(token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token".to_string(), token.clone()));
+        }
+        headers.sort();
+        let path = if self.endpoint.path().is_empty() { "/" } else { self.endpoint.path() };
+        let (scope, signed_headers, signature) = signature_v4(&self.credentials.secret_access_key, &self.region, "sqs", now, "POST", path, &headers, body);
+
+        headers.retain(|(n, _)| n != "host");
+        headers.push((
+            "authorization".to_string(),
+            format!("AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}", self.credentials.access_key_id, scope, signed_headers, signature),
+        ));
+        Ok(headers)
+    }
+
+    /// Receives up to `max_messages` messages, waiting up to `wait_time` for one to arrive.
+    pub async fn receive_messages(&self, queue_url: &str, max_messages: u32, wait_time: Duration, visibility_timeout: Duration) -> Result<Vec<SqsMessage>, Errors> {
+        let result: ReceiveMessageResult = self
+            .call(
+                "ReceiveMessage",
+                json!({
+                    "QueueUrl": queue_url,
+                    "MaxNumberOfMessages": max_messages.clamp(1, 10),
+                    "WaitTimeSeconds": wait_time.as_secs().min(20),
+                    "VisibilityTimeout": visibility_timeout.as_secs(),
+                    "AttributeNames": ["ApproximateReceiveCount"],
+                    "MessageSystemAttributeNames": ["ApproximateReceiveCount"],
+                }),
+            )
+            .await?;
+        Ok(result.messages)
+    }
+
+    pub async fn delete_message(&self, queue_url: &str, receipt_handle: &str) -> Result<(), Errors> {
+        self.call::<Value>("DeleteMessage", json!({ "QueueUrl": queue_url, "ReceiptHandle": receipt_handle })).await?;
+        Ok(())
+    }
+
+    pub async fn change_message_visibility(&self, queue_url: &str, receipt_handle: &str, visibility_timeout: Duration) -> Result<(), Errors> {
+        self.call::<Value>(
+            "ChangeMessageVisibility",
+            json!({ "QueueUrl": queue_url, "ReceiptHandle": receipt_handle, "VisibilityTimeout": visibility_timeout.as_secs() }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn send_message(&self, queue_url: &str, body: &str) -> Result<(), Errors> {
+        self.call::<Value>("SendMessage", json!({ "QueueUrl": queue_url, "MessageBody": body })).await?;
+        Ok(())
+    }
+}
+
+/// Signs a request without a query string with AWS Signature Version 4. `headers` are the
+/// lowercase headers to sign, sorted by name. Returns the credential scope, the signed header
+/// names and the signature.
+#[allow(clippy::too_many_arguments)]
+fn signature_v4(
+    secret_access_key: &str,
+    region: &str,
+    service: &str,
+    now: chrono::DateTime<chrono::Utc>,
+    method: &str,
+    path: &str,
+    headers: &[(String, String)],
+    body: &str,
+) -> (String, String, String) {
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let canonical_headers: String = headers.iter().map(|(n, v)| format!("{}:{}\n", n, v.trim())).collect();
+    let signed_headers = headers.iter().map(|(n, _)| n.as_str()).collect::<Vec<&str>>().join(";");
+    let canonical_request = format!("{}\n{}\n\n{}\n{}\n{}", method, path, canonical_headers, signed_headers, hex::encode(Sha256::digest(body)));
+
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!("AWS4-HMAC-SHA256\n{}\n{}\n{}", amz_date, scope, hex::encode(Sha256::digest(&canonical_request)));
+    let mut key = hmac_sha256(format!("AWS4{}", secret_access_key).as_bytes(), date.as_bytes());
+    for part in [region, service, "aws4_request"] {
+        key = hmac_sha256(&key, part.as_bytes());
+    }
+    let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+    (scope, signed_headers, signature)
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Where and how the consumer reads its queue.
+#[derive(Debug, Clone)]
+pub struct ConsumerConfig {
+    pub queue_url: String,
+    /// Where requests are sent. Defaults to the scheme, host and port of `queue_url`.
+    pub endpoint: Option<String>,
+    pub region: String,
+    pub credentials: AwsCredentials,
+    /// How long a receive waits for a message to arrive, at most 20 seconds.
+    pub wait_time: Duration,
+    /// How many messages are received at once, at most 10.
+    pub max_messages: u32,
+    /// How long a received message stays hidden from other consumers. Extended until the
+    /// message is processed.
+    pub visibility_timeout: Duration,
+    /// Messages received more often than this are dead-lettered instead of handled again.
+    pub max_receive_count: Option<u32>,
+    /// The queue that dead-lettered messages are moved to. Without one, they are reported to the
+    /// handler and left for the queue's own redrive policy.
+    pub dead_letter_queue_url: Option<String>,
+    /// `run` returns the error after this many receives in a row failed. Without a limit it
+    /// keeps retrying transient errors.
+    pub max_receive_errors: Option<u32>,
+}
+impl ConsumerConfig {
+    pub fn new(queue_url: &str, region: &str, credentials: AwsCredentials) -> Self {
+        ConsumerConfig {
+            queue_url: queue_url.to_string(),
+            endpoint: None,
+            region: region.to_string(),
+            credentials,
+            wait_time: Duration::from_secs(20),
+            max_messages: 10,
+            visibility_timeout: Duration::from_secs(30),
+            max_receive_count: None,
+            dead_letter_queue_url: None,
+            max_receive_errors: None,
+        }
+    }
+    /// Sends requests to `endpoint`, e.g. `http://localhost:9324` for ElasticMQ.
+    pub fn endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = Some(endpoint.to_string());
+        self
+    }
+    pub fn wait_time(mut self, wait_time: Duration) -> Self {
+        self.wait_time = wait_time;
+        self
+    }
+    pub fn max_messages(mut self, max_messages: u32) -> Self {
+        self.max_messages = max_messages;
+        self
+    }
+    pub fn visibility_timeout(mut self, visibility_timeout: Duration) -> Self {
+        self.visibility_timeout = visibility_timeout;
+        self
+    }
+    pub fn dead_letter(mut self, max_receive_count: u32, dead_letter_queue_url: Option<String>) -> Self {
+        self.max_receive_count = Some(max_receive_count);
+        self.dead_letter_queue_url = dead_letter_queue_url;
+        self
+    }
+    pub fn max_receive_errors(mut self, max_receive_errors: u32) -> Self {
+        self.max_receive_errors = Some(max_receive_errors);
+        self
+    }
+
+    fn sqs_client(&self) -> Result<SqsClient, Errors> {
+        let endpoint = match &self.endpoint {
+            Some(endpoint) => endpoint.clone(),
+            None => {
+                let url = Url::parse(&self.queue_url)?;
+                url.origin().ascii_serialization()
+            }
+        };
+        SqsClient::new(&endpoint, &self.region, self.credentials.clone())
+    }
+}
+
+/// Why a message was dead-lettered.
+#[derive(Debug, Clone)]
+pub enum DeadLetterReason {
+    /// The body is not a notification.
+    Unparseable(String),
+    /// The message was received more often than `max_receive_count`.
+    TooManyReceives(u32),
+}
+
+/// Handles the notifications received by a `NotificationConsumer`.
+///
+/// Closures `FnMut(Notification) -> impl Future<Output = Result<(), Errors>>` are handlers too.
+pub trait NotificationHandler {
+    /// Handles one notification. The message is deleted only when this returns `Ok`; otherwise it
+    /// becomes visible again after the visibility timeout and is retried.
+    fn handle(&mut self, notification: Notification) -> impl Future<Output = Result<(), Errors>>;
+
+    /// Called for every dead-lettered message, before it is moved to the dead-letter queue.
+    fn dead_letter(&mut self, _message: &SqsMessage, _reason: &DeadLetterReason) -> impl Future<Output = ()> {
+        async {}
+    }
+
+    /// Called when a receive failed and is going to be retried. `consecutive` counts the failed
+    /// receives in a row, starting at 1.
+    fn receive_error(&mut self, _error: &Errors, _consecutive: u32) -> impl Future<Output = ()> {
+        async {}
+    }
+}
+impl<F, Fut> NotificationHandler for F
+where
+    F: FnMut(Notification) -> Fut,
+    Fut: Future<Output = Result<(), Errors>>,
+{
+    fn handle(&mut self, notification: Notification) -> impl Future<Output = Result<(), Errors>> {
+        self(notification)
+    }
+}
+
+/// What happened to the messages of one receive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsumeStats {
+    pub received: usize,
+    pub handled: usize,
+    pub failed: usize,
+    pub dead_lettered: usize,
+    /// The messages whose delete or dead-lettering failed, by message ID. They become visible
+    /// again after the visibility timeout.
+    pub errors: Vec<(String, String)>,
+}
+
+pub struct NotificationConsumer<H: NotificationHandler> {
+    config: ConsumerConfig,
+    sqs: SqsClient,
+    handler: H,
+    cancellation: CancellationToken,
+}
+impl<H: NotificationHandler> NotificationConsumer<H> {
+    pub fn new(config: ConsumerConfig, handler: H) -> Result<Self, Errors> {
+        let sqs = config.sqs_client()?;
+        Ok(NotificationConsumer { config, sqs, handler, cancellation: CancellationToken::new() })
+    }
+    /// Stops `run` once the current receive is done.
+    pub fn cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+    pub fn into_handler(self) -> H {
+        self.handler
+    }
+
+    /// Receives messages until cancelled.
+    ///
+    /// Transient receive errors are reported to `NotificationHandler::receive_error` and retried
+    /// with a growing delay, up to `max_receive_errors` in a row. Errors that retrying can't fix,
+    /// such as bad credentials or a queue that doesn't exist, are returned right away.
+    pub async fn run(&mut self) -> Result<(), Errors> {
+        let cancellation = self.cancellation.clone();
+        let mut delay = Duration::from_secs(1);
+        let mut consecutive = 0;
+        while !cancellation.is_cancelled() {
+            match self.poll_once().await {
+                Ok(_) => {
+                    delay = Duration::from_secs(1);
+                    consecutive = 0;
+                }
+                Err(e) => {
+                    consecutive += 1;
+                    // SQS reports throttling as a 400, so it is told apart by its error code.
+                    let permanent = matches!(&e, Errors::ApiError(status, body)
+                        if (400..500).contains(status) && *status != 429 && !body.contains("Throttl"));
+                    if permanent || self.config.max_receive_errors.is_some_and(|max| consecutive >= max) {
+                        return Err(e);
+                    }
+                    self.handler.receive_error(&e, consecutive).await;
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => {}
+                        _ = cancellation.cancelled() => {}
+                    }
+                    delay = (delay * 2).min(Duration::from_secs(60));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Receives one batch of messages and processes each of them.
+    ///
+    /// The messages are handled one after another, so the visibility of every message not yet
+    /// processed is extended while earlier ones are handled. A failed delete or dead-lettering is
+    /// recorded in `errors` and the rest of the batch is still processed.
+    pub async fn poll_once(&mut self) -> Result<ConsumeStats, Errors> {
+        let messages = self
+            .sqs
+            .receive_messages(&self.config.queue_url, self.config.max_messages, self.config.wait_time, self.config.visibility_timeout)
+            .await?;
+        let mut stats = ConsumeStats { received: messages.len(), ..Default::default() };
+        let mut next_extension = Instant::now() + self.extend_every();
+        for (i, message) in messages.iter().enumerate() {
+            let held = &messages[i..];
+            if Instant::now() >= next_extension {
+                next_extension = self.extend_visibility(held).await;
+            }
+            match self.process(message, held, &mut next_extension).await {
+                Ok(Outcome::Handled) => stats.handled += 1,
+                Ok(Outcome::Failed) => stats.failed += 1,
+                Ok(Outcome::DeadLettered) => stats.dead_lettered += 1,
+                Err(e) => stats.errors.push((message.message_id.clone(), e.to_string())),
+            }
+        }
+        Ok(stats)
+    }
+
+    /// How often the visibility of held messages is extended, well before it runs out.
+    fn extend_every(&self) -> Duration {
+        (self.config.visibility_timeout / 2).max(Duration::from_secs(1))
+    }
+
+    /// Extends the visibility of `held` and returns when to extend it next. Failures are
+    /// ignored; at worst the message is received again.
+    async fn extend_visibility(&self, held: &[SqsMessage]) -> Instant {
+        for message in held {
+            let _ = self
+                .sqs
+                .change_message_visibility(&self.config.queue_url, &message.receipt_handle, self.config.visibility_timeout)
+                .await;
+        }
+        Instant::now() + self.extend_every()
+    }
+
+    /// Processes `message`, the first of the messages in `held`.
+    async fn process(&mut self, message: &SqsMessage, held: &[SqsMessage], next_extension: &mut Instant) -> Result<Outcome, Errors> {
+        if let Some(max) = self.config.max_receive_count {
+            let count = message.receive_count();
+            if count > max {
+                self.dead_letter(message, DeadLetterReason::TooManyReceives(count)).await?;
+                return Ok(Outcome::DeadLettered);
+            }
+        }
+        let notification = match parse_notification(&message.body) {
+            Ok(notification) => notification,
+            Err(e) => {
+                self.dead_letter(message, DeadLetterReason::Unparseable(e.to_string())).await?;
+                return Ok(Outcome::DeadLettered);
+            }
+        };
+
+        let extend_every = self.extend_every();
+        let handled = {
+            let handle = self.handler.handle(notification);
+            tokio::pin!(handle);
+            loop {
+                tokio::select! {
+                    result = &mut handle => break result,
+                    _ = tokio::time::sleep_until(*next_extension) => {
+                        *next_extension = Instant::now() + extend_every;
+                        for held in held {
+                            let _ = self
+                                .sqs
+                                .change_message_visibility(&self.config.queue_url, &held.receipt_handle, self.config.visibility_timeout)
+                                .await;
+                        }
+                    }
+                }
+            }
+        };
+
+        match handled {
+            Ok(()) => {
+                self.sqs.delete_message(&self.config.queue_url, &message.receipt_handle).await?;
+                Ok(Outcome::Handled)
+            }
+            Err(_) => Ok(Outcome::Failed),
+        }
+    }
+
+    async fn dead_letter(&mut self, message: &SqsMessage, reason: DeadLetterReason) -> Result<(), Errors> {
+        self.handler.dead_letter(message, &reason).await;
+        if let Some(dead_letter_queue_url) = &self.config.dead_letter_queue_url {
+            self.sqs.send_message(dead_letter_queue_url, &message.body).await?;
+            self.sqs.delete_message(&self.config.queue_url, &message.receipt_handle).await?;
+        }
+        Ok(())
+    }
+}
+
+enum Outcome {
+    Handled,
+    Failed,
+    DeadLettered,
+}
+
+/// Makes sure the SQS queue `queue_arn` is a destination and that each notification type is
+/// subscribed to it, creating what is missing.
+///
+/// **DEV NOTE:** Amazon allows only one subscription per type. A subscription of the same type to
+/// another destination may belong to another consumer, so it is an error unless
+/// `replace_conflicting` is set, in which case it is deleted and replaced.
+///
+/// # Parameters
+/// - `destination_name`: The name to give the destination if it has to be created.
+/// - `queue_arn`: The ARN of the SQS queue the consumer reads.
+/// - `subscriptions`: The notification types with their payload versions, e.g. `(ORDER_CHANGE, "1.0")`.
+/// - `replace_conflicting`: Whether to replace subscriptions to another destination.
+pub async fn ensure_sqs_subscriptions(
+    client: &mut Client,
+    destination_name: &str,
+    queue_arn: &str,
+    subscriptions: &[(NotificationType, &str)],
+    replace_conflicting: bool,
+) -> Result<(Destination, Vec<Subscription>), Errors> {
+    let existing = Notifications::get_destinations(client).await?;
+    let destination = match existing.into_iter().find(|d| d.resource.sqs.as_ref().is_some_and(|s| s.arn == queue_arn)) {
+        Some(destination) => destination,
+        None => {
+            let specification = DestinationResourceSpecification::Sqs { arn: queue_arn.to_string() };
+            Notifications::create_destination(client, destination_name, specification).await?
+        }
+    };
+
+    let mut subscribed = Vec::with_capacity(subscriptions.len());
+    for (notification_type, payload_version) in subscriptions {
+        let subscription = match Notifications::get_subscription(client, *notification_type, Some(payload_version.to_string())).await {
+            Ok(subscription) if subscription.destination_id == destination.destination_id => subscription,
+            Ok(other) if !replace_conflicting => {
+                return Err(Errors::CustomError(format!(
+                    "{} is already subscribed to destination {}",
+                    notification_type, other.destination_id
+                )));
+            }
+            Ok(other) => {
+                Notifications::delete_subscription_by_id(client, *notification_type, &other.subscription_id).await?;
+                Notifications::create_subscription(client, *notification_type, payload_version, &destination.destination_id, None).await?
+            }
+            Err(Errors::ApiError(404, _)) => {
+                Notifications::create_subscription(client, *notification_type, payload_version, &destination.destination_id, None).await?
+            }
+            Err(e) => return Err(e),
+        };
+        subscribed.push(subscription);
+    }
+    Ok((destination, subscribed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const SECRET: &str = "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY";
+
+    fn example_time() -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap()
+    }
+
+    /// The `post-vanilla` case of the AWS Signature Version 4 test suite.
+    #[test]
+    fn signature_matches_the_aws_test_suite() {
+        let headers = vec![
+            ("host".to_string(), "example.amazonaws.com".to_string()),
+            ("x-amz-date".to_string(), "20150830T123600Z".to_string()),
+        ];
+        let (scope, signed_headers, signature) = signature_v4(SECRET, "us-east-1", "service", example_time(), "POST", "/", &headers, "");
+        assert_eq!(scope, "20150830/us-east-1/service/aws4_request");
+        assert_eq!(signed_headers, "host;x-amz-date");
+        assert_eq!(signature, "5da7c1a2acd57cee7505fc6676e4e544621c30862966e37dddb68e92efbe5d6b");
+    }
+
+    #[test]
+    fn sqs_requests_sign_their_headers() {
+        let client = SqsClient::new("http://localhost:9324", "us-east-1", AwsCredentials::new("AKIDEXAMPLE", SECRET)).unwrap();
+        let headers = client.sign("AmazonSQS.ReceiveMessage", "{}", example_time()).unwrap();
+        let authorization = &headers.iter().find(|(n, _)| n == "authorization").unwrap().1;
+        assert!(authorization.starts_with(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/sqs/aws4_request, SignedHeaders=content-type;host;x-amz-date;x-amz-target, Signature="
+        ));
+        assert!(headers.iter().all(|(n, _)| n != "host"));
+    }
+}
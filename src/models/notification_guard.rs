@@ -0,0 +1,351 @@
+//! De-duplication and ordering of notifications, which SP-API delivers at least once and not
+//! necessarily in order.
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::future::Future;
+use chrono::{DateTime, Utc};
+use crate::error_handling::Errors;
+use crate::models::notification_consumer::{DeadLetterReason, NotificationHandler, SqsMessage};
+use crate::models::notification_payloads::{Notification, NotificationPayload};
+
+/// Remembers the `NotificationId`s that have been applied.
+///
+/// Implement this over a database or cache to share it between consumers and restarts.
+pub trait SeenStore {
+    fn contains(&mut self, notification_id: &str) -> impl Future<Output = Result<bool, Errors>>;
+    fn insert(&mut self, notification_id: &str) -> impl Future<Output = Result<(), Errors>>;
+}
+
+/// A `SeenStore` in memory that remembers the most recent `capacity` ids.
+#[derive(Debug, Clone)]
+pub struct MemorySeenStore {
+    capacity: usize,
+    order: VecDeque<String>,
+    ids: HashSet<String>,
+}
+impl MemorySeenStore {
+    pub fn new(capacity: usize) -> Self {
+        MemorySeenStore { capacity: capacity.max(1), order: VecDeque::new(), ids: HashSet::new() }
+    }
+}
+impl Default for MemorySeenStore {
+    fn default() -> Self {
+        Self::new(100_000)
+    }
+}
+impl SeenStore for MemorySeenStore {
+    async fn contains(&mut self, notification_id: &str) -> Result<bool, Errors> {
+        Ok(self.ids.contains(notification_id))
+    }
+    async fn insert(&mut self, notification_id: &str) -> Result<(), Errors> {
+        if self.ids.insert(notification_id.to_string()) {
+            self.order.push_back(notification_id.to_string());
+            while self.order.len() > self.capacity {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.ids.remove(&oldest);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What notifications are ordered by: events about the same ASIN or the same order must be
+/// applied in `EventTime` order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OrderingKey {
+    Asin { marketplace_id: String, asin: String },
+    Order(String),
+}
+impl OrderingKey {
+    /// The ordering key of a notification, or `None` for types that are not ordered.
+    pub fn of(notification: &Notification) -> Option<Self> {
+        match &notification.payload {
+            NotificationPayload::AnyOfferChanged(p) | NotificationPayload::B2bAnyOfferChanged(p) => {
+                let trigger = p.offer_change_trigger.as_ref()?;
+                Some(OrderingKey::Asin {
+                    marketplace_id: trigger.marketplace_id.clone().unwrap_or_default(),
+                    asin: trigger.asin.clone()?,
+                })
+            }
+            NotificationPayload::PricingHealth(p) => {
+                let trigger = p.offer_change_trigger.as_ref()?;
+                Some(OrderingKey::Asin {
+                    marketplace_id: trigger.marketplace_id.clone().unwrap_or_default(),
+                    asin: trigger.asin.clone()?,
+                })
+            }
+            NotificationPayload::OrderChange(p) => p.amazon_order_id.clone().map(OrderingKey::Order),
+            NotificationPayload::OrderStatusChange(p) | NotificationPayload::MfnOrderStatusChange(p) => {
+                p.amazon_order_id.clone().map(OrderingKey::Order)
+            }
+            NotificationPayload::FbaOutboundShipmentStatus(p) => p.amazon_order_id.clone().map(OrderingKey::Order),
+            _ => None,
+        }
+    }
+}
+
+/// Remembers the `EventTime` of the last applied event per ASIN or order.
+///
+/// Implement this over a database or cache, next to the `SeenStore`, so events that are stale
+/// are still recognized after a restart.
+pub trait EventTimeStore {
+    fn get(&mut self, key: &OrderingKey) -> impl Future<Output = Result<Option<DateTime<Utc>>, Errors>>;
+    fn set(&mut self, key: &OrderingKey, event_time: DateTime<Utc>) -> impl Future<Output = Result<(), Errors>>;
+}
+
+/// An `EventTimeStore` in memory that keeps the `capacity` keys with the most recent events.
+///
+/// **DEV NOTE:** Once a key is evicted, an older event for it is applied again.
+#[derive(Debug, Clone)]
+pub struct MemoryEventTimeStore {
+    capacity: usize,
+    times: HashMap<OrderingKey, DateTime<Utc>>,
+    by_time: BTreeSet<(DateTime<Utc>, OrderingKey)>,
+}
+impl MemoryEventTimeStore {
+    pub fn new(capacity: usize) -> Self {
+        MemoryEventTimeStore { capacity: capacity.max(1), times: HashMap::new(), by_time: BTreeSet::new() }
+    }
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+}
+impl Default for MemoryEventTimeStore {
+    fn default() -> Self {
+        Self::new(100_000)
+    }
+}
+impl EventTimeStore for MemoryEventTimeStore {
+    async fn get(&mut self, key: &OrderingKey) -> Result<Option<DateTime<Utc>>, Errors> {
+        Ok(self.times.get(key).copied())
+    }
+    async fn set(&mut self, key: &OrderingKey, event_time: DateTime<Utc>) -> Result<(), Errors> {
+        if let Some(previous) = self.times.insert(key.clone(), event_time) {
+            self.by_time.remove(&(previous, key.clone()));
+        }
+        self.by_time.insert((event_time, key.clone()));
+        while self.times.len() > self.capacity {
+            if let Some((_, oldest)) = self.by_time.pop_first() {
+                self.times.remove(&oldest);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether a notification should be applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardDecision {
+    Apply,
+    /// A notification with the same `NotificationId` has already been applied.
+    Duplicate,
+    /// A newer event for the same ASIN or order has already been applied.
+    Stale { last_applied: DateTime<Utc> },
+}
+
+/// What `mark_applied` needs to know about a notification, taken before it is handed on.
+#[derive(Debug, Clone)]
+struct Applied {
+    notification_id: Option<String>,
+    key: Option<OrderingKey>,
+    event_time: Option<DateTime<Utc>>,
+}
+impl Applied {
+    fn of(notification: &Notification) -> Self {
+        Applied {
+            notification_id: notification.notification_id().map(str::to_string),
+            key: OrderingKey::of(notification),
+            event_time: notification
+                .event_time
+                .as_deref()
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&Utc)),
+        }
+    }
+}
+
+/// Drops duplicate and stale notifications.
+///
+/// Call `check` before applying a notification and `mark_applied` once it has been applied, or
+/// use `process` to do both. A notification that failed to apply is not marked, so its
+/// redelivery is applied.
+pub struct NotificationGuard<S: SeenStore, T: EventTimeStore = MemoryEventTimeStore> {
+    seen: S,
+    event_times: T,
+}
+impl<S: SeenStore> NotificationGuard<S> {
+    /// Keeps the last applied event times in a bounded `MemoryEventTimeStore`.
+    pub fn new(seen: S) -> Self {
+        NotificationGuard { seen, event_times: MemoryEventTimeStore::default() }
+    }
+}
+impl<S: SeenStore, T: EventTimeStore> NotificationGuard<S, T> {
+    pub fn with_event_time_store(seen: S, event_times: T) -> Self {
+        NotificationGuard { seen, event_times }
+    }
+    pub fn seen_store(&self) -> &S {
+        &self.seen
+    }
+    pub fn event_time_store(&self) -> &T {
+        &self.event_times
+    }
+    /// The `EventTime` of the last applied event for `key`.
+    pub async fn last_applied(&mut self, key: &OrderingKey) -> Result<Option<DateTime<Utc>>, Errors> {
+        self.event_times.get(key).await
+    }
+
+    pub async fn check(&mut self, notification: &Notification) -> Result<GuardDecision, Errors> {
+        self.decide(&Applied::of(notification)).await
+    }
+
+    pub async fn mark_applied(&mut self, notification: &Notification) -> Result<(), Errors> {
+        self.record(Applied::of(notification)).await
+    }
+
+    /// Applies `notification` with `apply` unless it is a duplicate or stale.
+    pub async fn process<F, Fut>(&mut self, notification: Notification, apply: F) -> Result<GuardDecision, Errors>
+    where
+        F: FnOnce(Notification) -> Fut,
+        Fut: Future<Output = Result<(), Errors>>,
+    {
+        let applied = Applied::of(&notification);
+        let decision = self.decide(&applied).await?;
+        if decision == GuardDecision::Apply {
+            apply(notification).await?;
+            self.record(applied).await?;
+        }
+        Ok(decision)
+    }
+
+    async fn decide(&mut self, applied: &Applied) -> Result<GuardDecision, Errors> {
+        if let Some(id) = &applied.notification_id {
+            if self.seen.contains(id).await? {
+                return Ok(GuardDecision::Duplicate);
+            }
+        }
+        if let (Some(key), Some(time)) = (&applied.key, applied.event_time) {
+            if let Some(last) = self.event_times.get(key).await? {
+                if time < last {
+                    return Ok(GuardDecision::Stale { last_applied: last });
+                }
+            }
+        }
+        Ok(GuardDecision::Apply)
+    }
+
+    async fn record(&mut self, applied: Applied) -> Result<(), Errors> {
+        if let Some(id) = &applied.notification_id {
+            self.seen.insert(id).await?;
+        }
+        if let (Some(key), Some(time)) = (applied.key, applied.event_time) {
+            if self.event_times.get(&key).await?.is_none_or(|last| time > last) {
+                self.event_times.set(&key, time).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A `NotificationHandler` that passes only new, in-order notifications on to `inner`.
+/// Duplicate and stale notifications count as handled, so the consumer deletes them.
+pub struct GuardedHandler<S: SeenStore, H: NotificationHandler, T: EventTimeStore = MemoryEventTimeStore> {
+    pub guard: NotificationGuard<S, T>,
+    pub inner: H,
+}
+impl<S: SeenStore, H: NotificationHandler> GuardedHandler<S, H> {
+    pub fn new(seen: S, inner: H) -> Self {
+        GuardedHandler { guard: NotificationGuard::new(seen), inner }
+    }
+}
+impl<S: SeenStore, H: NotificationHandler, T: EventTimeStore> GuardedHandler<S, H, T> {
+    pub fn with_guard(guard: NotificationGuard<S, T>, inner: H) -> Self {
+        GuardedHandler { guard, inner }
+    }
+}
+impl<S: SeenStore, H: NotificationHandler, T: EventTimeStore> NotificationHandler for GuardedHandler<S, H, T> {
+    async fn handle(&mut self, notification: Notification) -> Result<(), Errors> {
+        let applied = Applied::of(&notification);
+        if self.guard.decide(&applied).await? != GuardDecision::Apply {
+            return Ok(());
+        }
+        self.inner.handle(notification).await?;
+        self.guard.record(applied).await
+    }
+
+    async fn dead_letter(&mut self, message: &SqsMessage, reason: &DeadLetterReason) {
+        self.inner.dead_letter(message, reason).await
+    }
+
+    async fn receive_error(&mut self, error: &Errors, consecutive: u32) {
+        self.inner.receive_error(error, consecutive).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().with_timezone(&Utc)
+    }
+
+    fn applied(id: &str, order: &str, time: &str) -> Applied {
+        Applied { notification_id: Some(id.to_string()), key: Some(OrderingKey::Order(order.to_string())), event_time: Some(at(time)) }
+    }
+
+    #[tokio::test]
+    async fn memory_event_time_store_keeps_the_most_recent_keys() {
+        let mut store = MemoryEventTimeStore::new(2);
+        let (a, b, c) = (OrderingKey::Order("a".into()), OrderingKey::Order("b".into()), OrderingKey::Order("c".into()));
+        store.set(&a, at("2026-10-19T10:00:00Z")).await.unwrap();
+        store.set(&b, at("2026-10-19T09:00:00Z")).await.unwrap();
+        store.set(&a, at("2026-10-19T11:00:00Z")).await.unwrap();
+        store.set(&c, at("2026-10-19T12:00:00Z")).await.unwrap();
+
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get(&a).await.unwrap(), Some(at("2026-10-19T11:00:00Z")));
+        assert_eq!(store.get(&b).await.unwrap(), None);
+        assert_eq!(store.get(&c).await.unwrap(), Some(at("2026-10-19T12:00:00Z")));
+    }
+
+    #[tokio::test]
+    async fn guard_drops_duplicates_and_stale_events() {
+        let mut guard = NotificationGuard::new(MemorySeenStore::default());
+        guard.record(applied("n1", "111", "2026-10-19T10:00:00Z")).await.unwrap();
+
+        assert_eq!(guard.decide(&applied("n1", "111", "2026-10-19T10:00:00Z")).await.unwrap(), GuardDecision::Duplicate);
+        assert_eq!(
+            guard.decide(&applied("n0", "111", "2026-10-19T09:00:00Z")).await.unwrap(),
+            GuardDecision::Stale { last_applied: at("2026-10-19T10:00:00Z") }
+        );
+        assert_eq!(guard.decide(&applied("n2", "111", "2026-10-19T11:00:00Z")).await.unwrap(), GuardDecision::Apply);
+
+        // An older event applied late doesn't move the last applied time back.
+        guard.record(applied("n0", "111", "2026-10-19T09:00:00Z")).await.unwrap();
+        assert_eq!(guard.last_applied(&OrderingKey::Order("111".into())).await.unwrap(), Some(at("2026-10-19T10:00:00Z")));
+    }
+
+    #[derive(Default)]
+    struct RecordingHandler {
+        receive_errors: Vec<u32>,
+    }
+    impl NotificationHandler for RecordingHandler {
+        async fn handle(&mut self, _notification: Notification) -> Result<(), Errors> {
+            Ok(())
+        }
+        async fn receive_error(&mut self, _error: &Errors, consecutive: u32) {
+            self.receive_errors.push(consecutive);
+        }
+    }
+
+    #[tokio::test]
+    async fn guarded_handler_forwards_receive_errors() {
+        let mut handler = GuardedHandler::new(MemorySeenStore::default(), RecordingHandler::default());
+        handler.receive_error(&Errors::Timeout, 1).await;
+        handler.receive_error(&Errors::Timeout, 2).await;
+        assert_eq!(handler.inner.receive_errors, vec![1, 2]);
+    }
+}
@@ -0,0 +1,792 @@
+//! Typed notifications, as delivered to an SQS queue or an EventBridge event bus.
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use crate::error_handling::Errors;
+use crate::models::notifications::NotificationType;
+use crate::models::product_fees::MoneyType;
+use crate::models::product_pricing_competitive::MoneyType as CamelMoneyType;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationMetadata {
+    #[serde(rename = "ApplicationId", alias = "applicationId")]
+    pub application_id: Option<String>,
+    #[serde(rename = "SubscriptionId", alias = "subscriptionId")]
+    pub subscription_id: Option<String>,
+    #[serde(rename = "PublishTime", alias = "publishTime")]
+    pub publish_time: Option<String>,
+    #[serde(rename = "NotificationId", alias = "notificationId")]
+    pub notification_id: Option<String>,
+}
+
+/// A notification with its envelope and typed payload.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub notification_version: Option<String>,
+    /// The notification type as sent, also for types this crate doesn't know.
+    pub notification_type: String,
+    pub payload_version: Option<String>,
+    pub event_time: Option<String>,
+    pub notification_metadata: NotificationMetadata,
+    pub payload: NotificationPayload,
+}
+impl Notification {
+    pub fn notification_id(&self) -> Option<&str> {
+        self.notification_metadata.notification_id.as_deref()
+    }
+    /// The known notification type, or `None` for a type this crate doesn't know.
+    pub fn known_type(&self) -> Option<NotificationType> {
+        serde_json::from_value(Value::String(self.notification_type.clone())).ok()
+    }
+}
+
+#[derive(Deserialize)]
+struct RawNotification {
+    #[serde(rename = "NotificationVersion", alias = "notificationVersion", default)]
+    notification_version: Option<String>,
+    #[serde(rename = "NotificationType", alias = "notificationType")]
+    notification_type: String,
+    #[serde(rename = "PayloadVersion", alias = "payloadVersion", default)]
+    payload_version: Option<String>,
+    #[serde(rename = "EventTime", alias = "eventTime", default)]
+    event_time: Option<String>,
+    #[serde(rename = "NotificationMetadata", alias = "notificationMetadata", default)]
+    notification_metadata: NotificationMetadata,
+    #[serde(rename = "Payload", alias = "payload", default)]
+    payload: Value,
+}
+
+/// Parses a notification, either the SQS message body or an EventBridge event whose `detail`
+/// holds the notification.
+///
+/// Only an envelope that isn't a notification is an error. A payload that doesn't match the
+/// model of its type is returned as `NotificationPayload::Unparsed`.
+pub fn parse_notification(message: &str) -> Result<Notification, Errors> {
+    let mut value: Value = serde_json::from_str(message)?;
+    if let Some(detail) = value.get_mut("detail").filter(|d| d.is_object()) {
+        value = detail.take();
+    }
+    let raw: RawNotification = serde_json::from_value(value)?;
+    let payload = match NotificationPayload::parse(&raw.notification_type, raw.payload.clone()) {
+        Ok(payload) => payload,
+        Err(e) => NotificationPayload::Unparsed { payload: raw.payload, error: e.to_string() },
+    };
+
+    Ok(Notification {
+        notification_version: raw.notification_version,
+        notification_type: raw.notification_type,
+        payload_version: raw.payload_version,
+        event_time: raw.event_time,
+        notification_metadata: raw.notification_metadata,
+        payload,
+    })
+}
+
+/// The payload of a notification, by notification type.
+#[derive(Debug, Clone)]
+pub enum NotificationPayload {
+    AccountStatusChanged(AccountStatusChangedNotification),
+    AnyOfferChanged(AnyOfferChangedNotification),
+    B2bAnyOfferChanged(AnyOfferChangedNotification),
+    BrandedItemContentChange(BrandedItemContentChangeNotification),
+    DataKioskQueryProcessingFinished(DataKioskQueryProcessingFinishedNotification),
+    DetailPageTrafficEvent(DetailPageTrafficEventNotification),
+    ExternalFulfillmentShipmentStatusChange(ExternalFulfillmentShipmentStatusChangeNotification),
+    FbaInventoryAvailabilityChanges(FbaInventoryAvailabilityChangesNotification),
+    FbaOutboundShipmentStatus(FbaOutboundShipmentStatusNotification),
+    FeePromotion(FeePromotionNotification),
+    FeedProcessingFinished(FeedProcessingFinishedNotification),
+    FulfillmentOrderStatus(FulfillmentOrderStatusNotification),
+    ItemInventoryEventChange(ItemInventoryEventChangeNotification),
+    ItemProductTypeChange(ItemProductTypeChangeNotification),
+    ListingsItemIssuesChange(ListingsItemIssuesChangeNotification),
+    ListingsItemMfnQuantityChange(ListingsItemMfnQuantityChangeNotification),
+    ListingsItemStatusChange(ListingsItemStatusChangeNotification),
+    MfnOrderStatusChange(OrderStatusChangeNotification),
+    OrderChange(OrderChangeNotification),
+    OrderStatusChange(OrderStatusChangeNotification),
+    PricingHealth(PricingHealthNotification),
+    ProductTypeDefinitionsChange(ProductTypeDefinitionsChangeNotification),
+    ReportProcessingFinished(ReportProcessingFinishedNotification),
+    TransactionUpdate(TransactionUpdateNotification),
+    /// A notification type this crate doesn't know yet.
+    Unknown(Value),
+    /// A known notification type whose payload doesn't match its model, e.g. after Amazon
+    /// changed a field. Holds the raw payload and why it didn't parse.
+    Unparsed { payload: Value, error: String },
+}
+
+/// Some payloads wrap their content in a single named object, e.g.
+/// `{"AnyOfferChangedNotification": {...}}`. Returns that object, or the payload itself.
+fn unwrap_payload<T: serde::de::DeserializeOwned>(mut payload: Value, key: &str) -> Result<T, Errors> {
+    let inner = match payload.get_mut(key) {
+        Some(inner) => inner.take(),
+        None => payload,
+    };
+    Ok(serde_json::from_value(inner)?)
+}
+
+impl NotificationPayload {
+    fn parse(notification_type: &str, payload: Value) -> Result<Self, Errors> {
+        let notification_type: NotificationType = match serde_json::from_value(Value::String(notification_type.to_string())) {
+            Ok(t) => t,
+            Err(_) => return Ok(NotificationPayload::Unknown(payload)),
+        };
+        Ok(match notification_type {
+            NotificationType::AccountStatusChanged => {
+                NotificationPayload::AccountStatusChanged(unwrap_payload(payload, "accountStatusChangeNotification")?)
+            }
+            NotificationType::AnyOfferChanged => NotificationPayload::AnyOfferChanged(unwrap_payload(payload, "AnyOfferChangedNotification")?),
+            NotificationType::B2bAnyOfferChanged => {
+                NotificationPayload::B2bAnyOfferChanged(unwrap_payload(payload, "B2BAnyOfferChangedNotification")?)
+            }
+            NotificationType::BrandedItemContentChange => NotificationPayload::BrandedItemContentChange(serde_json::from_value(payload)?),
+            NotificationType::DataKioskQueryProcessingFinished => {
+                NotificationPayload::DataKioskQueryProcessingFinished(serde_json::from_value(payload)?)
+            }
+            NotificationType::DetailPageTrafficEvent => NotificationPayload::DetailPageTrafficEvent(serde_json::from_value(payload)?),
+            NotificationType::ExternalFulfillmentShipmentStatusChange => {
+                NotificationPayload::ExternalFulfillmentShipmentStatusChange(serde_json::from_value(payload)?)
+            }
+            NotificationType::FbaInventoryAvailabilityChanges => {
+                NotificationPayload::FbaInventoryAvailabilityChanges(serde_json::from_value(payload)?)
+            }
+            NotificationType::FbaOutboundShipmentStatus => {
+                NotificationPayload::FbaOutboundShipmentStatus(unwrap_payload(payload, "FBAOutboundShipmentStatusNotification")?)
+            }
+            NotificationType::FeePromotion => NotificationPayload::FeePromotion(unwrap_payload(payload, "FeePromotionNotification")?),
+            NotificationType::FeedProcessingFinished => {
+                NotificationPayload::FeedProcessingFinished(unwrap_payload(payload, "feedProcessingFinishedNotification")?)
+            }
+            NotificationType::FulfillmentOrderStatus => {
+                NotificationPayload::FulfillmentOrderStatus(unwrap_payload(payload, "FulfillmentOrderStatusNotification")?)
+            }
+            NotificationType::ItemInventoryEventChange => NotificationPayload::ItemInventoryEventChange(serde_json::from_value(payload)?),
+            NotificationType::ItemProductTypeChange => NotificationPayload::ItemProductTypeChange(serde_json::from_value(payload)?),
+            NotificationType::ListingsItemIssuesChange => NotificationPayload::ListingsItemIssuesChange(serde_json::from_value(payload)?),
+            NotificationType::ListingsItemMfnQuantityChange => {
+                NotificationPayload::ListingsItemMfnQuantityChange(serde_json::from_value(payload)?)
+            }
+            NotificationType::ListingsItemStatusChange => NotificationPayload::ListingsItemStatusChange(serde_json::from_value(payload)?),
+            NotificationType::MfnOrderStatusChange => {
+                NotificationPayload::MfnOrderStatusChange(unwrap_payload(payload, "MFNOrderStatusChangeNotification")?)
+            }
+            NotificationType::OrderChange => NotificationPayload::OrderChange(unwrap_payload(payload, "OrderChangeNotification")?),
+            NotificationType::OrderStatusChange => {
+                NotificationPayload::OrderStatusChange(unwrap_payload(payload, "OrderStatusChangeNotification")?)
+            }
+            NotificationType::PricingHealth => NotificationPayload::PricingHealth(serde_json::from_value(payload)?),
+            NotificationType::ProductTypeDefinitionsChange => {
+                NotificationPayload::ProductTypeDefinitionsChange(serde_json::from_value(payload)?)
+            }
+            NotificationType::ReportProcessingFinished => {
+                NotificationPayload::ReportProcessingFinished(unwrap_payload(payload, "reportProcessingFinishedNotification")?)
+            }
+            NotificationType::TransactionUpdate => {
+                NotificationPayload::TransactionUpdate(unwrap_payload(payload, "transactionUpdateNotification")?)
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AccountStatusChangedNotification {
+    pub seller_id: Option<String>,
+    /// `NORMAL`, `AT_RISK` or `DEACTIVATED`.
+    pub previous_account_status: Option<String>,
+    pub current_account_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OfferChangeTrigger {
+    pub marketplace_id: Option<String>,
+    #[serde(rename = "ASIN")]
+    pub asin: Option<String>,
+    pub item_condition: Option<String>,
+    pub time_of_offer_change: Option<String>,
+    /// `External`, `Internal` or `Featured Offer`.
+    pub offer_change_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OfferCount {
+    pub condition: Option<String>,
+    pub fulfillment_channel: Option<String>,
+    pub offer_count: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct NotificationPrice {
+    pub condition: Option<String>,
+    pub fulfillment_channel: Option<String>,
+    pub offer_type: Option<String>,
+    pub quantity_tier: Option<i64>,
+    pub quantity_discount_type: Option<String>,
+    pub landed_price: Option<MoneyType>,
+    pub listing_price: Option<MoneyType>,
+    pub shipping: Option<MoneyType>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct SalesRank {
+    pub product_category_id: Option<String>,
+    pub rank: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OfferSummary {
+    pub number_of_offers: Vec<OfferCount>,
+    pub lowest_prices: Vec<NotificationPrice>,
+    pub buy_box_prices: Vec<NotificationPrice>,
+    pub list_price: Option<MoneyType>,
+    pub suggested_lower_price_plus_shipping: Option<MoneyType>,
+    pub sales_rankings: Vec<SalesRank>,
+    pub number_of_buy_box_eligible_offers: Vec<OfferCount>,
+    pub competitive_price_threshold: Option<MoneyType>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct SellerFeedbackRating {
+    pub seller_positive_feedback_rating: Option<f64>,
+    pub feedback_count: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ShippingTime {
+    pub minimum_hours: Option<i64>,
+    pub maximum_hours: Option<i64>,
+    pub available_date: Option<String>,
+    pub availability_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ShipsFrom {
+    pub state: Option<String>,
+    pub country: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct PrimeInformation {
+    pub is_prime: Option<bool>,
+    pub is_national_prime: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct NotificationOffer {
+    pub seller_id: Option<String>,
+    pub sub_condition: Option<String>,
+    pub seller_feedback_rating: Option<SellerFeedbackRating>,
+    pub shipping_time: Option<ShippingTime>,
+    pub listing_price: Option<MoneyType>,
+    pub shipping: Option<MoneyType>,
+    pub ships_from: Option<ShipsFrom>,
+    pub is_fulfilled_by_amazon: Option<bool>,
+    pub is_buy_box_winner: Option<bool>,
+    pub condition_notes: Option<String>,
+    pub prime_information: Option<PrimeInformation>,
+    pub is_expedited_shipping_available: Option<bool>,
+    pub is_featured_merchant: Option<bool>,
+    pub ships_domestically: Option<bool>,
+}
+
+/// The payload of `ANY_OFFER_CHANGED` and `B2B_ANY_OFFER_CHANGED`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct AnyOfferChangedNotification {
+    pub seller_id: Option<String>,
+    pub offer_change_trigger: Option<OfferChangeTrigger>,
+    pub summary: Option<OfferSummary>,
+    pub offers: Vec<NotificationOffer>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct BrandedItemContentChangeNotification {
+    pub marketplace_id: Option<String>,
+    pub brand_name: Option<String>,
+    pub asin: Option<String>,
+    pub attributes_changed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DataKioskPagination {
+    pub next_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DataKioskQueryProcessingFinishedNotification {
+    pub account_id: Option<String>,
+    pub query_id: Option<String>,
+    pub query: Option<String>,
+    /// `DONE`, `CANCELLED` or `FATAL`.
+    pub processing_status: Option<String>,
+    pub data_document_id: Option<String>,
+    pub error_document_id: Option<String>,
+    pub pagination: Option<DataKioskPagination>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DetailPageTrafficEventNotification {
+    pub marketplace_id: Option<String>,
+    pub asin: Option<String>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub glance_views: Option<i64>,
+    /// Fields not modeled above.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExternalFulfillmentShipmentStatusChangeNotification {
+    pub seller_id: Option<String>,
+    pub marketplace_id: Option<String>,
+    pub shipment_id: Option<String>,
+    pub previous_status: Option<String>,
+    pub current_status: Option<String>,
+    /// Fields not modeled above.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct InboundQuantityBreakdown {
+    pub working: Option<i64>,
+    pub shipped: Option<i64>,
+    pub receiving: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ReservedQuantityBreakdown {
+    pub warehouse_processing: Option<i64>,
+    pub warehouse_transfer: Option<i64>,
+    pub pending_customer_order: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FulfillmentInventory {
+    pub inbound_quantity_breakdown: Option<InboundQuantityBreakdown>,
+    pub fulfillable: Option<i64>,
+    pub unfulfillable: Option<i64>,
+    pub researching: Option<i64>,
+    pub reserved_quantity_breakdown: Option<ReservedQuantityBreakdown>,
+    pub future_supply_buyable: Option<i64>,
+    pub pending_customer_order_in_transit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct MarketplaceInventory {
+    pub marketplace_id: Option<String>,
+    pub fulfillment_inventory: Option<FulfillmentInventory>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FbaInventoryAvailabilityChangesNotification {
+    pub seller_id: Option<String>,
+    #[serde(rename = "FNSKU")]
+    pub fnsku: Option<String>,
+    #[serde(rename = "ASIN")]
+    pub asin: Option<String>,
+    #[serde(rename = "SKU")]
+    pub sku: Option<String>,
+    pub fulfillment_inventory_by_marketplace: Vec<MarketplaceInventory>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FbaOutboundShipmentStatusNotification {
+    pub seller_id: Option<String>,
+    pub amazon_order_id: Option<String>,
+    pub amazon_shipment_id: Option<String>,
+    /// `Created`, `Cancelled` or `Closed`.
+    pub shipment_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FeePromotionIdentifier {
+    pub identifier_type: Option<String>,
+    pub identifier_values: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FeePromotionNotification {
+    pub merchant_id: Option<String>,
+    pub marketplace_id: Option<String>,
+    pub fee_promotion_type: Option<String>,
+    pub fee_promotion_type_description: Option<String>,
+    pub marketplace_name: Option<String>,
+    pub promotion_active_status: Option<String>,
+    pub promotion_information: Vec<Value>,
+    pub identifiers: Vec<FeePromotionIdentifier>,
+    pub effective_from_date: Option<String>,
+    pub effective_through_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FeedProcessingFinishedNotification {
+    pub seller_id: Option<String>,
+    pub account_id: Option<String>,
+    pub feed_id: Option<String>,
+    pub feed_type: Option<String>,
+    pub processing_status: Option<String>,
+    pub result_feed_document_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FulfillmentShipmentPackage {
+    pub package_number: Option<i64>,
+    pub carrier_code: Option<String>,
+    pub tracking_number: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FulfillmentShipment {
+    pub fulfillment_shipment_status: Option<String>,
+    pub amazon_shipment_id: Option<String>,
+    pub estimated_arrival_date_time: Option<String>,
+    pub fulfillment_shipment_packages: Vec<FulfillmentShipmentPackage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FulfillmentReturnItem {
+    pub received_date_time: Option<String>,
+    pub returned_quantity: Option<i64>,
+    #[serde(rename = "SellerSKU")]
+    pub seller_sku: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FulfillmentOrderStatusNotification {
+    pub seller_id: Option<String>,
+    /// `Order`, `Shipment` or `Return`.
+    pub event_type: Option<String>,
+    pub status_updated_date_time: Option<String>,
+    pub seller_fulfillment_order_id: Option<String>,
+    pub fulfillment_order_status: Option<String>,
+    pub fulfillment_shipment: Option<FulfillmentShipment>,
+    pub fulfillment_return_item: Option<FulfillmentReturnItem>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ItemInventoryEventChangeNotification {
+    pub seller_id: Option<String>,
+    pub marketplace_id: Option<String>,
+    pub asin: Option<String>,
+    pub sku: Option<String>,
+    /// Fields not modeled above.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ItemProductTypeChangeNotification {
+    pub marketplace_id: Option<String>,
+    pub asin: Option<String>,
+    pub previous_product_type: Option<String>,
+    pub current_product_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ListingsItemIssuesChangeNotification {
+    pub seller_id: Option<String>,
+    pub marketplace_id: Option<String>,
+    pub asin: Option<String>,
+    pub sku: Option<String>,
+    /// e.g. `ERROR`, `WARNING`.
+    pub severities: Vec<String>,
+    /// e.g. `SEARCH_SUPPRESSED`.
+    pub enforcement_actions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ListingsItemMfnQuantityChangeNotification {
+    pub seller_id: Option<String>,
+    pub fulfillment_channel_code: Option<String>,
+    pub sku: Option<String>,
+    pub quantity: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ListingsItemStatusChangeNotification {
+    pub seller_id: Option<String>,
+    pub marketplace_id: Option<String>,
+    pub asin: Option<String>,
+    pub sku: Option<String>,
+    pub created_date: Option<String>,
+    /// e.g. `BUYABLE`, `DISCOVERABLE`.
+    pub status: Vec<String>,
+}
+
+/// The payload of `ORDER_STATUS_CHANGE` and `MFN_ORDER_STATUS_CHANGE`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OrderStatusChangeNotification {
+    pub seller_id: Option<String>,
+    pub marketplace_id: Option<String>,
+    pub amazon_order_id: Option<String>,
+    /// Sent as epoch milliseconds, either as a number or a string.
+    pub purchase_date: Option<Value>,
+    pub order_status: Option<String>,
+    pub destination_postal_code: Option<String>,
+    pub supply_source_id: Option<String>,
+    pub order_item_id: Option<String>,
+    #[serde(rename = "SellerSKU")]
+    pub seller_sku: Option<String>,
+    pub quantity: Option<i64>,
+    pub fulfillment_channel: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OrderChangeTrigger {
+    pub time_of_order_change: Option<String>,
+    pub change_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OrderChangeItem {
+    pub order_item_id: Option<String>,
+    #[serde(rename = "SellerSKU")]
+    pub seller_sku: Option<String>,
+    pub supply_source_id: Option<String>,
+    pub quantity: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OrderChangeSummary {
+    pub marketplace_id: Option<String>,
+    pub order_status: Option<String>,
+    pub purchase_date: Option<String>,
+    pub destination_postal_code: Option<String>,
+    pub fulfillment_type: Option<String>,
+    pub order_type: Option<String>,
+    pub easy_ship_shipment_status: Option<String>,
+    pub order_programs: Vec<String>,
+    pub shipping_programs: Vec<String>,
+    pub order_items: Vec<OrderChangeItem>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OrderChangeNotification {
+    /// `OrderLevel` or `OrderItemLevel`.
+    pub notification_level: Option<String>,
+    pub seller_id: Option<String>,
+    pub amazon_order_id: Option<String>,
+    /// `OrderStatusChange` or `BuyerRequestedChange`.
+    pub order_change_type: Option<String>,
+    pub order_change_trigger: Option<OrderChangeTrigger>,
+    pub summary: Option<OrderChangeSummary>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PricingHealthOfferChangeTrigger {
+    pub marketplace_id: Option<String>,
+    pub asin: Option<String>,
+    pub item_condition: Option<String>,
+    pub time_of_offer_change: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MerchantOffer {
+    pub condition: Option<String>,
+    pub fulfillment_type: Option<String>,
+    pub listing_price: Option<CamelMoneyType>,
+    pub shipping_price: Option<CamelMoneyType>,
+    pub points: Option<Value>,
+    pub sku: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReferencePrices {
+    pub average_selling_price: Option<CamelMoneyType>,
+    pub competitive_price_threshold: Option<CamelMoneyType>,
+    pub msrp_price: Option<CamelMoneyType>,
+    pub retail_offer_price: Option<CamelMoneyType>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PricingHealthSummary {
+    pub number_of_offers: Vec<Value>,
+    pub buy_box_eligible_offers: Vec<Value>,
+    pub buy_box_prices: Vec<Value>,
+    pub lowest_prices: Vec<Value>,
+    pub reference_price: Option<ReferencePrices>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PricingHealthNotification {
+    /// e.g. `BuyBoxEligibility`.
+    pub issue_type: Option<String>,
+    pub seller_id: Option<String>,
+    pub offer_change_trigger: Option<PricingHealthOfferChangeTrigger>,
+    pub merchant_offer: Option<MerchantOffer>,
+    pub summary: Option<PricingHealthSummary>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ProductTypeDefinitionsChangeNotification {
+    pub account_id: Option<String>,
+    pub marketplace_id: Option<String>,
+    pub product_type: Option<String>,
+    pub product_type_version: Option<String>,
+    /// Fields not modeled above.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReportProcessingFinishedNotification {
+    pub seller_id: Option<String>,
+    pub account_id: Option<String>,
+    pub report_id: Option<String>,
+    pub report_type: Option<String>,
+    pub processing_status: Option<String>,
+    pub report_document_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TransactionUpdateNotification {
+    pub selling_partner_metadata: Option<Value>,
+    pub transaction_id: Option<String>,
+    pub transaction_type: Option<String>,
+    pub transaction_status: Option<String>,
+    pub posted_date: Option<String>,
+    /// Fields not modeled above.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mismatched_payload_keeps_the_envelope() {
+        let message = r#"{
+            "NotificationVersion": "1.0",
+            "NotificationType": "ANY_OFFER_CHANGED",
+            "PayloadVersion": "1.0",
+            "EventTime": "2026-10-19T10:00:00.000Z",
+            "Payload": { "AnyOfferChangedNotification": { "OfferChangeTrigger": "not an object" } },
+            "NotificationMetadata": { "NotificationId": "n-1" }
+        }"#;
+        let notification = parse_notification(message).unwrap();
+        assert_eq!(notification.notification_type, "ANY_OFFER_CHANGED");
+        assert_eq!(notification.notification_id(), Some("n-1"));
+        assert!(matches!(notification.payload, NotificationPayload::Unparsed { .. }));
+    }
+
+    #[test]
+    fn body_without_a_notification_type_is_an_error() {
+        assert!(parse_notification(r#"{"hello": "world"}"#).is_err());
+    }
+
+    #[test]
+    fn parses_fba_inventory_availability_changes() {
+        let message = r#"{
+            "NotificationVersion": "1.0",
+            "NotificationType": "FBA_INVENTORY_AVAILABILITY_CHANGES",
+            "PayloadVersion": "1.0",
+            "EventTime": "2026-10-19T10:00:00.000Z",
+            "Payload": {
+                "SellerId": "A1",
+                "FNSKU": "X000ABC",
+                "ASIN": "B000123",
+                "SKU": "SKU-1",
+                "FulfillmentInventoryByMarketplace": [{
+                    "MarketplaceId": "ATVPDKIKX0DER",
+                    "FulfillmentInventory": { "Fulfillable": 7, "Unfulfillable": 1 }
+                }]
+            },
+            "NotificationMetadata": { "NotificationId": "n-2" }
+        }"#;
+        let notification = parse_notification(message).unwrap();
+        let NotificationPayload::FbaInventoryAvailabilityChanges(payload) = notification.payload else {
+            panic!("unexpected payload {:?}", notification.payload);
+        };
+        assert_eq!(payload.fnsku.as_deref(), Some("X000ABC"));
+        assert_eq!(payload.asin.as_deref(), Some("B000123"));
+        assert_eq!(payload.sku.as_deref(), Some("SKU-1"));
+        let inventory = payload.fulfillment_inventory_by_marketplace[0].fulfillment_inventory.as_ref().unwrap();
+        assert_eq!(inventory.fulfillable, Some(7));
+    }
+
+    #[test]
+    fn parses_a_notification_wrapped_in_an_eventbridge_detail() {
+        let message = r#"{
+            "version": "0",
+            "id": "e-1",
+            "detail-type": "ORDER_CHANGE",
+            "source": "aws.partner/sellingpartnerapi.amazon.com/123/amzn1.sellingpartnerapi.123",
+            "detail": {
+                "notificationVersion": "1.0",
+                "notificationType": "ORDER_CHANGE",
+                "payloadVersion": "1.0",
+                "eventTime": "2026-10-19T10:00:00.000Z",
+                "notificationMetadata": { "notificationId": "n-3" },
+                "payload": {
+                    "OrderChangeNotification": {
+                        "NotificationLevel": "OrderLevel",
+                        "SellerId": "A1",
+                        "AmazonOrderId": "123-1234567-1234567",
+                        "OrderChangeType": "OrderStatusChange",
+                        "Summary": { "OrderStatus": "Shipped", "OrderItems": [{ "SellerSKU": "SKU-1", "Quantity": 2 }] }
+                    }
+                }
+            }
+        }"#;
+        let notification = parse_notification(message).unwrap();
+        assert_eq!(notification.notification_id(), Some("n-3"));
+        assert_eq!(notification.known_type(), Some(NotificationType::OrderChange));
+        let NotificationPayload::OrderChange(payload) = notification.payload else {
+            panic!("unexpected payload {:?}", notification.payload);
+        };
+        assert_eq!(payload.amazon_order_id.as_deref(), Some("123-1234567-1234567"));
+        let summary = payload.summary.unwrap();
+        assert_eq!(summary.order_status.as_deref(), Some("Shipped"));
+        assert_eq!(summary.order_items[0].seller_sku.as_deref(), Some("SKU-1"));
+    }
+}
@@ -0,0 +1,345 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::error_handling::Errors;
+use crate::general::{check_response, parse_payload, Client, CountryMarketplace, GrantlessScope};
+
+pub struct Notifications;
+
+/// The notification types that can be subscribed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, strum_macros::Display)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationType {
+    AccountStatusChanged,
+    AnyOfferChanged,
+    #[serde(rename = "B2B_ANY_OFFER_CHANGED")]
+    #[strum(serialize = "B2B_ANY_OFFER_CHANGED")]
+    B2bAnyOfferChanged,
+    BrandedItemContentChange,
+    DataKioskQueryProcessingFinished,
+    DetailPageTrafficEvent,
+    ExternalFulfillmentShipmentStatusChange,
+    FbaInventoryAvailabilityChanges,
+    FbaOutboundShipmentStatus,
+    FeePromotion,
+    FeedProcessingFinished,
+    FulfillmentOrderStatus,
+    ItemInventoryEventChange,
+    ItemProductTypeChange,
+    ListingsItemIssuesChange,
+    ListingsItemMfnQuantityChange,
+    ListingsItemStatusChange,
+    MfnOrderStatusChange,
+    OrderChange,
+    OrderStatusChange,
+    PricingHealth,
+    ProductTypeDefinitionsChange,
+    ReportProcessingFinished,
+    TransactionUpdate,
+}
+
+/// How often aggregated notifications are sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AggregationTimePeriod {
+    FiveMinutes,
+    TenMinutes,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregationSettings {
+    pub aggregation_time_period: AggregationTimePeriod,
+}
+
+/// The kind of event filter, which must match the notification type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EventFilterType {
+    AnyOfferChanged,
+    OrderChange,
+}
+
+/// The order changes an `ORDER_CHANGE` subscription is notified about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderChangeType {
+    OrderStatusChange,
+    BuyerRequestedChange,
+}
+
+/// Filters the notifications of a subscription.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventFilter {
+    pub event_filter_type: EventFilterType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregation_settings: Option<AggregationSettings>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub marketplace_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order_change_types: Vec<OrderChangeType>,
+}
+impl EventFilter {
+    pub fn new(event_filter_type: EventFilterType) -> Self {
+        EventFilter { event_filter_type, aggregation_settings: None, marketplace_ids: Vec::new(), order_change_types: Vec::new() }
+    }
+    pub fn aggregation(mut self, period: AggregationTimePeriod) -> Self {
+        self.aggregation_settings = Some(AggregationSettings { aggregation_time_period: period });
+        self
+    }
+    pub fn marketplaces(mut self, marketplaces: &[CountryMarketplace]) -> Self {
+        self.marketplace_ids = marketplaces.iter().map(|m| m.details().0.to_string()).collect();
+        self
+    }
+    pub fn order_change_types(mut self, types: Vec<OrderChangeType>) -> Self {
+        self.order_change_types = types;
+        self
+    }
+}
+
+/// Additional information about how notifications of a subscription are processed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingDirective {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_filter: Option<EventFilter>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Subscription {
+    pub subscription_id: String,
+    pub payload_version: String,
+    pub destination_id: String,
+    pub processing_directive: Option<ProcessingDirective>,
+}
+
+/// Where notifications are delivered.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DestinationResourceSpecification {
+    /// An Amazon SQS queue, given by its ARN.
+    Sqs { arn: String },
+    /// An Amazon EventBridge partner event source in the given AWS account and region.
+    #[serde(rename_all = "camelCase")]
+    EventBridge { region: String, account_id: String },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SqsResource {
+    pub arn: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventBridgeResource {
+    /// The name of the partner event source.
+    pub name: String,
+    pub region: String,
+    pub account_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DestinationResource {
+    pub sqs: Option<SqsResource>,
+    pub event_bridge: Option<EventBridgeResource>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Destination {
+    pub name: String,
+    pub destination_id: String,
+    pub resource: DestinationResource,
+}
+
+impl Notifications {
+    /// Returns information about the subscription for the specified notification type.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    ///
+    /// # Parameters
+    /// - `notification_type`: The type of notification.
+    /// - `payload_version` (optional): The version of the payload object to be used in the notification.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `Subscription`.
+    pub async fn get_subscription(client: &mut Client, notification_type: NotificationType, payload_version: Option<String>) -> Result<Subscription, Errors> {
+        let endpoint = format!("/notifications/v1/subscriptions/{}", notification_type);
+        let params = payload_version.map(|v| vec![("payloadVersion".to_string(), v)]);
+
+        parse_payload(client.make_request(&endpoint, Method::GET, params).await?).await
+    }
+
+    /// Creates a subscription for the specified notification type to be delivered to the
+    /// specified destination.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    ///
+    /// # Parameters
+    /// - `notification_type`: The type of notification.
+    /// - `payload_version`: The version of the payload object to be used in the notification, e.g. `1.0`.
+    /// - `destination_id`: The identifier for the destination where notifications will be delivered.
+    /// - `processing_directive` (optional): Event filters, only supported for `ANY_OFFER_CHANGED` and `ORDER_CHANGE`.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns the created `Subscription`.
+    pub async fn create_subscription(
+        client: &mut Client,
+        notification_type: NotificationType,
+        payload_version: &str,
+        destination_id: &str,
+        processing_directive: Option<ProcessingDirective>,
+    ) -> Result<Subscription, Errors> {
+        let endpoint = format!("/notifications/v1/subscriptions/{}", notification_type);
+        let mut body = json!({
+            "payloadVersion": payload_version,
+            "destinationId": destination_id,
+        });
+        if let Some(directive) = processing_directive {
+            body["processingDirective"] = json!(directive);
+        }
+
+        parse_payload(client.make_request_w_body(&endpoint, Method::POST, None::<Vec<(String, String)>>, body.to_string()).await?).await
+    }
+
+    /// Returns information about a subscription for the specified notification type. Uses a
+    /// grantless token.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    ///
+    /// # Parameters
+    /// - `notification_type`: The type of notification.
+    /// - `subscription_id`: The identifier for the subscription.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `Subscription`.
+    pub async fn get_subscription_by_id(client: &mut Client, notification_type: NotificationType, subscription_id: &str) -> Result<Subscription, Errors> {
+        let endpoint = format!("/notifications/v1/subscriptions/{}/{}", notification_type, subscription_id);
+
+        parse_payload(
+            client
+                .make_grantless_request(GrantlessScope::Notifications, &endpoint, Method::GET, None::<Vec<(String, String)>>, None)
+                .await?,
+        )
+        .await
+    }
+
+    /// Deletes the subscription indicated by the subscription identifier and notification type.
+    /// Uses a grantless token.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    ///
+    /// # Parameters
+    /// - `notification_type`: The type of notification.
+    /// - `subscription_id`: The identifier for the subscription.
+    ///
+    /// # Responses
+    /// - **200 (Success):** The subscription was deleted.
+    pub async fn delete_subscription_by_id(client: &mut Client, notification_type: NotificationType, subscription_id: &str) -> Result<(), Errors> {
+        let endpoint = format!("/notifications/v1/subscriptions/{}/{}", notification_type, subscription_id);
+
+        check_response(
+            client
+                .make_grantless_request(GrantlessScope::Notifications, &endpoint, Method::DELETE, None::<Vec<(String, String)>>, None)
+                .await?,
+        )
+        .await
+    }
+
+    /// Creates a destination resource to receive notifications. Uses a grantless token.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    ///
+    /// # Parameters
+    /// - `name`: A developer-defined name to help identify this destination.
+    /// - `resource_specification`: The SQS queue or EventBridge event source to deliver to.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns the created `Destination`.
+    pub async fn create_destination(client: &mut Client, name: &str, resource_specification: DestinationResourceSpecification) -> Result<Destination, Errors> {
+        let body = json!({
+            "name": name,
+            "resourceSpecification": resource_specification,
+        });
+
+        parse_payload(
+            client
+                .make_grantless_request(GrantlessScope::Notifications, "/notifications/v1/destinations", Method::POST, None::<Vec<(String, String)>>, Some(body.to_string()))
+                .await?,
+        )
+        .await
+    }
+
+    /// Returns information about all destinations. Uses a grantless token.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns the list of `Destination`.
+    pub async fn get_destinations(client: &mut Client) -> Result<Vec<Destination>, Errors> {
+        parse_payload(
+            client
+                .make_grantless_request(GrantlessScope::Notifications, "/notifications/v1/destinations", Method::GET, None::<Vec<(String, String)>>, None)
+                .await?,
+        )
+        .await
+    }
+
+    /// Returns information about the destination that you specify. Uses a grantless token.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    ///
+    /// # Parameters
+    /// - `destination_id`: The identifier generated when you created the destination.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `Destination`.
+    pub async fn get_destination(client: &mut Client, destination_id: &str) -> Result<Destination, Errors> {
+        let endpoint = format!("/notifications/v1/destinations/{}", destination_id);
+
+        parse_payload(
+            client
+                .make_grantless_request(GrantlessScope::Notifications, &endpoint, Method::GET, None::<Vec<(String, String)>>, None)
+                .await?,
+        )
+        .await
+    }
+
+    /// Deletes the destination that you specify. Uses a grantless token.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 5
+    ///
+    /// # Parameters
+    /// - `destination_id`: The identifier for the destination that you want to delete.
+    ///
+    /// # Responses
+    /// - **200 (Success):** The destination was deleted.
+    pub async fn delete_destination(client: &mut Client, destination_id: &str) -> Result<(), Errors> {
+        let endpoint = format!("/notifications/v1/destinations/{}", destination_id);
+
+        check_response(
+            client
+                .make_grantless_request(GrantlessScope::Notifications, &endpoint, Method::DELETE, None::<Vec<(String, String)>>, None)
+                .await?,
+        )
+        .await
+    }
+}
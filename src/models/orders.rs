@@ -1 +1,300 @@
+//! The v0 Orders API, for reading orders and confirming the shipment of seller-fulfilled ones.
+use std::time::Duration;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::error_handling::Errors;
+use crate::general::{check_response, parse_payload, Client, CountryMarketplace};
+use crate::pagination::{Page, PagedRequest, Paginator};
 
+pub struct Orders;
+
+const BASE: &str = "/orders/v0";
+/// getOrders allows 0.0167 requests per second, with a burst of 20.
+const ORDERS_INTERVAL: Duration = Duration::from_secs(60);
+/// getOrderItems allows 0.5 requests per second after its burst of 30.
+pub const ORDER_ITEMS_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct Money {
+    pub currency_code: Option<String>,
+    pub amount: Option<String>,
+}
+
+/// A shipping address. Buyer addresses are only returned with a restricted data token.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct Address {
+    pub name: Option<String>,
+    pub address_line1: Option<String>,
+    pub address_line2: Option<String>,
+    pub address_line3: Option<String>,
+    pub city: Option<String>,
+    pub county: Option<String>,
+    pub district: Option<String>,
+    pub state_or_region: Option<String>,
+    pub postal_code: Option<String>,
+    pub country_code: Option<String>,
+    pub phone: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct Order {
+    pub amazon_order_id: String,
+    pub seller_order_id: Option<String>,
+    pub purchase_date: String,
+    pub last_update_date: String,
+    /// e.g. `Pending`, `Unshipped`, `PartiallyShipped`, `Shipped`, `Canceled`.
+    pub order_status: String,
+    /// `AFN` (fulfilled by Amazon) or `MFN` (fulfilled by the seller).
+    pub fulfillment_channel: Option<String>,
+    pub sales_channel: Option<String>,
+    pub ship_service_level: Option<String>,
+    pub order_total: Option<Money>,
+    pub number_of_items_shipped: Option<u32>,
+    pub number_of_items_unshipped: Option<u32>,
+    pub payment_method: Option<String>,
+    pub marketplace_id: Option<String>,
+    /// e.g. `Expedited`, `FreeEconomy`, `NextDay`, `SameDay`, `SecondDay`, `Scheduled`, `Standard`.
+    pub shipment_service_level_category: Option<String>,
+    pub easy_ship_shipment_status: Option<String>,
+    pub order_type: Option<String>,
+    pub earliest_ship_date: Option<String>,
+    pub latest_ship_date: Option<String>,
+    pub earliest_delivery_date: Option<String>,
+    /// The latest date the order is promised to be delivered by.
+    pub latest_delivery_date: Option<String>,
+    pub is_business_order: Option<bool>,
+    pub is_prime: Option<bool>,
+    pub is_premium_order: Option<bool>,
+    pub is_replacement_order: Option<bool>,
+    pub shipping_address: Option<Address>,
+    pub default_ship_from_location_address: Option<Address>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OrdersList {
+    pub orders: Vec<Order>,
+    pub next_token: Option<String>,
+    pub last_updated_before: Option<String>,
+    pub created_before: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OrderItem {
+    #[serde(rename = "ASIN")]
+    pub asin: String,
+    #[serde(rename = "SellerSKU")]
+    pub seller_sku: Option<String>,
+    pub order_item_id: String,
+    pub title: Option<String>,
+    pub quantity_ordered: u32,
+    pub quantity_shipped: Option<u32>,
+    pub item_price: Option<Money>,
+    pub shipping_price: Option<Money>,
+    pub item_tax: Option<Money>,
+    pub is_gift: Option<String>,
+    pub condition_id: Option<String>,
+    pub is_transparency: Option<bool>,
+    pub serial_number_required: Option<bool>,
+}
+impl OrderItem {
+    /// The units that still have to be shipped.
+    pub fn quantity_unshipped(&self) -> u32 {
+        self.quantity_ordered.saturating_sub(self.quantity_shipped.unwrap_or(0))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OrderItemsList {
+    pub order_items: Vec<OrderItem>,
+    pub next_token: Option<String>,
+    pub amazon_order_id: String,
+}
+
+/// The filters of `get_orders`. Either `created_after` or `last_updated_after` is required.
+#[derive(Debug, Clone, Default)]
+pub struct GetOrdersQuery {
+    pub marketplaces: Vec<CountryMarketplace>,
+    pub created_after: Option<String>,
+    pub created_before: Option<String>,
+    pub last_updated_after: Option<String>,
+    pub last_updated_before: Option<String>,
+    /// e.g. `Unshipped`, `PartiallyShipped`.
+    pub order_statuses: Vec<String>,
+    /// `AFN` or `MFN`.
+    pub fulfillment_channels: Vec<String>,
+    pub max_results_per_page: Option<u32>,
+}
+impl GetOrdersQuery {
+    /// The seller-fulfilled orders created after `created_after` that still have items to ship.
+    pub fn unshipped_mfn(marketplace: CountryMarketplace, created_after: &str) -> Self {
+        GetOrdersQuery {
+            marketplaces: vec![marketplace],
+            created_after: Some(created_after.to_string()),
+            order_statuses: vec!["Unshipped".to_string(), "PartiallyShipped".to_string()],
+            fulfillment_channels: vec!["MFN".to_string()],
+            max_results_per_page: Some(100),
+            ..Default::default()
+        }
+    }
+
+    fn params(&self, next_token: Option<String>) -> Vec<(String, String)> {
+        let mut params = vec![(
+            "MarketplaceIds".to_string(),
+            self.marketplaces.iter().map(|m| m.details().0).collect::<Vec<&str>>().join(","),
+        )];
+        let mut push = |name: &str, value: &Option<String>| {
+            if let Some(value) = value {
+                params.push((name.to_string(), value.clone()));
+            }
+        };
+        push("CreatedAfter", &self.created_after);
+        push("CreatedBefore", &self.created_before);
+        push("LastUpdatedAfter", &self.last_updated_after);
+        push("LastUpdatedBefore", &self.last_updated_before);
+        push("NextToken", &next_token);
+        if !self.order_statuses.is_empty() {
+            params.push(("OrderStatuses".to_string(), self.order_statuses.join(",")));
+        }
+        if !self.fulfillment_channels.is_empty() {
+            params.push(("FulfillmentChannels".to_string(), self.fulfillment_channels.join(",")));
+        }
+        if let Some(max) = self.max_results_per_page {
+            params.push(("MaxResultsPerPage".to_string(), max.to_string()));
+        }
+        params
+    }
+}
+
+/// The paged request behind `Orders::orders`.
+#[derive(Debug, Clone)]
+pub struct OrdersRequest {
+    pub query: GetOrdersQuery,
+}
+impl PagedRequest for OrdersRequest {
+    type Item = Order;
+
+    async fn fetch_page(&mut self, client: &mut Client, next_token: Option<String>) -> Result<Page<Order>, Errors> {
+        let list = Orders::get_orders(client, &self.query, next_token).await?;
+        Ok(Page { items: list.orders, next_token: list.next_token })
+    }
+}
+
+/// An item of a shipment confirmation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmShipmentOrderItem {
+    pub order_item_id: String,
+    pub quantity: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transparency_codes: Vec<String>,
+}
+
+/// The package of a shipment confirmation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageDetail {
+    /// The seller's identifier of the package, unique within the order.
+    pub package_reference_id: String,
+    /// The carrier code, e.g. `USPS`, `UPS` or `Other`.
+    pub carrier_code: String,
+    /// The carrier name, required when `carrier_code` is `Other`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carrier_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shipping_method: Option<String>,
+    pub tracking_number: String,
+    /// When the package was shipped, in ISO 8601 format.
+    pub ship_date: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ship_from_supply_source_id: Option<String>,
+    pub order_items: Vec<ConfirmShipmentOrderItem>,
+}
+
+impl Orders {
+    /// Returns orders that are created or updated during the specified time period.
+    ///
+    /// Rate (requests per second): 0.0167
+    ///
+    /// Burst: 20
+    ///
+    /// # Parameters
+    /// - `query`: The marketplaces, dates, statuses and channels to filter by.
+    /// - `next_token` (optional): The token from the previous page.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns the orders and the token for the next page.
+    pub async fn get_orders(client: &mut Client, query: &GetOrdersQuery, next_token: Option<String>) -> Result<OrdersList, Errors> {
+        if query.created_after.is_none() && query.last_updated_after.is_none() && next_token.is_none() {
+            return Err(Errors::CustomError("Either CreatedAfter or LastUpdatedAfter is required".to_string()));
+        }
+        let endpoint = format!("{}/orders", BASE);
+
+        parse_payload(client.make_request(&endpoint, Method::GET, Some(query.params(next_token))).await?).await
+    }
+
+    /// Pages through `get_orders`, following the `NextToken` automatically.
+    ///
+    /// **DEV NOTE:** getOrders allows one request per minute after its burst, so pages after
+    /// the first are spaced by a minute.
+    pub fn orders(query: GetOrdersQuery) -> Paginator<OrdersRequest> {
+        Paginator::new(OrdersRequest { query }, ORDERS_INTERVAL)
+    }
+
+    /// Returns detailed order item information for the order that you specify.
+    ///
+    /// Rate (requests per second): 0.5
+    ///
+    /// Burst: 30
+    pub async fn get_order_items(client: &mut Client, order_id: &str, next_token: Option<String>) -> Result<OrderItemsList, Errors> {
+        let endpoint = format!("{}/orders/{}/orderItems", BASE, order_id);
+        let params: Vec<(String, String)> = next_token.map(|t| ("NextToken".to_string(), t)).into_iter().collect();
+
+        parse_payload(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Fetches every item of an order, following the `NextToken`.
+    pub async fn get_all_order_items(client: &mut Client, order_id: &str) -> Result<Vec<OrderItem>, Errors> {
+        let mut items = Vec::new();
+        let mut token = None;
+        loop {
+            let list = Self::get_order_items(client, order_id, token).await?;
+            items.extend(list.order_items);
+            token = list.next_token.filter(|t| !t.is_empty());
+            if token.is_none() {
+                return Ok(items);
+            }
+        }
+    }
+
+    /// Updates the shipment confirmation status for a specified order.
+    ///
+    /// Rate (requests per second): 2
+    ///
+    /// Burst: 10
+    ///
+    /// # Parameters
+    /// - `order_id`: The Amazon order ID.
+    /// - `marketplace`: The marketplace of the order.
+    /// - `package_detail`: The carrier, tracking number and items of the package.
+    ///
+    /// # Responses
+    /// - **204 (No Content):** The shipment was confirmed.
+    pub async fn confirm_shipment(
+        client: &mut Client,
+        order_id: &str,
+        marketplace: CountryMarketplace,
+        package_detail: &PackageDetail,
+    ) -> Result<(), Errors> {
+        let endpoint = format!("{}/orders/{}/shipmentConfirmation", BASE, order_id);
+        let body = json!({ "packageDetail": package_detail, "marketplaceId": marketplace.details().0 }).to_string();
+
+        check_response(client.make_request_w_body(&endpoint, Method::POST, None::<Vec<(String, String)>>, body).await?).await
+    }
+}
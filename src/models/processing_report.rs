@@ -0,0 +1,398 @@
+//! Typed feed processing reports.
+//!
+//! JSON feeds (e.g. `JSON_LISTINGS_FEED`) produce a JSON processing report, while the legacy XML
+//! and flat-file feeds produce an XML `ProcessingReport` envelope or a tab-delimited summary.
+//! All three are parsed into the same `ProcessingReport`.
+use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize};
+use crate::error_handling::Errors;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+    Info,
+}
+impl IssueSeverity {
+    /// Parses `ERROR`, `Error`, `Fatal`, `WARNING`, `Warning`, `INFO`...
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "ERROR" | "FATAL" => Some(IssueSeverity::Error),
+            "WARNING" => Some(IssueSeverity::Warning),
+            "INFO" => Some(IssueSeverity::Info),
+            _ => None,
+        }
+    }
+}
+impl<'de> Deserialize<'de> for IssueSeverity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        IssueSeverity::parse(&value).ok_or_else(|| serde::de::Error::custom(format!("unknown issue severity {}", value)))
+    }
+}
+
+/// An issue reported for one message of a feed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingIssue {
+    pub message_id: u32,
+    /// Reported directly by XML and flat-file reports. For JSON reports it is only known after
+    /// `ProcessingReport::with_message_skus`.
+    #[serde(default)]
+    pub sku: Option<String>,
+    pub code: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+    #[serde(default)]
+    pub attribute_names: Vec<String>,
+}
+
+/// Message counts of a processed feed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingSummary {
+    #[serde(default)]
+    pub messages_processed: u32,
+    #[serde(default)]
+    pub messages_accepted: u32,
+    #[serde(default)]
+    pub messages_invalid: u32,
+    #[serde(default)]
+    pub errors: u32,
+    #[serde(default)]
+    pub warnings: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessingReportHeader {
+    pub seller_id: Option<String>,
+    pub version: Option<String>,
+    pub feed_id: Option<String>,
+}
+
+/// A parsed feed processing report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessingReport {
+    pub header: Option<ProcessingReportHeader>,
+    #[serde(default)]
+    pub summary: ProcessingSummary,
+    #[serde(default)]
+    pub issues: Vec<ProcessingIssue>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct XmlEnvelope {
+    header: Option<XmlHeader>,
+    #[serde(default)]
+    message: Vec<XmlMessage>,
+}
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct XmlHeader {
+    merchant_identifier: Option<String>,
+    document_version: Option<String>,
+}
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct XmlMessage {
+    processing_report: Option<XmlProcessingReport>,
+}
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct XmlProcessingReport {
+    #[serde(rename = "DocumentTransactionID")]
+    document_transaction_id: Option<String>,
+    processing_summary: Option<XmlProcessingSummary>,
+    #[serde(default)]
+    result: Vec<XmlResult>,
+}
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct XmlProcessingSummary {
+    messages_processed: u32,
+    messages_successful: u32,
+    messages_with_error: u32,
+    messages_with_warning: u32,
+}
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct XmlResult {
+    #[serde(rename = "MessageID")]
+    message_id: u32,
+    result_code: String,
+    result_message_code: Option<String>,
+    result_description: Option<String>,
+    additional_info: Option<XmlAdditionalInfo>,
+}
+#[derive(Deserialize)]
+struct XmlAdditionalInfo {
+    #[serde(rename = "SKU")]
+    sku: Option<String>,
+}
+
+impl ProcessingReport {
+    /// Parses a processing report, detecting whether it is JSON, XML or a flat file.
+    pub fn parse(content: &[u8]) -> Result<Self, Errors> {
+        let text = String::from_utf8_lossy(content);
+        let text = text.trim_start_matches('\u{feff}').trim_start();
+        if text.starts_with('{') {
+            Self::from_json(text.as_bytes())
+        } else if text.starts_with('<') {
+            Self::from_xml(text)
+        } else {
+            Self::from_flat_file(text)
+        }
+    }
+
+    /// Parses the JSON processing report of a JSON feed.
+    pub fn from_json(content: &[u8]) -> Result<Self, Errors> {
+        Ok(serde_json::from_slice(content)?)
+    }
+
+    /// Parses the `ProcessingReport` envelope of a legacy XML feed.
+    pub fn from_xml(content: &str) -> Result<Self, Errors> {
+        let envelope: XmlEnvelope = quick_xml::de::from_str(content)?;
+        let mut report = ProcessingReport {
+            header: envelope.header.map(|h| ProcessingReportHeader {
+                seller_id: h.merchant_identifier,
+                version: h.document_version,
+                feed_id: None,
+            }),
+            summary: ProcessingSummary::default(),
+            issues: Vec::new(),
+        };
+
+        for processing in envelope.message.into_iter().filter_map(|m| m.processing_report) {
+            if let (Some(header), Some(id)) = (report.header.as_mut(), processing.document_transaction_id) {
+                header.feed_id = Some(id);
+            }
+            if let Some(summary) = processing.processing_summary {
+                report.summary.messages_processed += summary.messages_processed;
+                report.summary.messages_accepted += summary.messages_successful;
+                report.summary.messages_invalid += summary.messages_with_error;
+                report.summary.warnings += summary.messages_with_warning;
+            }
+            for result in processing.result {
+                let Some(severity) = IssueSeverity::parse(&result.result_code) else { continue };
+                report.issues.push(ProcessingIssue {
+                    message_id: result.message_id,
+                    sku: result.additional_info.and_then(|i| i.sku),
+                    code: result.result_message_code.unwrap_or_default(),
+                    severity,
+                    message: result.result_description.unwrap_or_default(),
+                    attribute_names: Vec::new(),
+                });
+            }
+        }
+        report.summary.errors = report.issues.iter().filter(|i| i.severity == IssueSeverity::Error).count() as u32;
+        Ok(report)
+    }
+
+    /// Parses the tab-delimited processing summary of a legacy flat-file feed.
+    pub fn from_flat_file(content: &str) -> Result<Self, Errors> {
+        let mut summary = ProcessingSummary::default();
+        let mut issues = Vec::new();
+        let mut columns: Option<Vec<String>> = None;
+
+        for line in content.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(columns) = &columns {
+                let values: Vec<&str> = line.split('\t').collect();
+                let column = |name: &str| columns.iter().position(|c| c == name).and_then(|i| values.get(i)).map(|v| v.trim().to_string());
+                let Some(message_id) = column("original-record-number").and_then(|v| v.parse().ok()) else { continue };
+                let Some(severity) = column("error-type").and_then(|v| IssueSeverity::parse(&v)) else { continue };
+                issues.push(ProcessingIssue {
+                    message_id,
+                    sku: column("sku").filter(|s| !s.is_empty()),
+                    code: column("error-code").unwrap_or_default(),
+                    severity,
+                    message: column("error-message").unwrap_or_default(),
+                    attribute_names: column("attribute-name").filter(|s| !s.is_empty()).into_iter().collect(),
+                });
+            } else if line.starts_with("original-record-number") {
+                columns = Some(line.split('\t').map(|c| c.trim().to_string()).collect());
+            } else {
+                let number = line.split('\t').filter_map(|v| v.trim().parse::<u32>().ok()).next_back();
+                match (line.contains("records processed"), line.contains("records successful"), number) {
+                    (true, _, Some(n)) => summary.messages_processed = n,
+                    (_, true, Some(n)) => summary.messages_accepted = n,
+                    _ => {}
+                }
+            }
+        }
+        if summary.messages_processed == 0 && summary.messages_accepted == 0 && columns.is_none() {
+            return Err(Errors::CustomError("Not a flat-file processing summary".to_string()));
+        }
+
+        summary.messages_invalid = summary.messages_processed.saturating_sub(summary.messages_accepted);
+        summary.errors = issues.iter().filter(|i| i.severity == IssueSeverity::Error).count() as u32;
+        summary.warnings = issues.iter().filter(|i| i.severity == IssueSeverity::Warning).count() as u32;
+        Ok(ProcessingReport { header: None, summary, issues })
+    }
+
+    /// Fills in the SKU of issues that do not report one, using the message ID to SKU mapping of
+    /// the submitted feed (see `ListingsFeed::message_skus`).
+    pub fn with_message_skus(mut self, message_skus: &HashMap<u32, String>) -> Self {
+        for issue in self.issues.iter_mut().filter(|i| i.sku.is_none()) {
+            issue.sku = message_skus.get(&issue.message_id).cloned();
+        }
+        self
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|i| i.severity == IssueSeverity::Error)
+    }
+    pub fn issues_for_message(&self, message_id: u32) -> Vec<&ProcessingIssue> {
+        self.issues.iter().filter(|i| i.message_id == message_id).collect()
+    }
+    pub fn issues_for_sku(&self, sku: &str) -> Vec<&ProcessingIssue> {
+        self.issues.iter().filter(|i| i.sku.as_deref() == Some(sku)).collect()
+    }
+    pub fn issues_by_message(&self) -> HashMap<u32, Vec<&ProcessingIssue>> {
+        let mut map: HashMap<u32, Vec<&ProcessingIssue>> = HashMap::new();
+        for issue in &self.issues {
+            map.entry(issue.message_id).or_default().push(issue);
+        }
+        map
+    }
+    /// Issues without a known SKU are left out.
+    pub fn issues_by_sku(&self) -> HashMap<&str, Vec<&ProcessingIssue>> {
+        let mut map: HashMap<&str, Vec<&ProcessingIssue>> = HashMap::new();
+        for issue in &self.issues {
+            if let Some(sku) = issue.sku.as_deref() {
+                map.entry(sku).or_default().push(issue);
+            }
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{
+        "header": {"sellerId": "A1", "version": "2.0", "feedId": "123"},
+        "summary": {"messagesProcessed": 2, "messagesAccepted": 1, "messagesInvalid": 1, "errors": 1, "warnings": 1},
+        "issues": [
+            {"messageId": 1, "code": "90220", "severity": "ERROR", "message": "missing title", "attributeNames": ["item_name"]},
+            {"messageId": 2, "code": "99022", "severity": "WARNING", "message": "ignored value"}
+        ]
+    }"#;
+
+    const XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<AmazonEnvelope>
+  <Header>
+    <DocumentVersion>1.02</DocumentVersion>
+    <MerchantIdentifier>A1</MerchantIdentifier>
+  </Header>
+  <MessageType>ProcessingReport</MessageType>
+  <Message>
+    <MessageID>1</MessageID>
+    <ProcessingReport>
+      <DocumentTransactionID>456</DocumentTransactionID>
+      <StatusCode>Complete</StatusCode>
+      <ProcessingSummary>
+        <MessagesProcessed>3</MessagesProcessed>
+        <MessagesSuccessful>2</MessagesSuccessful>
+        <MessagesWithError>1</MessagesWithError>
+        <MessagesWithWarning>1</MessagesWithWarning>
+      </ProcessingSummary>
+      <Result>
+        <MessageID>2</MessageID>
+        <ResultCode>Error</ResultCode>
+        <ResultMessageCode>8560</ResultMessageCode>
+        <ResultDescription>missing brand</ResultDescription>
+        <AdditionalInfo><SKU>SKU-2</SKU></AdditionalInfo>
+      </Result>
+      <Result>
+        <MessageID>3</MessageID>
+        <ResultCode>Warning</ResultCode>
+        <ResultMessageCode>5000</ResultMessageCode>
+        <ResultDescription>value truncated</ResultDescription>
+      </Result>
+    </ProcessingReport>
+  </Message>
+</AmazonEnvelope>"#;
+
+    const FLAT_FILE: &str = "Feed Processing Summary:\n\
+        \tNumber of records processed\t\t3\n\
+        \tNumber of records successful\t\t1\n\
+        \n\
+        original-record-number\tsku\terror-code\terror-type\terror-message\n\
+        1\tSKU-1\t8541\tError\tconflicting ASIN\n\
+        2\tSKU-2\t8542\tWarning\tprice changed\n\
+        3\t\t8543\tError\tmissing image\n";
+
+    #[test]
+    fn parses_json_reports() {
+        let report = ProcessingReport::parse(JSON.as_bytes()).unwrap();
+        assert_eq!(report.header.as_ref().and_then(|h| h.feed_id.as_deref()), Some("123"));
+        assert_eq!(report.summary.messages_processed, 2);
+        assert_eq!(report.issues.len(), 2);
+        assert_eq!(report.issues[0].severity, IssueSeverity::Error);
+        assert_eq!(report.issues[0].attribute_names, vec!["item_name".to_string()]);
+        assert!(report.issues[0].sku.is_none());
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn parses_xml_reports() {
+        let report = ProcessingReport::parse(XML.as_bytes()).unwrap();
+        let header = report.header.as_ref().unwrap();
+        assert_eq!(header.seller_id.as_deref(), Some("A1"));
+        assert_eq!(header.feed_id.as_deref(), Some("456"));
+        assert_eq!(report.summary, ProcessingSummary {
+            messages_processed: 3,
+            messages_accepted: 2,
+            messages_invalid: 1,
+            errors: 1,
+            warnings: 1,
+        });
+        assert_eq!(report.issues_for_sku("SKU-2").len(), 1);
+        assert_eq!(report.issues_for_message(3)[0].severity, IssueSeverity::Warning);
+        assert_eq!(report.issues_for_message(3)[0].code, "5000");
+    }
+
+    #[test]
+    fn parses_flat_file_reports() {
+        let report = ProcessingReport::parse(FLAT_FILE.as_bytes()).unwrap();
+        assert!(report.header.is_none());
+        assert_eq!(report.summary, ProcessingSummary {
+            messages_processed: 3,
+            messages_accepted: 1,
+            messages_invalid: 2,
+            errors: 2,
+            warnings: 1,
+        });
+        assert_eq!(report.issues.len(), 3);
+        assert_eq!(report.issues[0].sku.as_deref(), Some("SKU-1"));
+        assert!(report.issues[2].sku.is_none());
+        assert_eq!(report.issues[2].message, "missing image");
+    }
+
+    #[test]
+    fn rejects_text_that_is_not_a_report() {
+        assert!(ProcessingReport::parse(b"hello world").is_err());
+    }
+
+    #[test]
+    fn message_skus_fill_in_missing_skus_only() {
+        let mut skus = HashMap::new();
+        skus.insert(1, "SKU-1".to_string());
+        skus.insert(3, "OTHER".to_string());
+        let report = ProcessingReport::parse(FLAT_FILE.as_bytes()).unwrap().with_message_skus(&skus);
+        assert_eq!(report.issues[2].sku.as_deref(), Some("OTHER"));
+
+        let report = ProcessingReport::from_json(JSON.as_bytes()).unwrap().with_message_skus(&skus);
+        let by_sku = report.issues_by_sku();
+        assert_eq!(by_sku.len(), 1);
+        assert_eq!(by_sku["SKU-1"][0].message_id, 1);
+        assert_eq!(report.issues_by_message().len(), 2);
+    }
+}
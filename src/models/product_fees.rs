@@ -0,0 +1,287 @@
+use std::time::Duration;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::error_handling::Errors;
+use crate::general::{encode_path_segment, parse_payload, parse_response, Client, CountryMarketplace};
+
+pub struct ProductFees;
+
+/// getMyFeesEstimates accepts at most this many estimates per request.
+pub const MAX_ESTIMATES_PER_REQUEST: usize = 20;
+/// getMyFeesEstimates allows 0.5 requests per second.
+const BATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MoneyType {
+    pub currency_code: Option<String>,
+    pub amount: Option<f64>,
+}
+impl MoneyType {
+    pub fn new(currency_code: &str, amount: f64) -> Self {
+        MoneyType { currency_code: Some(currency_code.to_string()), amount: Some(amount) }
+    }
+}
+
+/// Amazon points granted with the purchase (Japan only).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Points {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points_number: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points_monetary_value: Option<MoneyType>,
+}
+
+/// The price the fees are estimated at.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PriceToEstimateFees {
+    pub listing_price: MoneyType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shipping: Option<MoneyType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points: Option<Points>,
+}
+impl PriceToEstimateFees {
+    pub fn new(listing_price: MoneyType) -> Self {
+        PriceToEstimateFees { listing_price, shipping: None, points: None }
+    }
+    pub fn shipping(mut self, shipping: MoneyType) -> Self {
+        self.shipping = Some(shipping);
+        self
+    }
+    pub fn points(mut self, points: Points) -> Self {
+        self.points = Some(points);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IdType {
+    ASIN,
+    SellerSKU,
+}
+
+/// The fulfillment program used when `is_amazon_fulfilled` is true.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OptionalFulfillmentProgram {
+    FBA_CORE,
+    FBA_SNL,
+    FBA_EFN,
+}
+
+/// The request body of getMyFeesEstimateForSKU and getMyFeesEstimateForASIN.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct FeesEstimateRequest {
+    pub marketplace_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_amazon_fulfilled: Option<bool>,
+    pub price_to_estimate_fees: PriceToEstimateFees,
+    /// A unique value returned in the response, used to match estimates to requests.
+    pub identifier: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optional_fulfillment_program: Option<OptionalFulfillmentProgram>,
+}
+
+/// One estimate of a getMyFeesEstimates batch.
+#[derive(Debug, Clone)]
+pub struct FeesEstimateByIdRequest {
+    pub id_type: IdType,
+    /// The ASIN or seller SKU, depending on `id_type`.
+    pub id_value: String,
+    pub marketplace: CountryMarketplace,
+    pub is_amazon_fulfilled: Option<bool>,
+    pub price_to_estimate_fees: PriceToEstimateFees,
+    /// Defaults to `id_value` when `None`.
+    pub identifier: Option<String>,
+    pub optional_fulfillment_program: Option<OptionalFulfillmentProgram>,
+}
+impl FeesEstimateByIdRequest {
+    fn to_body(&self) -> Value {
+        serde_json::json!({
+            "FeesEstimateRequest": FeesEstimateRequest {
+                marketplace_id: self.marketplace.details().0.to_string(),
+                is_amazon_fulfilled: self.is_amazon_fulfilled,
+                price_to_estimate_fees: self.price_to_estimate_fees.clone(),
+                identifier: self.identifier.clone().unwrap_or_else(|| self.id_value.clone()),
+                optional_fulfillment_program: self.optional_fulfillment_program,
+            },
+            "IdType": self.id_type,
+            "IdValue": self.id_value,
+        })
+    }
+}
+
+/// A fee that is part of another fee, e.g. a per-item fee included in the FBA fee.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct IncludedFeeDetail {
+    pub fee_type: Option<String>,
+    pub fee_amount: Option<MoneyType>,
+    pub fee_promotion: Option<MoneyType>,
+    pub tax_amount: Option<MoneyType>,
+    pub final_fee: Option<MoneyType>,
+}
+
+/// One fee of an estimate, e.g. `ReferralFee` or `FBAFees`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FeeDetail {
+    pub fee_type: Option<String>,
+    pub fee_amount: Option<MoneyType>,
+    pub fee_promotion: Option<MoneyType>,
+    pub tax_amount: Option<MoneyType>,
+    pub final_fee: Option<MoneyType>,
+    pub included_fee_detail_list: Vec<IncludedFeeDetail>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FeesEstimate {
+    pub time_of_fees_estimation: Option<String>,
+    pub total_fees_estimate: Option<MoneyType>,
+    pub fee_detail_list: Vec<FeeDetail>,
+}
+impl FeesEstimate {
+    /// The final amount of the fee of type `fee_type`, e.g. `ReferralFee`.
+    pub fn fee(&self, fee_type: &str) -> Option<f64> {
+        self.fee_detail_list
+            .iter()
+            .find(|f| f.fee_type.as_deref() == Some(fee_type))
+            .and_then(|f| f.final_fee.as_ref().or(f.fee_amount.as_ref()))
+            .and_then(|m| m.amount)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FeesEstimateIdentifier {
+    pub marketplace_id: Option<String>,
+    pub seller_id: Option<String>,
+    pub id_type: Option<IdType>,
+    pub id_value: Option<String>,
+    pub is_amazon_fulfilled: Option<bool>,
+    pub price_to_estimate_fees: Option<PriceToEstimateFees>,
+    pub seller_input_identifier: Option<String>,
+    pub optional_fulfillment_program: Option<OptionalFulfillmentProgram>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FeesEstimateError {
+    #[serde(rename = "Type")]
+    pub error_type: Option<String>,
+    pub code: Option<String>,
+    pub message: Option<String>,
+    pub detail: Vec<Value>,
+}
+
+/// The result of one fees estimate. `status` is `Success`, `ClientError` or `ServiceError`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct FeesEstimateResult {
+    pub status: Option<String>,
+    pub fees_estimate_identifier: Option<FeesEstimateIdentifier>,
+    pub fees_estimate: Option<FeesEstimate>,
+    pub error: Option<FeesEstimateError>,
+}
+impl FeesEstimateResult {
+    pub fn is_success(&self) -> bool {
+        self.status.as_deref() == Some("Success")
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GetMyFeesEstimateResult {
+    fees_estimate_result: FeesEstimateResult,
+}
+
+impl ProductFees {
+    async fn get_my_fees_estimate(client: &mut Client, endpoint: &str, request: FeesEstimateRequest) -> Result<FeesEstimateResult, Errors> {
+        let body = serde_json::json!({ "FeesEstimateRequest": request });
+        let result: GetMyFeesEstimateResult = parse_payload(
+            client
+                .make_request_w_body(endpoint, Method::POST, None::<Vec<(String, String)>>, body.to_string())
+                .await?,
+        )
+        .await?;
+        Ok(result.fees_estimate_result)
+    }
+
+    /// Returns the estimated fees for the item indicated by the specified seller SKU in the
+    /// marketplace specified in the request body.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 2
+    ///
+    /// # Parameters
+    /// - `seller_sku`: Used to identify an item in the given marketplace.
+    /// - `request`: The fees estimate request, including the price to estimate fees at.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `FeesEstimateResult`.
+    pub async fn get_my_fees_estimate_for_sku(client: &mut Client, seller_sku: &str, request: FeesEstimateRequest) -> Result<FeesEstimateResult, Errors> {
+        let endpoint = format!("/products/fees/v0/listings/{}/feesEstimate", encode_path_segment(seller_sku));
+
+        Self::get_my_fees_estimate(client, &endpoint, request).await
+    }
+
+    /// Returns the estimated fees for the item indicated by the specified ASIN in the marketplace
+    /// specified in the request body.
+    ///
+    /// Rate (requests per second): 1
+    ///
+    /// Burst: 2
+    ///
+    /// # Parameters
+    /// - `asin`: The Amazon Standard Identification Number (ASIN) of the item.
+    /// - `request`: The fees estimate request, including the price to estimate fees at.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `FeesEstimateResult`.
+    pub async fn get_my_fees_estimate_for_asin(client: &mut Client, asin: &str, request: FeesEstimateRequest) -> Result<FeesEstimateResult, Errors> {
+        let endpoint = format!("/products/fees/v0/items/{}/feesEstimate", asin);
+
+        Self::get_my_fees_estimate(client, &endpoint, request).await
+    }
+
+    /// Returns the estimated fees for a list of products.
+    ///
+    /// **DEV NOTE:** Amazon accepts at most 20 estimates per request. Longer lists are split into
+    /// chunks of 20 that are sent one after another within the rate limit, and the results are
+    /// returned in the order of `requests`.
+    ///
+    /// Rate (requests per second): 0.5
+    ///
+    /// Burst: 1
+    ///
+    /// # Parameters
+    /// - `requests`: The estimates to make.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns one `FeesEstimateResult` per request. Check `status` for
+    ///   estimates that failed individually.
+    pub async fn get_my_fees_estimates(client: &mut Client, requests: Vec<FeesEstimateByIdRequest>) -> Result<Vec<FeesEstimateResult>, Errors> {
+        let mut results = Vec::with_capacity(requests.len());
+        for (i, chunk) in requests.chunks(MAX_ESTIMATES_PER_REQUEST).enumerate() {
+            if i > 0 {
+                tokio::time::sleep(BATCH_INTERVAL).await;
+            }
+            let body = Value::Array(chunk.iter().map(|r| r.to_body()).collect());
+            let chunk_results: Vec<FeesEstimateResult> = parse_response(
+                client
+                    .make_request_w_body("/products/fees/v0/feesEstimate", Method::POST, None::<Vec<(String, String)>>, body.to_string())
+                    .await?,
+            )
+            .await?;
+            results.extend(chunk_results);
+        }
+        Ok(results)
+    }
+}
@@ -1,121 +1,577 @@
-use std::collections::HashMap;
-use reqwest::{Method, Response};
+use std::time::Duration;
+use reqwest::Method;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::Value;
+use crate::batch::{self, BatchEndpoint, BatchRequest, BatchResponse, BatchResults, SubRequest};
 use crate::error_handling::Errors;
-use crate::general::{Client, CountryMarketplace};
-pub enum CompetitiveSummaryIncludedData {
-    FeaturedBuyingOptions,
-    ReferencePrices,
-    LowestPricedOffers
-}
-impl CompetitiveSummaryIncludedData {
-    fn to_string(&self) -> String {
-        match self {
-            CompetitiveSummaryIncludedData::FeaturedBuyingOptions => "featuredBuyingOptions".to_string(),
-            CompetitiveSummaryIncludedData::ReferencePrices => "referencePrices".to_string(),
-            CompetitiveSummaryIncludedData::LowestPricedOffers => "lowestPricedOffers".to_string()
+use crate::general::{encode_path_segment, parse_payload, Client, CountryMarketplace};
+use crate::models::product_fees::MoneyType;
+pub use crate::models::product_fees::Points;
+pub use crate::models::product_pricing_competitive::CompetitiveSummaryIncludedData;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+pub enum ItemCondition {
+    New,
+    Used,
+    Collectible,
+    Refurbished,
+    Club,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+pub enum CustomerType {
+    Consumer,
+    Business,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+pub enum OfferType {
+    B2C,
+    B2B,
+}
+
+/// Whether `get_pricing` and `get_competitive_pricing` are given ASINs or seller SKUs.
+#[derive(Debug, Clone)]
+pub enum PricingItems {
+    Asins(Vec<String>),
+    Skus(Vec<String>),
+}
+impl PricingItems {
+    fn params(&self) -> Result<Vec<(String, String)>, Errors> {
+        let (item_type, key, items) = match self {
+            PricingItems::Asins(asins) => ("Asin", "Asins", asins),
+            PricingItems::Skus(skus) => ("Sku", "Skus", skus),
+        };
+        if items.is_empty() || items.len() > 20 {
+            return Err(Errors::CustomError("Between 1 and 20 ASINs or SKUs are allowed".to_string()));
+        }
+        Ok(vec![("ItemType".to_string(), item_type.to_string()), (key.to_string(), items.join(","))])
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct PriceType {
+    pub landed_price: Option<MoneyType>,
+    pub listing_price: Option<MoneyType>,
+    pub shipping: Option<MoneyType>,
+    pub points: Option<Points>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuantityDiscountPriceType {
+    #[serde(rename = "quantityTier")]
+    pub quantity_tier: Option<i64>,
+    #[serde(rename = "quantityDiscountType")]
+    pub quantity_discount_type: Option<String>,
+    #[serde(rename = "listingPrice")]
+    pub listing_price: Option<MoneyType>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct MarketplaceAsin {
+    pub marketplace_id: Option<String>,
+    #[serde(rename = "ASIN")]
+    pub asin: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct SkuIdentifier {
+    pub marketplace_id: Option<String>,
+    pub seller_id: Option<String>,
+    #[serde(rename = "SellerSKU")]
+    pub seller_sku: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdentifierType {
+    #[serde(rename = "MarketplaceASIN")]
+    pub marketplace_asin: Option<MarketplaceAsin>,
+    #[serde(rename = "SKUIdentifier")]
+    pub sku_identifier: Option<SkuIdentifier>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct CompetitivePriceType {
+    pub competitive_price_id: Option<String>,
+    pub price: Option<PriceType>,
+    #[serde(rename = "condition")]
+    pub condition: Option<String>,
+    #[serde(rename = "subcondition")]
+    pub subcondition: Option<String>,
+    #[serde(rename = "offerType")]
+    pub offer_type: Option<OfferType>,
+    #[serde(rename = "quantityTier")]
+    pub quantity_tier: Option<i64>,
+    #[serde(rename = "quantityDiscountType")]
+    pub quantity_discount_type: Option<String>,
+    #[serde(rename = "sellerId")]
+    pub seller_id: Option<String>,
+    #[serde(rename = "belongsToRequester")]
+    pub belongs_to_requester: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OfferListingCountType {
+    pub count: Option<i64>,
+    #[serde(rename = "condition")]
+    pub condition: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct CompetitivePricingType {
+    pub competitive_prices: Vec<CompetitivePriceType>,
+    pub number_of_offer_listings: Vec<OfferListingCountType>,
+    pub trade_in_value: Option<MoneyType>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct SalesRankType {
+    pub product_category_id: Option<String>,
+    pub rank: Option<i64>,
+}
+
+/// One of the seller's own offers, as returned by `get_pricing`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct PricingOffer {
+    #[serde(rename = "offerType")]
+    pub offer_type: Option<OfferType>,
+    pub buying_price: Option<PriceType>,
+    pub regular_price: Option<MoneyType>,
+    #[serde(rename = "businessPrice")]
+    pub business_price: Option<MoneyType>,
+    #[serde(rename = "quantityDiscountPrices")]
+    pub quantity_discount_prices: Vec<QuantityDiscountPriceType>,
+    pub fulfillment_channel: Option<String>,
+    pub item_condition: Option<String>,
+    pub item_sub_condition: Option<String>,
+    #[serde(rename = "SellerSKU")]
+    pub seller_sku: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct PricingProduct {
+    pub identifiers: Option<IdentifierType>,
+    pub attribute_sets: Vec<Value>,
+    pub relationships: Vec<Value>,
+    pub competitive_pricing: Option<CompetitivePricingType>,
+    pub sales_rankings: Vec<SalesRankType>,
+    pub offers: Vec<PricingOffer>,
+}
+
+/// The pricing of one ASIN or SKU, as returned by `get_pricing` and `get_competitive_pricing`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Price {
+    /// `Success` when the item was found.
+    pub status: Option<String>,
+    #[serde(rename = "SellerSKU")]
+    pub seller_sku: Option<String>,
+    #[serde(rename = "ASIN")]
+    pub asin: Option<String>,
+    #[serde(rename = "Product")]
+    pub product: Option<PricingProduct>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OfferCountType {
+    pub condition: Option<String>,
+    #[serde(rename = "fulfillmentChannel")]
+    pub fulfillment_channel: Option<String>,
+    #[serde(rename = "OfferCount")]
+    pub offer_count: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct LowestPriceType {
+    #[serde(rename = "condition")]
+    pub condition: Option<String>,
+    #[serde(rename = "fulfillmentChannel")]
+    pub fulfillment_channel: Option<String>,
+    #[serde(rename = "offerType")]
+    pub offer_type: Option<OfferType>,
+    #[serde(rename = "quantityTier")]
+    pub quantity_tier: Option<i64>,
+    #[serde(rename = "quantityDiscountType")]
+    pub quantity_discount_type: Option<String>,
+    pub landed_price: Option<MoneyType>,
+    pub listing_price: Option<MoneyType>,
+    pub shipping: Option<MoneyType>,
+    pub points: Option<Points>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct BuyBoxPriceType {
+    #[serde(rename = "condition")]
+    pub condition: Option<String>,
+    #[serde(rename = "offerType")]
+    pub offer_type: Option<OfferType>,
+    #[serde(rename = "quantityTier")]
+    pub quantity_tier: Option<i64>,
+    #[serde(rename = "quantityDiscountType")]
+    pub quantity_discount_type: Option<String>,
+    pub landed_price: Option<MoneyType>,
+    pub listing_price: Option<MoneyType>,
+    pub shipping: Option<MoneyType>,
+    pub points: Option<Points>,
+    #[serde(rename = "sellerId")]
+    pub seller_id: Option<String>,
+}
+
+/// Offer counts, lowest prices and buy box prices of an item.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OffersSummary {
+    pub total_offer_count: Option<i64>,
+    pub number_of_offers: Vec<OfferCountType>,
+    pub lowest_prices: Vec<LowestPriceType>,
+    pub buy_box_prices: Vec<BuyBoxPriceType>,
+    pub list_price: Option<MoneyType>,
+    pub competitive_price_threshold: Option<MoneyType>,
+    pub suggested_lower_price_plus_shipping: Option<MoneyType>,
+    pub sales_rankings: Vec<SalesRankType>,
+    pub buy_box_eligible_offers: Vec<OfferCountType>,
+    pub offers_available_time: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct SellerFeedbackType {
+    pub seller_positive_feedback_rating: Option<f64>,
+    pub feedback_count: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DetailedShippingTimeType {
+    pub minimum_hours: Option<i64>,
+    pub maximum_hours: Option<i64>,
+    pub available_date: Option<String>,
+    pub availability_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ShipsFromType {
+    pub state: Option<String>,
+    pub country: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct PrimeInformationType {
+    pub is_prime: Option<bool>,
+    pub is_national_prime: Option<bool>,
+}
+
+/// One offer on an item, as returned by `get_item_offers` and `get_listing_offers`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct OfferDetail {
+    pub my_offer: Option<bool>,
+    #[serde(rename = "offerType")]
+    pub offer_type: Option<OfferType>,
+    pub sub_condition: Option<String>,
+    pub seller_id: Option<String>,
+    pub condition_notes: Option<String>,
+    pub seller_feedback_rating: Option<SellerFeedbackType>,
+    pub shipping_time: Option<DetailedShippingTimeType>,
+    pub listing_price: Option<MoneyType>,
+    #[serde(rename = "quantityDiscountPrices")]
+    pub quantity_discount_prices: Vec<QuantityDiscountPriceType>,
+    pub points: Option<Points>,
+    pub shipping: Option<MoneyType>,
+    pub ships_from: Option<ShipsFromType>,
+    pub is_fulfilled_by_amazon: Option<bool>,
+    pub prime_information: Option<PrimeInformationType>,
+    pub is_buy_box_winner: Option<bool>,
+    pub is_featured_merchant: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct ItemIdentifier {
+    pub marketplace_id: Option<String>,
+    #[serde(rename = "ASIN")]
+    pub asin: Option<String>,
+    #[serde(rename = "SellerSKU")]
+    pub seller_sku: Option<String>,
+    pub item_condition: Option<ItemCondition>,
+}
+
+/// The lowest priced offers of a single item.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct GetOffersResult {
+    #[serde(rename = "MarketplaceID")]
+    pub marketplace_id: Option<String>,
+    #[serde(rename = "ASIN")]
+    pub asin: Option<String>,
+    #[serde(rename = "SKU")]
+    pub sku: Option<String>,
+    pub item_condition: Option<ItemCondition>,
+    #[serde(rename = "status")]
+    pub status: Option<String>,
+    pub identifier: Option<ItemIdentifier>,
+    pub summary: Option<OffersSummary>,
+    pub offers: Vec<OfferDetail>,
+}
+impl GetOffersResult {
+    /// The offer that currently holds the buy box, if any.
+    pub fn buy_box_winner(&self) -> Option<&OfferDetail> {
+        self.offers.iter().find(|o| o.is_buy_box_winner == Some(true))
+    }
+}
+
+/// An error entry of an SP-API response.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiErrorEntry {
+    pub code: Option<String>,
+    pub message: Option<String>,
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GetOffersResponse {
+    pub payload: Option<GetOffersResult>,
+    pub errors: Vec<ApiErrorEntry>,
+}
+
+/// One request of `get_item_offers_batch` (by ASIN) or `get_listing_offers_batch` (by SKU).
+#[derive(Debug, Clone)]
+pub struct OffersBatchRequest {
+    /// The ASIN for item offers, or the seller SKU for listing offers.
+    pub id: String,
+    pub marketplace: CountryMarketplace,
+    pub item_condition: ItemCondition,
+    pub customer_type: Option<CustomerType>,
+}
+impl OffersBatchRequest {
+    fn to_batch_request(&self, uri_prefix: &str) -> BatchRequest {
+        let mut request = BatchRequest::new(Method::GET, &format!("{}/{}/offers", uri_prefix, encode_path_segment(&self.id)))
+            .param("MarketplaceId", self.marketplace.details().0)
+            .param("ItemCondition", self.item_condition);
+        if let Some(customer_type) = self.customer_type {
+            request = request.param("CustomerType", customer_type);
         }
+        request
     }
 }
-#[allow(non_snake_case)]
-#[derive(Debug, Deserialize, Serialize, Clone)]
-struct CompetitiveSummaryData {
-    method: String,
-    uri: String,
-    marketplaceId: String,
-    asin: String,
-    includedData: Vec<String>,
+
+/// `OffersBatchRequest` as a sub-request of getItemOffersBatch.
+struct ItemOffersRequest(OffersBatchRequest);
+impl SubRequest for ItemOffersRequest {
+    type Response = GetOffersResponse;
+
+    fn to_batch_request(&self) -> BatchRequest {
+        self.0.to_batch_request("/products/pricing/v0/items")
+    }
+    fn matches(&self, response: &BatchResponse) -> Option<bool> {
+        response.request.get("Asin").and_then(Value::as_str).map(|asin| asin == self.0.id)
+    }
+}
+
+/// `OffersBatchRequest` as a sub-request of getListingOffersBatch.
+struct ListingOffersRequest(OffersBatchRequest);
+impl SubRequest for ListingOffersRequest {
+    type Response = GetOffersResponse;
+
+    fn to_batch_request(&self) -> BatchRequest {
+        self.0.to_batch_request("/products/pricing/v0/listings")
+    }
+    fn matches(&self, response: &BatchResponse) -> Option<bool> {
+        response.request.get("SellerSKU").and_then(Value::as_str).map(|sku| sku == self.0.id)
+    }
 }
+
+const ITEM_OFFERS_BATCH: BatchEndpoint = BatchEndpoint {
+    url: "/batches/products/pricing/v0/itemOffers",
+    max_requests: 20,
+    interval: Duration::from_secs(10),
+};
+const LISTING_OFFERS_BATCH: BatchEndpoint = BatchEndpoint {
+    url: "/batches/products/pricing/v0/listingOffers",
+    max_requests: 20,
+    interval: Duration::from_secs(2),
+};
+
+fn offers_params(marketplace: CountryMarketplace, item_condition: ItemCondition, customer_type: Option<CustomerType>) -> Vec<(String, String)> {
+    let mut params = vec![
+        ("MarketplaceId".to_string(), marketplace.details().0.to_string()),
+        ("ItemCondition".to_string(), item_condition.to_string()),
+    ];
+    if let Some(customer_type) = customer_type {
+        params.push(("CustomerType".to_string(), customer_type.to_string()));
+    }
+    params
+}
+
 pub struct ProductPricing;
 impl ProductPricing {
-    /// **DEV NOTE:** This will internally use the Vec of sku to make it as a batch
-    /// the set of responses for a batch of Featured Offer Expected Price (FOEP) requests.
+    /// Returns pricing information for a seller's offer listings based on seller SKU or ASIN.
     ///
-    /// Rate (requests per second): 0.033
+    /// Rate (requests per second): 0.5
     /// Burst: 1
     ///
     /// # Parameters
-    /// - `client`: Reference to the HTTP client.
-    /// - `requests`: A vector of individual FOEP request parameters.
-    /// - `uri`: The URI associated with the requests. Default: `/products/pricing/2022-05-01/offer/featuredOfferExpectedPrice`.
+    /// - `marketplace`: The marketplace for which prices are returned.
+    /// - `items`: Up to 20 ASINs or seller SKUs.
+    /// - `item_condition` (optional): Filters the offer listings based on item condition.
+    /// - `offer_type` (optional): Indicates whether to request pricing information for B2C or B2B offers. Default: B2C.
     ///
     /// # Responses
-    /// - **200 (Success):** Returns a `GetFeaturedOfferExpectedPriceBatchResponse` object.
-    pub async fn get_featured_offer_expected_price_batch(
+    /// - **200 (Success):** Returns one `Price` per item.
+    pub async fn get_pricing(
         client: &mut Client,
-        //requests: Vec<(String, String)>, // Tuple (marketplace_id, sku)
-        uri: Option<String>, // Optional custom URI
-        method: String,
-        body: Option<String>,
-        headers: Option<HashMap<String, String>>,
-        market_place: crate::general::CountryMarketplace,
-        sku: Vec<String>,
-    ) -> Result<Response, Errors> {
-        const URL: &str = "/batches/products/pricing/2022-05-01/offer/featuredOfferExpectedPrice";
-        const DEFAULT_URI: &str = "/products/pricing/2022-05-01/offer/featuredOfferExpectedPrice";
-
-        let uri = uri.unwrap_or(DEFAULT_URI.to_string());
-        let mut params: HashMap<String, String> = HashMap::from([("uri".to_string(),uri.clone()), ("method".to_string(), method), ("marketplaceId".to_string(), market_place.details().0.to_string())]);
-        if let Some(o) = body {
-            params.insert("body".to_string(), o);
+        marketplace: CountryMarketplace,
+        items: PricingItems,
+        item_condition: Option<ItemCondition>,
+        offer_type: Option<OfferType>,
+    ) -> Result<Vec<Price>, Errors> {
+        let mut params = vec![("MarketplaceId".to_string(), marketplace.details().0.to_string())];
+        params.extend(items.params()?);
+        if let Some(condition) = item_condition {
+            params.push(("ItemCondition".to_string(), condition.to_string()));
+        }
+        if let Some(offer_type) = offer_type {
+            params.push(("OfferType".to_string(), offer_type.to_string()));
         }
-        if let Some(h) = headers {
-            params.insert("headers".to_string(), h.iter().map(|s| serde_json::to_string(&s).unwrap()).collect());
+
+        parse_payload(client.make_request("/products/pricing/v0/price", Method::GET, Some(params)).await?).await
+    }
+
+    /// Returns competitive pricing information for a seller's offer listings based on seller SKU or ASIN.
+    ///
+    /// Rate (requests per second): 0.5
+    /// Burst: 1
+    ///
+    /// # Parameters
+    /// - `marketplace`: The marketplace for which prices are returned.
+    /// - `items`: Up to 20 ASINs or seller SKUs.
+    /// - `customer_type` (optional): Indicates whether to request pricing information from the point of view of Consumer or Business buyers. Default: Consumer.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns one `Price` per item.
+    pub async fn get_competitive_pricing(
+        client: &mut Client,
+        marketplace: CountryMarketplace,
+        items: PricingItems,
+        customer_type: Option<CustomerType>,
+    ) -> Result<Vec<Price>, Errors> {
+        let mut params = vec![("MarketplaceId".to_string(), marketplace.details().0.to_string())];
+        params.extend(items.params()?);
+        if let Some(customer_type) = customer_type {
+            params.push(("CustomerType".to_string(), customer_type.to_string()));
         }
-        let final_params: Vec<HashMap<String,String>> = sku.iter().map(|small_sku| {
-            let mut ff = params.clone();
-            ff.insert(String::from("sku"), small_sku.clone());
-            ff
-        }).collect();
-
-        client
-            .make_request_w_body(URL, Method::POST, None::<Vec<(String, String)>>, serde_json::to_string(&HashMap::from([("requests".to_string(),final_params)]))?)
-            .await
+
+        parse_payload(client.make_request("/products/pricing/v0/competitivePrice", Method::GET, Some(params)).await?).await
     }
 
-    /// Returns the competitive summary for a batch of ASIN and marketplaceId combinations.
-    /// With number of items between 1 and 20.
+    /// Returns the lowest priced offers for a single SKU listing.
+    ///
+    /// Rate (requests per second): 1
+    /// Burst: 2
     ///
-    /// Rate (requests per second): 0.033
+    /// # Parameters
+    /// - `seller_sku`: Identifies an item in the given marketplace.
+    /// - `marketplace`: The marketplace for which prices are returned.
+    /// - `item_condition`: Filters the offer listings based upon item condition.
+    /// - `customer_type` (optional): Consumer or Business. Default: Consumer.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns a `GetOffersResult`.
+    pub async fn get_listing_offers(
+        client: &mut Client,
+        seller_sku: &str,
+        marketplace: CountryMarketplace,
+        item_condition: ItemCondition,
+        customer_type: Option<CustomerType>,
+    ) -> Result<GetOffersResult, Errors> {
+        let endpoint = format!("/products/pricing/v0/listings/{}/offers", encode_path_segment(seller_sku));
+        let params = offers_params(marketplace, item_condition, customer_type);
+
+        parse_payload(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
+    }
+
+    /// Returns the lowest priced offers for a single item based on ASIN.
+    ///
+    /// Rate (requests per second): 0.5
     /// Burst: 1
     ///
     /// # Parameters
-    /// - `client`: Reference to the HTTP client.
-    /// - `requests`: A vector of individual request parameters.
-    /// - `uri`: The URI associated with the requests. Default: `/products/pricing/2022-05-01/items/competitiveSummary`.
+    /// - `asin`: The Amazon Standard Identification Number (ASIN) of the item.
+    /// - `marketplace`: The marketplace for which prices are returned.
+    /// - `item_condition`: Filters the offer listings based upon item condition.
+    /// - `customer_type` (optional): Consumer or Business. Default: Consumer.
     ///
     /// # Responses
-    /// - **200 (Success):** Returns a `CompetitiveSummaryBatchResponse` object.
-    pub async fn get_competitive_summary(
+    /// - **200 (Success):** Returns a `GetOffersResult`.
+    pub async fn get_item_offers(
         client: &mut Client,
-        asin: Vec<String>,
-        market_place: CountryMarketplace,
-        included_data: Vec<CompetitiveSummaryIncludedData>,
-        method: String,
-        uri: Option<String>, // Optional custom URI
-    ) -> Result<Response, Errors> {
-        const URL: &str = "/batches/products/pricing/2022-05-01/items/competitiveSummary";
-        const DEFAULT_URI: &str = "/products/pricing/2022-05-01/items/competitiveSummary";
-
-        let uri = uri.unwrap_or(DEFAULT_URI.to_string());
-        let data = &included_data.iter().map(|s| s.to_string()).collect::<Vec<String>>();
-
-        let final_result = asin.iter().map(|a| {
-            CompetitiveSummaryData {
-                method: method.clone(),
-                uri: uri.clone(),
-                marketplaceId: market_place.details().0.to_string(),
-                asin: a.clone(),
-                includedData: data.clone(),
-            }
-        }).collect::<Vec<CompetitiveSummaryData>>();
-        let to_send = json!({"requests": final_result});
-        client
-            .make_request_w_body(URL, Method::POST, None::<Vec<(String, String)>>, serde_json::to_string(&to_send)?)
-            .await
+        asin: &str,
+        marketplace: CountryMarketplace,
+        item_condition: ItemCondition,
+        customer_type: Option<CustomerType>,
+    ) -> Result<GetOffersResult, Errors> {
+        let endpoint = format!("/products/pricing/v0/items/{}/offers", asin);
+        let params = offers_params(marketplace, item_condition, customer_type);
 
+        parse_payload(client.make_request(&endpoint, Method::GET, Some(params)).await?).await
     }
 
+    /// Returns the lowest priced offers for a batch of items based on ASIN.
+    ///
+    /// Rate (requests per second): 0.1
+    /// Burst: 1
+    ///
+    /// # Parameters
+    /// - `requests`: Any number of requests, with `id` set to the ASIN. They are sent in batches
+    ///   of 20 within the rate limit.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns every request with its own response, in the order of `requests`,
+    ///   and the responses that match no request.
+    pub async fn get_item_offers_batch(
+        client: &mut Client,
+        requests: Vec<OffersBatchRequest>,
+    ) -> Result<BatchResults<OffersBatchRequest, GetOffersResponse>, Errors> {
+        let requests = requests.into_iter().map(ItemOffersRequest).collect();
+        let results = batch::send(client, &ITEM_OFFERS_BATCH, requests).await?;
+        Ok(results.map_request(|r| r.0))
+    }
 
-}
\ No newline at end of file
+    /// Returns the lowest priced offers for a batch of listings by SKU.
+    ///
+    /// Rate (requests per second): 0.5
+    /// Burst: 1
+    ///
+    /// # Parameters
+    /// - `requests`: Any number of requests, with `id` set to the seller SKU. They are sent in
+    ///   batches of 20 within the rate limit.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns every request with its own response, in the order of `requests`,
+    ///   and the responses that match no request.
+    pub async fn get_listing_offers_batch(
+        client: &mut Client,
+        requests: Vec<OffersBatchRequest>,
+    ) -> Result<BatchResults<OffersBatchRequest, GetOffersResponse>, Errors> {
+        let requests = requests.into_iter().map(ListingOffersRequest).collect();
+        let results = batch::send(client, &LISTING_OFFERS_BATCH, requests).await?;
+        Ok(results.map_request(|r| r.0))
+    }
+}
@@ -0,0 +1,331 @@
+//! The 2022-05-01 version of the Product Pricing API: featured offer expected prices and
+//! competitive summaries, both of which are only available as batch operations.
+use std::collections::HashSet;
+use std::fmt;
+use std::time::Duration;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::batch::{self, BatchEndpoint, BatchRequest, BatchResponse, BatchResults, SubRequest};
+use crate::error_handling::Errors;
+use crate::general::{Client, CountryMarketplace};
+use crate::models::product_pricing::{ApiErrorEntry, ProductPricing};
+
+/// Both batch operations accept at most this many requests per call.
+pub const MAX_REQUESTS_PER_BATCH: usize = 20;
+/// Both batch operations allow 0.033 requests per second.
+const BATCH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompetitiveSummaryIncludedData {
+    FeaturedBuyingOptions,
+    ReferencePrices,
+    LowestPricedOffers
+}
+impl fmt::Display for CompetitiveSummaryIncludedData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let data = match self {
+            CompetitiveSummaryIncludedData::FeaturedBuyingOptions => "featuredBuyingOptions",
+            CompetitiveSummaryIncludedData::ReferencePrices => "referencePrices",
+            CompetitiveSummaryIncludedData::LowestPricedOffers => "lowestPricedOffers"
+        };
+        f.write_str(data)
+    }
+}
+
+/// A currency code and amount.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MoneyType {
+    pub currency_code: Option<String>,
+    pub amount: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Points {
+    pub points_number: Option<i64>,
+    pub points_monetary_value: Option<MoneyType>,
+}
+
+/// Identifies an offer of a seller.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OfferIdentifier {
+    pub marketplace_id: Option<String>,
+    pub seller_id: Option<String>,
+    pub sku: Option<String>,
+    pub asin: Option<String>,
+    /// `AFN` (fulfilled by Amazon) or `MFN` (fulfilled by the merchant).
+    pub fulfillment_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OfferPrice {
+    pub listing_price: Option<MoneyType>,
+    pub shipping_price: Option<MoneyType>,
+    pub points: Option<Points>,
+}
+
+/// An offer that holds, or would hold, the featured offer.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FeaturedOffer {
+    pub offer_identifier: Option<OfferIdentifier>,
+    /// e.g. `New`, `Used`, `Collectible`.
+    pub condition: Option<String>,
+    pub price: Option<OfferPrice>,
+}
+
+/// The price at or below which the seller's offer is expected to become the featured offer.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FeaturedOfferExpectedPrice {
+    pub listing_price: Option<MoneyType>,
+    pub points: Option<Points>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FeaturedOfferExpectedPriceResult {
+    pub featured_offer_expected_price: Option<FeaturedOfferExpectedPrice>,
+    /// `VALID_FOEP`, `NO_COMPETING_OFFER`, `OFFER_NOT_ELIGIBLE`, `OFFER_NOT_FOUND` or `ASIN_NOT_ELIGIBLE`.
+    pub result_status: Option<String>,
+    pub competing_featured_offer: Option<FeaturedOffer>,
+    pub current_featured_offer: Option<FeaturedOffer>,
+}
+
+/// The featured offer expected price of one SKU.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FeaturedOfferExpectedPriceResponseBody {
+    pub offer_identifier: Option<OfferIdentifier>,
+    pub featured_offer_expected_price_results: Vec<FeaturedOfferExpectedPriceResult>,
+    pub errors: Vec<ApiErrorEntry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShippingOption {
+    /// e.g. `DEFAULT`.
+    pub shipping_option_type: Option<String>,
+    pub price: Option<MoneyType>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrimeDetails {
+    /// `NATIONAL`, `REGIONAL` or `NONE`.
+    pub eligibility: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SegmentDetails {
+    pub glance_view_weight_percentage: Option<f64>,
+}
+
+/// The customers for which an offer is featured.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FeaturedOfferSegment {
+    /// `PRIME` or `NON_PRIME`.
+    pub customer_membership: Option<String>,
+    pub segment_details: Option<SegmentDetails>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SegmentedFeaturedOffer {
+    pub seller_id: Option<String>,
+    pub condition: Option<String>,
+    pub sub_condition: Option<String>,
+    pub fulfillment_type: Option<String>,
+    pub listing_price: Option<MoneyType>,
+    pub shipping_options: Vec<ShippingOption>,
+    pub points: Option<Points>,
+    pub prime_details: Option<PrimeDetails>,
+    pub featured_offer_segments: Vec<FeaturedOfferSegment>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FeaturedBuyingOption {
+    /// e.g. `New`.
+    pub buying_option_type: Option<String>,
+    pub segmented_featured_offers: Vec<SegmentedFeaturedOffer>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LowestPricedOffersInput {
+    pub item_condition: Option<String>,
+    /// e.g. `Consumer`.
+    pub offer_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Offer {
+    pub seller_id: Option<String>,
+    pub condition: Option<String>,
+    pub sub_condition: Option<String>,
+    pub fulfillment_type: Option<String>,
+    pub listing_price: Option<MoneyType>,
+    pub shipping_options: Vec<ShippingOption>,
+    pub points: Option<Points>,
+    pub prime_details: Option<PrimeDetails>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LowestPricedOffer {
+    pub lowest_priced_offers_input: Option<LowestPricedOffersInput>,
+    pub offers: Vec<Offer>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReferencePrice {
+    /// e.g. `CompetitivePriceThreshold` or `WasPrice`.
+    pub name: Option<String>,
+    pub price: Option<MoneyType>,
+}
+
+/// The competitive summary of one ASIN.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CompetitiveSummaryResponseBody {
+    pub asin: Option<String>,
+    pub marketplace_id: Option<String>,
+    pub featured_buying_options: Vec<FeaturedBuyingOption>,
+    pub lowest_priced_offers: Vec<LowestPricedOffer>,
+    pub reference_prices: Vec<ReferencePrice>,
+    pub errors: Vec<ApiErrorEntry>,
+}
+
+/// One request of `get_featured_offer_expected_price_batch`.
+#[derive(Debug, Clone)]
+pub struct FeaturedOfferExpectedPriceRequest {
+    pub marketplace: CountryMarketplace,
+    pub sku: String,
+}
+impl SubRequest for FeaturedOfferExpectedPriceRequest {
+    type Response = FeaturedOfferExpectedPriceResponseBody;
+
+    fn to_batch_request(&self) -> BatchRequest {
+        BatchRequest::new(Method::GET, "/products/pricing/2022-05-01/offer/featuredOfferExpectedPrice")
+            .param("marketplaceId", self.marketplace.details().0)
+            .param("sku", &self.sku)
+    }
+    fn matches(&self, response: &BatchResponse) -> Option<bool> {
+        response
+            .request
+            .get("sku")
+            .or_else(|| response.body.pointer("/offerIdentifier/sku"))
+            .and_then(Value::as_str)
+            .map(|sku| sku == self.sku)
+    }
+}
+
+/// One request of `get_competitive_summary`.
+#[derive(Debug, Clone)]
+pub struct CompetitiveSummaryRequest {
+    pub marketplace: CountryMarketplace,
+    pub asin: String,
+    pub included_data: Vec<CompetitiveSummaryIncludedData>,
+}
+impl SubRequest for CompetitiveSummaryRequest {
+    type Response = CompetitiveSummaryResponseBody;
+
+    fn to_batch_request(&self) -> BatchRequest {
+        BatchRequest::new(Method::GET, "/products/pricing/2022-05-01/items/competitiveSummary")
+            .param("marketplaceId", self.marketplace.details().0)
+            .param("asin", &self.asin)
+            .param("includedData", self.included_data.iter().map(|d| d.to_string()).collect::<Vec<String>>())
+    }
+    fn matches(&self, response: &BatchResponse) -> Option<bool> {
+        response.body.get("asin").and_then(Value::as_str).map(|asin| asin == self.asin)
+    }
+}
+
+const FEATURED_OFFER_EXPECTED_PRICE_BATCH: BatchEndpoint = BatchEndpoint {
+    url: "/batches/products/pricing/2022-05-01/offer/featuredOfferExpectedPrice",
+    max_requests: MAX_REQUESTS_PER_BATCH,
+    interval: BATCH_INTERVAL,
+};
+const COMPETITIVE_SUMMARY_BATCH: BatchEndpoint = BatchEndpoint {
+    url: "/batches/products/pricing/2022-05-01/items/competitiveSummary",
+    max_requests: MAX_REQUESTS_PER_BATCH,
+    interval: BATCH_INTERVAL,
+};
+
+/// Drops repeated ids, keeping the first occurrence.
+fn unique(ids: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    ids.into_iter().filter(|id| seen.insert(id.clone())).collect()
+}
+
+impl ProductPricing {
+    /// Returns the featured offer expected price (FOEP) of each of the seller's SKUs: the price
+    /// at or below which their offer is expected to become the featured offer.
+    ///
+    /// **DEV NOTE:** Amazon accepts at most 20 requests per batch. Longer lists are split into
+    /// chunks of 20 that are sent 30 seconds apart to stay within the rate limit.
+    ///
+    /// Rate (requests per second): 0.033
+    /// Burst: 1
+    ///
+    /// # Parameters
+    /// - `marketplace`: The marketplace of the SKUs.
+    /// - `skus`: The seller SKUs, any number.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns one `Result` per SKU, with the SKU as its request, and the
+    ///   responses that match no SKU. Inner responses that failed are returned as
+    ///   `Errors::ApiError` with their own status.
+    pub async fn get_featured_offer_expected_price_batch(
+        client: &mut Client,
+        marketplace: CountryMarketplace,
+        skus: Vec<String>,
+    ) -> Result<BatchResults<String, FeaturedOfferExpectedPriceResponseBody>, Errors> {
+        let requests = unique(skus).into_iter().map(|sku| FeaturedOfferExpectedPriceRequest { marketplace, sku }).collect();
+        let results = batch::send(client, &FEATURED_OFFER_EXPECTED_PRICE_BATCH, requests).await?;
+
+        Ok(results.map_request(|r| r.sku))
+    }
+
+    /// Returns the competitive summary of each ASIN: featured buying options, reference prices
+    /// and lowest priced offers, depending on `included_data`.
+    ///
+    /// **DEV NOTE:** Amazon accepts at most 20 requests per batch. Longer lists are split into
+    /// chunks of 20 that are sent 30 seconds apart to stay within the rate limit.
+    ///
+    /// Rate (requests per second): 0.033
+    /// Burst: 1
+    ///
+    /// # Parameters
+    /// - `asins`: The ASINs, any number.
+    /// - `marketplace`: The marketplace of the ASINs.
+    /// - `included_data`: The data to include in each summary.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns one `Result` per ASIN, with the ASIN as its request, and the
+    ///   responses that match no ASIN. Inner responses that failed are returned as
+    ///   `Errors::ApiError` with their own status.
+    pub async fn get_competitive_summary(
+        client: &mut Client,
+        asins: Vec<String>,
+        marketplace: CountryMarketplace,
+        included_data: Vec<CompetitiveSummaryIncludedData>,
+    ) -> Result<BatchResults<String, CompetitiveSummaryResponseBody>, Errors> {
+        let requests = unique(asins)
+            .into_iter()
+            .map(|asin| CompetitiveSummaryRequest { marketplace, asin, included_data: included_data.clone() })
+            .collect();
+        let results = batch::send(client, &COMPETITIVE_SUMMARY_BATCH, requests).await?;
+
+        Ok(results.map_request(|r| r.asin))
+    }
+}
@@ -0,0 +1,570 @@
+//! Per-order and per-SKU net proceeds, folded from v0 financial events or 2024-06-19 transactions.
+//!
+//! Amounts are kept per currency and are never converted. Fees and refunds are negative, as
+//! Amazon reports them, so `net_proceeds` is the plain sum of all categories.
+use std::collections::BTreeMap;
+use std::io::Write;
+use serde::Serialize;
+use crate::error_handling::Errors;
+use crate::models::finances::{ChargeComponent, Currency, FeeComponent, FinancialEvents, Promotion, ShipmentEvent, ShipmentItem, TaxWithheldComponent};
+use crate::models::finances_transactions::{Breakdown, Transaction};
+
+/// FBA reimbursement reasons and adjustment types that don't say "reimbursement", e.g.
+/// `MISSING_FROM_INBOUND`, `WAREHOUSE_LOST`, `REVERSAL_REIMBURSEMENT` or `Damaged_Inbound`.
+/// Matched as prefixes, lowercase and without separators.
+const REIMBURSEMENT_TYPES: &[&str] = &[
+    "warehouse",
+    "reversal",
+    "missingfrominbound",
+    "inboundcarrierdamage",
+    "lostinbound",
+    "damagedinbound",
+    "lostwarehouse",
+    "damagedwarehouse",
+    "lostoutbound",
+    "damagedoutbound",
+    "removalorderlost",
+    "removalorderdamaged",
+    "customerreturn",
+    "customerserviceissue",
+    "freereplacementrefunditems",
+    "compensatedclawback",
+    "reevaluation",
+    "incorrectfees",
+    "feecorrection",
+];
+
+/// The bucket an amount is booked into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LedgerCategory {
+    Principal,
+    Tax,
+    Shipping,
+    ReferralFee,
+    FbaFee,
+    OtherFees,
+    Promotions,
+    Refunds,
+    Reimbursements,
+    Other,
+}
+impl LedgerCategory {
+    /// Classifies a charge, fee, breakdown or adjustment type such as `Principal`, `ShippingTax`,
+    /// `Commission`, `FBAPerUnitFulfillmentFee` or `WAREHOUSE_DAMAGE`.
+    pub fn classify(kind: &str) -> Self {
+        let lower = kind.to_ascii_lowercase();
+        let normalized: String = lower.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+        if normalized.contains("reimburse") || REIMBURSEMENT_TYPES.iter().any(|t| normalized.starts_with(t)) {
+            LedgerCategory::Reimbursements
+        } else if lower.contains("tax") {
+            LedgerCategory::Tax
+        } else if lower.contains("principal") || lower == "productcharges" {
+            LedgerCategory::Principal
+        } else if lower.contains("commission") || lower.contains("referralfee") {
+            LedgerCategory::ReferralFee
+        } else if lower.starts_with("fba") || lower.contains("fulfillmentfee") {
+            LedgerCategory::FbaFee
+        } else if lower.contains("promotion") || lower.contains("discount") {
+            LedgerCategory::Promotions
+        } else if lower.contains("shipping") || lower.contains("giftwrap") {
+            LedgerCategory::Shipping
+        } else if lower.contains("fee") {
+            LedgerCategory::OtherFees
+        } else {
+            LedgerCategory::Other
+        }
+    }
+}
+
+/// The amounts of one order or SKU in one currency.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LedgerAmounts {
+    pub principal: f64,
+    pub tax: f64,
+    pub shipping: f64,
+    pub referral_fee: f64,
+    pub fba_fee: f64,
+    pub other_fees: f64,
+    pub promotions: f64,
+    pub refunds: f64,
+    pub reimbursements: f64,
+    pub other: f64,
+}
+impl LedgerAmounts {
+    pub fn add(&mut self, category: LedgerCategory, amount: f64) {
+        let bucket = match category {
+            LedgerCategory::Principal => &mut self.principal,
+            LedgerCategory::Tax => &mut self.tax,
+            LedgerCategory::Shipping => &mut self.shipping,
+            LedgerCategory::ReferralFee => &mut self.referral_fee,
+            LedgerCategory::FbaFee => &mut self.fba_fee,
+            LedgerCategory::OtherFees => &mut self.other_fees,
+            LedgerCategory::Promotions => &mut self.promotions,
+            LedgerCategory::Refunds => &mut self.refunds,
+            LedgerCategory::Reimbursements => &mut self.reimbursements,
+            LedgerCategory::Other => &mut self.other,
+        };
+        *bucket += amount;
+    }
+    pub fn net_proceeds(&self) -> f64 {
+        self.principal
+            + self.tax
+            + self.shipping
+            + self.referral_fee
+            + self.fba_fee
+            + self.other_fees
+            + self.promotions
+            + self.refunds
+            + self.reimbursements
+            + self.other
+    }
+}
+
+/// One row of a ledger: an order ID or SKU, a currency and its amounts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LedgerEntry<'a> {
+    pub key: &'a str,
+    pub currency: &'a str,
+    pub amounts: &'a LedgerAmounts,
+}
+
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    key: &'a str,
+    currency: &'a str,
+    principal: String,
+    tax: String,
+    shipping: String,
+    referral_fee: String,
+    fba_fee: String,
+    other_fees: String,
+    promotions: String,
+    refunds: String,
+    reimbursements: String,
+    other: String,
+    net_proceeds: String,
+}
+
+type Ledger = BTreeMap<(String, String), LedgerAmounts>;
+
+/// Per-order and per-SKU ledgers.
+///
+/// Order-level charges and fees (e.g. shipping fees charged per order) only appear in the order
+/// ledger, and reimbursements that are not tied to an order only appear in the SKU ledger.
+#[derive(Debug, Clone, Default)]
+pub struct ProfitAndLoss {
+    orders: Ledger,
+    skus: Ledger,
+}
+
+fn book(ledger: &mut Ledger, key: &str, currency_code: &str, amount: f64, category: LedgerCategory) {
+    ledger.entry((key.to_string(), currency_code.to_string())).or_default().add(category, amount);
+}
+
+impl ProfitAndLoss {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn book_amount(&mut self, order_id: Option<&str>, sku: Option<&str>, currency_code: Option<&str>, amount: Option<f64>, category: LedgerCategory) {
+        let Some(amount) = amount else { return };
+        let currency_code = currency_code.unwrap_or_default();
+        if let Some(order_id) = order_id {
+            book(&mut self.orders, order_id, currency_code, amount, category);
+        }
+        if let Some(sku) = sku {
+            book(&mut self.skus, sku, currency_code, amount, category);
+        }
+    }
+    fn book(&mut self, order_id: Option<&str>, sku: Option<&str>, amount: &Currency, category: LedgerCategory) {
+        self.book_amount(order_id, sku, amount.currency_code.as_deref(), amount.currency_amount, category);
+    }
+
+    fn book_charges(&mut self, order_id: Option<&str>, sku: Option<&str>, charges: &[ChargeComponent], refund: bool) {
+        for charge in charges {
+            let Some(amount) = &charge.charge_amount else { continue };
+            let category = LedgerCategory::classify(charge.charge_type.as_deref().unwrap_or_default());
+            // Refunded principal and shipping are the refund itself, refunded tax stays tax.
+            let category = match category {
+                LedgerCategory::Principal | LedgerCategory::Shipping | LedgerCategory::Other if refund => LedgerCategory::Refunds,
+                category => category,
+            };
+            self.book(order_id, sku, amount, category);
+        }
+    }
+    fn book_fees(&mut self, order_id: Option<&str>, sku: Option<&str>, fees: &[FeeComponent]) {
+        for fee in fees {
+            let Some(amount) = &fee.fee_amount else { continue };
+            let category = match LedgerCategory::classify(fee.fee_type.as_deref().unwrap_or_default()) {
+                LedgerCategory::ReferralFee => LedgerCategory::ReferralFee,
+                LedgerCategory::FbaFee => LedgerCategory::FbaFee,
+                _ => LedgerCategory::OtherFees,
+            };
+            self.book(order_id, sku, amount, category);
+        }
+    }
+    fn book_promotions(&mut self, order_id: Option<&str>, sku: Option<&str>, promotions: &[Promotion]) {
+        for promotion in promotions {
+            if let Some(amount) = &promotion.promotion_amount {
+                self.book(order_id, sku, amount, LedgerCategory::Promotions);
+            }
+        }
+    }
+    fn book_taxes_withheld(&mut self, order_id: Option<&str>, sku: Option<&str>, taxes: &[TaxWithheldComponent]) {
+        for component in taxes {
+            for tax in &component.taxes_withheld {
+                if let Some(amount) = &tax.charge_amount {
+                    self.book(order_id, sku, amount, LedgerCategory::Tax);
+                }
+            }
+        }
+    }
+    fn book_item(&mut self, order_id: Option<&str>, item: &ShipmentItem, refund: bool) {
+        let sku = item.seller_sku.as_deref();
+        self.book_charges(order_id, sku, &item.item_charge_list, refund);
+        self.book_charges(order_id, sku, &item.item_charge_adjustment_list, refund);
+        self.book_fees(order_id, sku, &item.item_fee_list);
+        self.book_fees(order_id, sku, &item.item_fee_adjustment_list);
+        self.book_taxes_withheld(order_id, sku, &item.item_tax_withheld_list);
+        self.book_promotions(order_id, sku, &item.promotion_list);
+        self.book_promotions(order_id, sku, &item.promotion_adjustment_list);
+        if let Some(points) = &item.cost_of_points_granted {
+            self.book(order_id, sku, points, LedgerCategory::Promotions);
+        }
+        if let Some(points) = &item.cost_of_points_returned {
+            self.book(order_id, sku, points, LedgerCategory::Promotions);
+        }
+    }
+    fn book_shipment_event(&mut self, event: &ShipmentEvent, refund: bool) {
+        let order_id = event.amazon_order_id.as_deref();
+        self.book_charges(order_id, None, &event.order_charge_list, refund);
+        self.book_charges(order_id, None, &event.order_charge_adjustment_list, refund);
+        self.book_fees(order_id, None, &event.shipment_fee_list);
+        self.book_fees(order_id, None, &event.shipment_fee_adjustment_list);
+        self.book_fees(order_id, None, &event.order_fee_list);
+        self.book_fees(order_id, None, &event.order_fee_adjustment_list);
+        for payment in &event.direct_payment_list {
+            if let Some(amount) = &payment.direct_payment_amount {
+                self.book(order_id, None, amount, LedgerCategory::Other);
+            }
+        }
+        for item in event.shipment_item_list.iter().chain(&event.shipment_item_adjustment_list) {
+            self.book_item(order_id, item, refund);
+        }
+    }
+
+    /// Folds v0 financial events into the ledgers.
+    ///
+    /// Shipments, refunds, guarantee claims, chargebacks, service fees, FBA inventory
+    /// reimbursements and other adjustments, SAFE-T reimbursements and retrocharges are booked.
+    /// Events that are tied to neither an order nor a SKU (e.g. loan servicing) are skipped.
+    pub fn add_financial_events(&mut self, events: &FinancialEvents) {
+        for event in &events.shipment_event_list {
+            self.book_shipment_event(event, false);
+        }
+        for event in events.refund_event_list.iter().chain(&events.guarantee_claim_event_list).chain(&events.chargeback_event_list) {
+            self.book_shipment_event(event, true);
+        }
+        for event in &events.service_fee_event_list {
+            self.book_fees(event.amazon_order_id.as_deref(), event.seller_sku.as_deref(), &event.fee_list);
+        }
+        for event in &events.adjustment_event_list {
+            let category = match LedgerCategory::classify(event.adjustment_type.as_deref().unwrap_or_default()) {
+                LedgerCategory::Reimbursements => LedgerCategory::Reimbursements,
+                _ => LedgerCategory::Other,
+            };
+            for item in &event.adjustment_item_list {
+                if let Some(amount) = &item.total_amount {
+                    self.book(None, item.seller_sku.as_deref(), amount, category);
+                }
+            }
+        }
+        for event in &events.retrocharge_event_list {
+            let order_id = event.amazon_order_id.as_deref();
+            for tax in event.base_tax.iter().chain(&event.shipping_tax) {
+                self.book(order_id, None, tax, LedgerCategory::Tax);
+            }
+        }
+        for event in &events.trial_shipment_event_list {
+            self.book_fees(event.amazon_order_id.as_deref(), event.sku.as_deref(), &event.fee_list);
+        }
+    }
+
+    fn book_breakdowns(&mut self, order_id: Option<&str>, sku: Option<&str>, breakdowns: &[Breakdown], refund: bool) {
+        for leaf in breakdowns.iter().flat_map(|b| b.leaves()) {
+            let Some(amount) = &leaf.breakdown_amount else { continue };
+            let category = match LedgerCategory::classify(leaf.breakdown_type.as_deref().unwrap_or_default()) {
+                LedgerCategory::Principal | LedgerCategory::Shipping | LedgerCategory::Other if refund => LedgerCategory::Refunds,
+                category => category,
+            };
+            self.book_amount(order_id, sku, amount.currency_code.as_deref(), amount.currency_amount, category);
+        }
+    }
+
+    /// Folds 2024-06-19 transactions into the ledgers.
+    ///
+    /// The order ledger uses the transaction's own breakdowns and the SKU ledger the breakdowns
+    /// of its items. Transactions without any breakdown are booked with their total amount.
+    pub fn add_transactions(&mut self, transactions: &[Transaction]) {
+        for transaction in transactions {
+            let order_id = transaction.order_id();
+            let transaction_type = transaction.transaction_type.as_deref().unwrap_or_default();
+            let refund = transaction_type.to_ascii_lowercase().contains("refund");
+            // Reimbursements are often `Adjustment` transactions, e.g. "FBA Inventory Reimbursement - Lost:Warehouse".
+            let reimbursement = [Some(transaction_type), transaction.description.as_deref()]
+                .into_iter()
+                .flatten()
+                .any(|kind| LedgerCategory::classify(kind) == LedgerCategory::Reimbursements);
+
+            if !transaction.breakdowns.is_empty() {
+                self.book_breakdowns(order_id, None, &transaction.breakdowns, refund);
+            }
+            for item in &transaction.items {
+                let sku = item.sku();
+                if transaction.breakdowns.is_empty() {
+                    // The items carry the only breakdowns, so they count for the order as well.
+                    self.book_breakdowns(order_id, sku, &item.breakdowns, refund);
+                } else {
+                    self.book_breakdowns(None, sku, &item.breakdowns, refund);
+                }
+            }
+
+            let has_breakdowns = !transaction.breakdowns.is_empty() || transaction.items.iter().any(|i| !i.breakdowns.is_empty());
+            if !has_breakdowns {
+                if let Some(total) = &transaction.total_amount {
+                    let category = if reimbursement { LedgerCategory::Reimbursements } else if refund { LedgerCategory::Refunds } else { LedgerCategory::Other };
+                    let sku = transaction.items.iter().find_map(|i| i.sku());
+                    self.book_amount(order_id, sku, total.currency_code.as_deref(), total.currency_amount, category);
+                }
+            }
+        }
+    }
+
+    fn entries(ledger: &Ledger) -> impl Iterator<Item = LedgerEntry<'_>> {
+        ledger.iter().map(|((key, currency), amounts)| LedgerEntry { key, currency, amounts })
+    }
+    /// The per-order ledger, sorted by order ID and currency.
+    pub fn orders(&self) -> impl Iterator<Item = LedgerEntry<'_>> {
+        Self::entries(&self.orders)
+    }
+    /// The per-SKU ledger, sorted by SKU and currency.
+    pub fn skus(&self) -> impl Iterator<Item = LedgerEntry<'_>> {
+        Self::entries(&self.skus)
+    }
+    pub fn order(&self, order_id: &str, currency: &str) -> Option<&LedgerAmounts> {
+        self.orders.get(&(order_id.to_string(), currency.to_string()))
+    }
+    pub fn sku(&self, sku: &str, currency: &str) -> Option<&LedgerAmounts> {
+        self.skus.get(&(sku.to_string(), currency.to_string()))
+    }
+
+    fn write_csv<W: Write>(ledger: &Ledger, key_column: &str, writer: W) -> Result<(), Errors> {
+        let mut csv = csv::WriterBuilder::new().has_headers(false).from_writer(writer);
+        csv.write_record([
+            key_column, "currency", "principal", "tax", "shipping", "referral_fee", "fba_fee", "other_fees", "promotions", "refunds",
+            "reimbursements", "other", "net_proceeds",
+        ])?;
+        let money = |amount: f64| format!("{:.2}", amount);
+        for entry in Self::entries(ledger) {
+            let a = entry.amounts;
+            csv.serialize(CsvRow {
+                key: entry.key,
+                currency: entry.currency,
+                principal: money(a.principal),
+                tax: money(a.tax),
+                shipping: money(a.shipping),
+                referral_fee: money(a.referral_fee),
+                fba_fee: money(a.fba_fee),
+                other_fees: money(a.other_fees),
+                promotions: money(a.promotions),
+                refunds: money(a.refunds),
+                reimbursements: money(a.reimbursements),
+                other: money(a.other),
+                net_proceeds: money(a.net_proceeds()),
+            })?;
+        }
+        csv.flush()?;
+        Ok(())
+    }
+    /// Writes the per-order ledger as CSV, one row per order and currency.
+    pub fn write_orders_csv<W: Write>(&self, writer: W) -> Result<(), Errors> {
+        Self::write_csv(&self.orders, "order_id", writer)
+    }
+    /// Writes the per-SKU ledger as CSV, one row per SKU and currency.
+    pub fn write_skus_csv<W: Write>(&self, writer: W) -> Result<(), Errors> {
+        Self::write_csv(&self.skus, "sku", writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn classifies_charge_fee_and_adjustment_types() {
+        let cases = [
+            ("Principal", LedgerCategory::Principal),
+            ("ProductCharges", LedgerCategory::Principal),
+            ("Tax", LedgerCategory::Tax),
+            ("ShippingTax", LedgerCategory::Tax),
+            ("ShippingCharge", LedgerCategory::Shipping),
+            ("GiftWrap", LedgerCategory::Shipping),
+            ("Commission", LedgerCategory::ReferralFee),
+            ("FBAPerUnitFulfillmentFee", LedgerCategory::FbaFee),
+            ("FBAWeightBasedFee", LedgerCategory::FbaFee),
+            ("PromotionMetaDataDefinitionValue", LedgerCategory::Promotions),
+            ("DigitalServicesFee", LedgerCategory::OtherFees),
+            ("FBAInventoryReimbursement", LedgerCategory::Reimbursements),
+            ("REVERSAL_REIMBURSEMENT", LedgerCategory::Reimbursements),
+            ("WAREHOUSE_DAMAGE", LedgerCategory::Reimbursements),
+            ("WAREHOUSE_LOST_MANUAL", LedgerCategory::Reimbursements),
+            ("MISSING_FROM_INBOUND", LedgerCategory::Reimbursements),
+            ("MISSING_FROM_INBOUND_CLAWBACK", LedgerCategory::Reimbursements),
+            ("INBOUND_CARRIER_DAMAGE", LedgerCategory::Reimbursements),
+            ("FREE_REPLACEMENT_REFUND_ITEMS", LedgerCategory::Reimbursements),
+            ("COMPENSATED_CLAWBACK", LedgerCategory::Reimbursements),
+            ("Lost_Warehouse", LedgerCategory::Reimbursements),
+            ("Damaged_Inbound", LedgerCategory::Reimbursements),
+            ("CustomerReturn", LedgerCategory::Reimbursements),
+            ("ReserveDebit", LedgerCategory::Other),
+            ("", LedgerCategory::Other),
+        ];
+        for (kind, expected) in cases {
+            assert_eq!(LedgerCategory::classify(kind), expected, "{}", kind);
+        }
+    }
+
+    fn usd(amount: f64) -> serde_json::Value {
+        json!({ "CurrencyCode": "USD", "CurrencyAmount": amount })
+    }
+
+    #[test]
+    fn folds_financial_events_per_order_and_sku() {
+        let events: FinancialEvents = serde_json::from_value(json!({
+            "ShipmentEventList": [{
+                "AmazonOrderId": "111",
+                "OrderFeeList": [{ "FeeType": "ShippingChargeback", "FeeAmount": usd(-1.0) }],
+                "ShipmentItemList": [{
+                    "SellerSKU": "SKU-1",
+                    "ItemChargeList": [
+                        { "ChargeType": "Principal", "ChargeAmount": usd(20.0) },
+                        { "ChargeType": "Tax", "ChargeAmount": usd(2.0) }
+                    ],
+                    "ItemFeeList": [
+                        { "FeeType": "Commission", "FeeAmount": usd(-3.0) },
+                        { "FeeType": "FBAPerUnitFulfillmentFee", "FeeAmount": usd(-4.0) }
+                    ],
+                    "PromotionList": [{ "PromotionAmount": usd(-1.5) }]
+                }]
+            }],
+            "RefundEventList": [{
+                "AmazonOrderId": "111",
+                "ShipmentItemAdjustmentList": [{
+                    "SellerSKU": "SKU-1",
+                    "ItemChargeAdjustmentList": [
+                        { "ChargeType": "Principal", "ChargeAmount": usd(-20.0) },
+                        { "ChargeType": "Tax", "ChargeAmount": usd(-2.0) }
+                    ],
+                    "ItemFeeAdjustmentList": [{ "FeeType": "Commission", "FeeAmount": usd(2.5) }]
+                }]
+            }],
+            "AdjustmentEventList": [
+                { "AdjustmentType": "MISSING_FROM_INBOUND", "AdjustmentItemList": [{ "SellerSKU": "SKU-1", "TotalAmount": usd(8.0) }] },
+                { "AdjustmentType": "ReserveDebit", "AdjustmentItemList": [{ "SellerSKU": "SKU-2", "TotalAmount": usd(-5.0) }] }
+            ]
+        }))
+        .unwrap();
+        let mut pnl = ProfitAndLoss::new();
+        pnl.add_financial_events(&events);
+
+        let order = pnl.order("111", "USD").unwrap();
+        assert_eq!(order.principal, 20.0);
+        assert_eq!(order.tax, 0.0);
+        assert_eq!(order.refunds, -20.0);
+        assert_eq!(order.referral_fee, -0.5);
+        assert_eq!(order.fba_fee, -4.0);
+        assert_eq!(order.other_fees, -1.0);
+        assert_eq!(order.promotions, -1.5);
+        assert_eq!(order.reimbursements, 0.0);
+
+        let sku = pnl.sku("SKU-1", "USD").unwrap();
+        assert_eq!(sku.other_fees, 0.0);
+        assert_eq!(sku.reimbursements, 8.0);
+        assert_eq!(pnl.sku("SKU-2", "USD").unwrap().other, -5.0);
+        assert_eq!(pnl.orders().count(), 1);
+        assert_eq!(pnl.skus().count(), 2);
+    }
+
+    #[test]
+    fn folds_transactions_per_order_and_sku() {
+        let transactions: Vec<Transaction> = serde_json::from_value(json!([
+            {
+                "transactionType": "Shipment",
+                "relatedIdentifiers": [{ "relatedIdentifierName": "ORDER_ID", "relatedIdentifierValue": "111" }],
+                "totalAmount": { "currencyCode": "EUR", "currencyAmount": 14.0 },
+                "items": [{
+                    "contexts": [{ "contextType": "ProductContext", "sku": "SKU-1" }],
+                    "breakdowns": [
+                        { "breakdownType": "ProductCharges", "breakdownAmount": { "currencyCode": "EUR", "currencyAmount": 20.0 } },
+                        { "breakdownType": "AmazonFees", "breakdowns": [
+                            { "breakdownType": "Commission", "breakdownAmount": { "currencyCode": "EUR", "currencyAmount": -3.0 } },
+                            { "breakdownType": "FBAPerUnitFulfillmentFee", "breakdownAmount": { "currencyCode": "EUR", "currencyAmount": -3.0 } }
+                        ] }
+                    ]
+                }]
+            },
+            {
+                "transactionType": "Refund",
+                "relatedIdentifiers": [{ "relatedIdentifierName": "ORDER_ID", "relatedIdentifierValue": "111" }],
+                "breakdowns": [{ "breakdownType": "ProductCharges", "breakdownAmount": { "currencyCode": "EUR", "currencyAmount": -20.0 } }],
+                "items": [{
+                    "contexts": [{ "contextType": "ProductContext", "sku": "SKU-1" }],
+                    "breakdowns": [{ "breakdownType": "ProductCharges", "breakdownAmount": { "currencyCode": "EUR", "currencyAmount": -20.0 } }]
+                }]
+            },
+            {
+                "transactionType": "Adjustment",
+                "description": "FBA Inventory Reimbursement - Lost:Warehouse",
+                "totalAmount": { "currencyCode": "EUR", "currencyAmount": 9.0 },
+                "items": [{ "contexts": [{ "contextType": "ProductContext", "sku": "SKU-2" }] }]
+            }
+        ]))
+        .unwrap();
+        let mut pnl = ProfitAndLoss::new();
+        pnl.add_transactions(&transactions);
+
+        // The shipment has item breakdowns only, so they count for the order as well; the refund
+        // books its own breakdowns for the order and the item's for the SKU.
+        let order = pnl.order("111", "EUR").unwrap();
+        assert_eq!((order.principal, order.referral_fee, order.fba_fee, order.refunds), (20.0, -3.0, -3.0, -20.0));
+        let sku = pnl.sku("SKU-1", "EUR").unwrap();
+        assert_eq!((sku.principal, sku.refunds), (20.0, -20.0));
+        assert_eq!(sku.net_proceeds(), -6.0);
+        assert_eq!(pnl.sku("SKU-2", "EUR").unwrap().reimbursements, 9.0);
+        assert!(pnl.order("111", "USD").is_none());
+    }
+
+    #[test]
+    fn writes_one_csv_row_per_key_and_currency() {
+        let mut pnl = ProfitAndLoss::new();
+        pnl.book_amount(Some("111"), Some("SKU-1"), Some("USD"), Some(10.0), LedgerCategory::Principal);
+        pnl.book_amount(Some("111"), Some("SKU-1"), Some("USD"), Some(-1.5), LedgerCategory::ReferralFee);
+        pnl.book_amount(Some("111"), None, Some("EUR"), Some(2.0), LedgerCategory::Shipping);
+
+        let mut orders = Vec::new();
+        pnl.write_orders_csv(&mut orders).unwrap();
+        assert_eq!(
+            String::from_utf8(orders).unwrap(),
+            "order_id,currency,principal,tax,shipping,referral_fee,fba_fee,other_fees,promotions,refunds,reimbursements,other,net_proceeds\n\
+             111,EUR,0.00,0.00,2.00,0.00,0.00,0.00,0.00,0.00,0.00,0.00,2.00\n\
+             111,USD,10.00,0.00,0.00,-1.50,0.00,0.00,0.00,0.00,0.00,0.00,8.50\n"
+        );
+
+        let mut skus = Vec::new();
+        pnl.write_skus_csv(&mut skus).unwrap();
+        let skus = String::from_utf8(skus).unwrap();
+        assert!(skus.starts_with("sku,currency,"));
+        assert_eq!(skus.lines().count(), 2);
+    }
+}
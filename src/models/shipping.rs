@@ -0,0 +1,788 @@
+//! The v2 Amazon Shipping API, for rates, labels and tracking from Amazon Shipping and its
+//! partner carriers.
+use std::cmp::Ordering;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use crate::error_handling::Errors;
+use crate::general::{check_response, parse_payload, Client};
+
+pub struct Shipping;
+
+const BASE: &str = "/shipping/v2";
+
+/// The Amazon Shipping business to act as, sent as `x-amzn-shipping-business-id`.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+pub enum ShippingBusinessId {
+    AmazonShipping_US,
+    AmazonShipping_IN,
+    AmazonShipping_UK,
+    AmazonShipping_UAE,
+    AmazonShipping_SA,
+    AmazonShipping_EG,
+    AmazonShipping_IT,
+    AmazonShipping_ES,
+    AmazonShipping_FR,
+    AmazonShipping_JP,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Address {
+    pub name: String,
+    pub address_line1: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_line2: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_line3: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub company_name: Option<String>,
+    #[serde(default)]
+    pub state_or_region: String,
+    #[serde(default)]
+    pub city: String,
+    #[serde(default)]
+    pub country_code: String,
+    #[serde(default)]
+    pub postal_code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WeightUnit {
+    Gram,
+    Kilogram,
+    Ounce,
+    Pound,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Weight {
+    pub unit: WeightUnit,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DimensionUnit {
+    Inch,
+    Centimeter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Dimensions {
+    pub length: f64,
+    pub width: f64,
+    pub height: f64,
+    pub unit: DimensionUnit,
+}
+
+/// An amount of money, e.g. `{ "value": 5.99, "unit": "USD" }`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Currency {
+    pub value: f64,
+    /// The ISO 4217 currency code.
+    pub unit: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+    pub quantity: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_value: Option<Currency>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The order item ID, for Amazon orders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_identifier: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<Weight>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_hazmat: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub serial_numbers: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Package {
+    pub dimensions: Dimensions,
+    pub weight: Weight,
+    pub insured_value: Currency,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_hazmat: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seller_display_name: Option<String>,
+    /// The seller's identifier of the package, unique within the shipment.
+    pub package_client_reference_id: String,
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ChannelType {
+    Amazon,
+    External,
+}
+
+/// Where the shipment comes from: an Amazon order or another channel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelDetails {
+    pub channel_type: ChannelType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amazon_order_details: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amazon_shipment_details: Option<Value>,
+}
+impl ChannelDetails {
+    pub fn amazon_order(order_id: &str) -> Self {
+        ChannelDetails { channel_type: ChannelType::Amazon, amazon_order_details: Some(json!({ "orderId": order_id })), amazon_shipment_details: None }
+    }
+    pub fn external() -> Self {
+        ChannelDetails { channel_type: ChannelType::External, amazon_order_details: None, amazon_shipment_details: None }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRatesRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ship_to: Option<Address>,
+    pub ship_from: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_to: Option<Address>,
+    /// When the shipment will be handed to the carrier, in ISO 8601 format.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ship_date: Option<String>,
+    pub packages: Vec<Package>,
+    pub channel_details: ChannelDetails,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_added_services: Option<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tax_details: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shipment_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    pub start: Option<String>,
+    pub end: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Promise {
+    pub delivery_window: Option<TimeWindow>,
+    pub pickup_window: Option<TimeWindow>,
+}
+impl Promise {
+    /// The end of the promised delivery window.
+    pub fn latest_delivery(&self) -> Option<DateTime<Utc>> {
+        let end = self.delivery_window.as_ref()?.end.as_deref()?;
+        DateTime::parse_from_rfc3339(end).ok().map(|t| t.with_timezone(&Utc))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+pub enum DocumentFormat {
+    PDF,
+    PNG,
+    ZPL,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DocumentSize {
+    pub width: f64,
+    pub length: f64,
+    /// `INCH` or `CENTIMETER`.
+    pub unit: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SupportedDocumentSpecification {
+    pub format: Option<DocumentFormat>,
+    pub size: DocumentSize,
+    pub print_options: Vec<Value>,
+}
+
+/// A carrier service offered for a shipment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Rate {
+    pub rate_id: String,
+    pub carrier_id: String,
+    pub carrier_name: String,
+    pub billed_weight: Option<Weight>,
+    pub total_charge: Currency,
+    pub service_id: String,
+    pub service_name: String,
+    pub promise: Promise,
+    pub supported_document_specifications: Vec<SupportedDocumentSpecification>,
+    pub available_value_added_service_groups: Vec<Value>,
+    /// Whether `get_additional_inputs` must be answered before purchasing this rate.
+    pub requires_additional_inputs: bool,
+    pub rate_item_list: Vec<Value>,
+    pub payment_type: Option<String>,
+    pub benefits: Option<Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IneligibilityReason {
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct IneligibleRate {
+    pub service_id: String,
+    pub service_name: String,
+    pub carrier_name: String,
+    pub carrier_id: String,
+    pub ineligibility_reasons: Vec<IneligibilityReason>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GetRatesResult {
+    /// The token to purchase one of the rates with, valid for 10 minutes.
+    pub request_token: String,
+    pub rates: Vec<Rate>,
+    pub ineligible_rates: Vec<IneligibleRate>,
+}
+
+/// The label and other documents to return with a purchase.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestedDocumentSpecification {
+    pub format: DocumentFormat,
+    pub size: DocumentSize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dpi: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_layout: Option<String>,
+    pub need_file_joining: bool,
+    /// e.g. `LABEL`, `RECEIPT`, `CUSTOM_FORM`, `PACKSLIP`.
+    pub requested_document_types: Vec<String>,
+}
+impl RequestedDocumentSpecification {
+    /// A 4x6 inch label.
+    pub fn label(format: DocumentFormat) -> Self {
+        RequestedDocumentSpecification {
+            format,
+            size: DocumentSize { width: 4.0, length: 6.0, unit: "INCH".to_string() },
+            dpi: None,
+            page_layout: None,
+            need_file_joining: false,
+            requested_document_types: vec!["LABEL".to_string()],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseShipmentRequest {
+    pub request_token: String,
+    pub rate_id: String,
+    pub requested_document_specification: RequestedDocumentSpecification,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requested_value_added_services: Vec<Value>,
+    /// The answers to `get_additional_inputs`, by field name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_inputs: Option<Map<String, Value>>,
+}
+
+/// A document of a package. `contents` is base64-encoded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PackageDocument {
+    #[serde(rename = "type")]
+    pub document_type: String,
+    pub format: Option<DocumentFormat>,
+    pub contents: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PackageDocumentDetail {
+    pub package_client_reference_id: String,
+    pub package_documents: Vec<PackageDocument>,
+    pub tracking_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PurchaseShipmentResult {
+    pub shipment_id: String,
+    pub package_document_details: Vec<PackageDocumentDetail>,
+    pub promise: Promise,
+    pub benefits: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OneClickShipmentRequest {
+    pub ship_to: Address,
+    pub ship_from: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_to: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ship_date: Option<String>,
+    pub packages: Vec<Package>,
+    pub channel_details: ChannelDetails,
+    pub label_specifications: RequestedDocumentSpecification,
+    /// The services to choose from, by service ID.
+    pub service_selection: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_added_services_details: Option<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tax_details: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NamedId {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OneClickShipmentResult {
+    pub shipment_id: String,
+    pub package_document_details: Vec<PackageDocumentDetail>,
+    pub promise: Promise,
+    pub carrier: NamedId,
+    pub service: NamedId,
+    pub total_charge: Currency,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TrackingEvent {
+    /// e.g. `PickupDone`, `OutForDelivery`, `Delivered`.
+    pub event_code: String,
+    pub location: Option<Value>,
+    pub event_time: String,
+    pub shipment_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TrackingSummary {
+    /// e.g. `PreTransit`, `InTransit`, `Delivered`, `Lost`, `OutForDelivery`, `Rejected`, `Undeliverable`.
+    pub status: Option<String>,
+    pub tracking_detail_codes: Option<Value>,
+    pub proof_of_delivery: Option<Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GetTrackingResult {
+    pub tracking_id: String,
+    pub alternate_leg_tracking_id: Option<String>,
+    pub event_history: Vec<TrackingEvent>,
+    pub promised_delivery_date: Option<String>,
+    pub summary: TrackingSummary,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GetShipmentDocumentsResult {
+    pub shipment_id: String,
+    pub package_document_detail: PackageDocumentDetail,
+    pub benefits: Option<Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ActiveAccount {
+    pub account_id: String,
+    pub carrier_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GetCarrierAccountsResult {
+    pub active_accounts: Vec<ActiveAccount>,
+}
+
+/// An attribute of a carrier account, e.g. the account number.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountAttribute {
+    pub attribute_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub property_group: Option<String>,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LinkCarrierAccountResult {
+    /// e.g. `SUCCESS`, `PENDING`, `REJECTED`.
+    pub registration_status: String,
+    pub account_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UnlinkCarrierAccountResult {
+    pub is_unlinked: bool,
+}
+
+/// What to do with a shipment after a delivery attempt failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NdrAction {
+    Reschedule,
+    Reattempt,
+    /// Return to origin.
+    Rto,
+}
+
+/// Which rate `choose_rate` prefers among the ones that arrive in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RatePreference {
+    /// The lowest total charge; ties go to the earliest delivery.
+    Cheapest,
+    /// The earliest promised delivery; ties go to the lowest total charge.
+    Fastest,
+}
+
+/// Chooses a rate that is promised to be delivered by `deliver_by`.
+///
+/// Without `deliver_by` every rate qualifies. With it, rates without a promised delivery window
+/// never qualify. Returns `None` when no rate qualifies.
+pub fn choose_rate(rates: &[Rate], deliver_by: Option<DateTime<Utc>>, preference: RatePreference) -> Option<&Rate> {
+    let by_charge = |a: &Rate, b: &Rate| a.total_charge.value.partial_cmp(&b.total_charge.value).unwrap_or(Ordering::Equal);
+    // Rates without a promise sort last when comparing delivery.
+    let by_delivery = |a: &Rate, b: &Rate| match (a.promise.latest_delivery(), b.promise.latest_delivery()) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+    rates
+        .iter()
+        .filter(|r| match deliver_by {
+            Some(by) => r.promise.latest_delivery().is_some_and(|t| t <= by),
+            None => true,
+        })
+        .min_by(|a, b| match preference {
+            RatePreference::Cheapest => by_charge(a, b).then_with(|| by_delivery(a, b)),
+            RatePreference::Fastest => by_delivery(a, b).then_with(|| by_charge(a, b)),
+        })
+}
+
+impl Shipping {
+    async fn send(
+        client: &mut Client,
+        endpoint: &str,
+        method: Method,
+        params: Option<Vec<(String, String)>>,
+        business: Option<ShippingBusinessId>,
+        body: Option<String>,
+    ) -> Result<reqwest::Response, Errors> {
+        let business = business.map(|b| b.to_string());
+        let headers: Vec<(&str, &str)> = business.iter().map(|b| ("x-amzn-shipping-business-id", b.as_str())).collect();
+        client.make_request_w_headers(endpoint, method, params, &headers, body).await
+    }
+
+    /// Returns the available shipping service offerings.
+    ///
+    /// Rate (requests per second): 80
+    ///
+    /// Burst: 100
+    ///
+    /// # Parameters
+    /// - `request`: The addresses, packages and channel of the shipment.
+    /// - `business` (optional): The Amazon Shipping business to act as.
+    ///
+    /// # Responses
+    /// - **200 (Success):** Returns the rates, the ineligible services and the `requestToken` to purchase with.
+    pub async fn get_rates(client: &mut Client, request: &GetRatesRequest, business: Option<ShippingBusinessId>) -> Result<GetRatesResult, Errors> {
+        let endpoint = format!("{}/shipments/rates", BASE);
+
+        parse_payload(Self::send(client, &endpoint, Method::POST, None, business, Some(serde_json::to_string(request)?)).await?).await
+    }
+
+    /// Purchases a shipping service and returns the label and other requested documents.
+    ///
+    /// Rate (requests per second): 80
+    ///
+    /// Burst: 100
+    pub async fn purchase_shipment(
+        client: &mut Client,
+        request: &PurchaseShipmentRequest,
+        business: Option<ShippingBusinessId>,
+    ) -> Result<PurchaseShipmentResult, Errors> {
+        let endpoint = format!("{}/shipments", BASE);
+
+        parse_payload(Self::send(client, &endpoint, Method::POST, None, business, Some(serde_json::to_string(request)?)).await?).await
+    }
+
+    /// Purchases a shipping service identifier and returns the label in one call, without
+    /// getting rates first.
+    ///
+    /// Rate (requests per second): 80
+    ///
+    /// Burst: 100
+    pub async fn one_click_shipment(
+        client: &mut Client,
+        request: &OneClickShipmentRequest,
+        business: Option<ShippingBusinessId>,
+    ) -> Result<OneClickShipmentResult, Errors> {
+        let endpoint = format!("{}/oneClickShipment", BASE);
+
+        parse_payload(Self::send(client, &endpoint, Method::POST, None, business, Some(serde_json::to_string(request)?)).await?).await
+    }
+
+    /// Returns tracking information for a purchased shipment.
+    ///
+    /// Rate (requests per second): 80
+    ///
+    /// Burst: 100
+    ///
+    /// # Parameters
+    /// - `tracking_id`: The carrier's tracking ID.
+    /// - `carrier_id`: The carrier, as returned in the rate.
+    pub async fn get_tracking(client: &mut Client, tracking_id: &str, carrier_id: &str, business: Option<ShippingBusinessId>) -> Result<GetTrackingResult, Errors> {
+        let endpoint = format!("{}/tracking", BASE);
+        let params = vec![("trackingId".to_string(), tracking_id.to_string()), ("carrierId".to_string(), carrier_id.to_string())];
+
+        parse_payload(Self::send(client, &endpoint, Method::GET, Some(params), business, None).await?).await
+    }
+
+    /// Returns the shipping documents associated with a package in a shipment.
+    ///
+    /// Rate (requests per second): 80
+    ///
+    /// Burst: 100
+    ///
+    /// # Parameters
+    /// - `shipment_id`: The shipment, as returned by a purchase.
+    /// - `package_client_reference_id`: The package of the shipment.
+    /// - `format` (optional): The document format; the purchased format when `None`.
+    /// - `dpi` (optional): The resolution of the document.
+    pub async fn get_shipment_documents(
+        client: &mut Client,
+        shipment_id: &str,
+        package_client_reference_id: &str,
+        format: Option<DocumentFormat>,
+        dpi: Option<u32>,
+        business: Option<ShippingBusinessId>,
+    ) -> Result<GetShipmentDocumentsResult, Errors> {
+        let endpoint = format!("{}/shipments/{}/documents", BASE, shipment_id);
+        let mut params = vec![("packageClientReferenceId".to_string(), package_client_reference_id.to_string())];
+        if let Some(format) = format {
+            params.push(("format".to_string(), format.to_string()));
+        }
+        if let Some(dpi) = dpi {
+            params.push(("dpi".to_string(), dpi.to_string()));
+        }
+
+        parse_payload(Self::send(client, &endpoint, Method::GET, Some(params), business, None).await?).await
+    }
+
+    /// Cancels a purchased shipment. Returns an empty object if the shipment is successfully
+    /// cancelled.
+    ///
+    /// Rate (requests per second): 80
+    ///
+    /// Burst: 100
+    pub async fn cancel_shipment(client: &mut Client, shipment_id: &str, business: Option<ShippingBusinessId>) -> Result<(), Errors> {
+        let endpoint = format!("{}/shipments/{}/cancel", BASE, shipment_id);
+
+        check_response(Self::send(client, &endpoint, Method::PUT, None, business, None).await?).await
+    }
+
+    /// Returns the JSON schema to use for providing additional inputs when needed to purchase
+    /// a shipping offering.
+    ///
+    /// Rate (requests per second): 80
+    ///
+    /// Burst: 100
+    ///
+    /// # Parameters
+    /// - `request_token`: The token returned by `get_rates`.
+    /// - `rate_id`: The rate that `requires_additional_inputs`.
+    pub async fn get_additional_inputs(client: &mut Client, request_token: &str, rate_id: &str, business: Option<ShippingBusinessId>) -> Result<Value, Errors> {
+        let endpoint = format!("{}/shipments/additionalInputs/schema", BASE);
+        let params = vec![("requestToken".to_string(), request_token.to_string()), ("rateId".to_string(), rate_id.to_string())];
+
+        parse_payload(Self::send(client, &endpoint, Method::GET, Some(params), business, None).await?).await
+    }
+
+    /// Returns the form inputs that a seller must provide to link a carrier account.
+    ///
+    /// Rate (requests per second): 80
+    ///
+    /// Burst: 100
+    pub async fn get_carrier_account_form_inputs(client: &mut Client, business: Option<ShippingBusinessId>) -> Result<Value, Errors> {
+        let endpoint = format!("{}/carrierAccountFormInputs", BASE);
+
+        parse_payload(Self::send(client, &endpoint, Method::GET, None, business, None).await?).await
+    }
+
+    /// Returns the carrier accounts linked to the seller.
+    ///
+    /// Rate (requests per second): 80
+    ///
+    /// Burst: 100
+    pub async fn get_carrier_accounts(client: &mut Client, business: Option<ShippingBusinessId>) -> Result<GetCarrierAccountsResult, Errors> {
+        let endpoint = format!("{}/carrierAccounts", BASE);
+
+        parse_payload(Self::send(client, &endpoint, Method::PUT, None, business, Some(json!({}).to_string())).await?).await
+    }
+
+    /// Links a carrier account to the seller, so its rates are returned by `get_rates`.
+    ///
+    /// Rate (requests per second): 80
+    ///
+    /// Burst: 100
+    ///
+    /// # Parameters
+    /// - `carrier_id`: The carrier to link.
+    /// - `carrier_account_type`: The account type, from `get_carrier_account_form_inputs`.
+    /// - `attributes`: The account attributes, e.g. the account number.
+    pub async fn link_carrier_account(
+        client: &mut Client,
+        carrier_id: &str,
+        carrier_account_type: &str,
+        attributes: &[AccountAttribute],
+        business: Option<ShippingBusinessId>,
+    ) -> Result<LinkCarrierAccountResult, Errors> {
+        let endpoint = format!("{}/carrierAccounts/{}", BASE, carrier_id);
+        let body = json!({ "carrierAccountType": carrier_account_type, "carrierAccountAttributes": attributes }).to_string();
+
+        parse_payload(Self::send(client, &endpoint, Method::PUT, None, business, Some(body)).await?).await
+    }
+
+    /// Unlinks a carrier account from the seller.
+    ///
+    /// Rate (requests per second): 80
+    ///
+    /// Burst: 100
+    pub async fn unlink_carrier_account(
+        client: &mut Client,
+        carrier_id: &str,
+        account_id: &str,
+        business: Option<ShippingBusinessId>,
+    ) -> Result<UnlinkCarrierAccountResult, Errors> {
+        let endpoint = format!("{}/carrierAccounts/{}/unlink", BASE, carrier_id);
+        let body = json!({ "accountId": account_id }).to_string();
+
+        parse_payload(Self::send(client, &endpoint, Method::PUT, None, business, Some(body)).await?).await
+    }
+
+    /// Submits the action to take after a non-delivery report (NDR), i.e. a failed delivery
+    /// attempt.
+    ///
+    /// Rate (requests per second): 80
+    ///
+    /// Burst: 100
+    ///
+    /// # Parameters
+    /// - `tracking_id`: The carrier's tracking ID.
+    /// - `action`: What to do with the shipment.
+    /// - `reschedule_date` (optional): The new delivery date, required for `Reschedule`.
+    /// - `additional_address_notes` (optional): Notes for the next attempt, e.g. a landmark.
+    ///
+    /// # Responses
+    /// - **204 (No Content):** The feedback was accepted.
+    pub async fn submit_ndr_feedback(
+        client: &mut Client,
+        tracking_id: &str,
+        action: NdrAction,
+        reschedule_date: Option<&str>,
+        additional_address_notes: Option<&str>,
+        business: Option<ShippingBusinessId>,
+    ) -> Result<(), Errors> {
+        let endpoint = format!("{}/ndrFeedback", BASE);
+        let mut body = json!({ "trackingId": tracking_id, "ndrAction": action });
+        let mut data = Map::new();
+        if let Some(date) = reschedule_date {
+            data.insert("rescheduleDate".to_string(), json!(date));
+        }
+        if let Some(notes) = additional_address_notes {
+            data.insert("additionalAddressNotes".to_string(), json!(notes));
+        }
+        if !data.is_empty() {
+            body["ndrRequestData"] = Value::Object(data);
+        }
+
+        check_response(Self::send(client, &endpoint, Method::POST, None, business, Some(body.to_string())).await?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(id: &str, charge: f64, delivered_by: Option<&str>) -> Rate {
+        Rate {
+            rate_id: id.to_string(),
+            total_charge: Currency { value: charge, unit: "USD".to_string() },
+            promise: Promise {
+                delivery_window: delivered_by.map(|end| TimeWindow { start: None, end: Some(end.to_string()) }),
+                pickup_window: None,
+            },
+            ..Default::default()
+        }
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().with_timezone(&Utc)
+    }
+
+    fn chosen(rates: &[Rate], deliver_by: Option<&str>, preference: RatePreference) -> Option<String> {
+        choose_rate(rates, deliver_by.map(at), preference).map(|r| r.rate_id.clone())
+    }
+
+    #[test]
+    fn deadline_filters_out_late_rates() {
+        let rates = [
+            rate("late", 3.0, Some("2026-10-25T00:00:00Z")),
+            rate("on-time", 6.0, Some("2026-10-22T00:00:00Z")),
+            rate("fast", 9.0, Some("2026-10-21T00:00:00Z")),
+        ];
+        assert_eq!(chosen(&rates, Some("2026-10-22T00:00:00Z"), RatePreference::Cheapest).as_deref(), Some("on-time"));
+        assert_eq!(chosen(&rates, Some("2026-10-22T00:00:00Z"), RatePreference::Fastest).as_deref(), Some("fast"));
+        assert_eq!(chosen(&rates, Some("2026-10-20T00:00:00Z"), RatePreference::Cheapest), None);
+    }
+
+    #[test]
+    fn rates_without_a_promise_only_qualify_without_a_deadline() {
+        let rates = [rate("no-promise", 1.0, None), rate("promised", 5.0, Some("2026-10-22T00:00:00Z"))];
+        assert_eq!(chosen(&rates, Some("2026-10-23T00:00:00Z"), RatePreference::Cheapest).as_deref(), Some("promised"));
+        assert_eq!(chosen(&rates, None, RatePreference::Cheapest).as_deref(), Some("no-promise"));
+        // Without a promise a rate sorts last by delivery.
+        assert_eq!(chosen(&rates, None, RatePreference::Fastest).as_deref(), Some("promised"));
+        assert_eq!(chosen(&rates[..1], Some("2026-10-23T00:00:00Z"), RatePreference::Fastest), None);
+    }
+
+    #[test]
+    fn ties_are_broken_by_the_other_criterion() {
+        let rates = [
+            rate("cheap-late", 4.0, Some("2026-10-22T00:00:00Z")),
+            rate("cheap-early", 4.0, Some("2026-10-21T00:00:00Z")),
+            rate("dear-early", 8.0, Some("2026-10-21T00:00:00Z")),
+        ];
+        assert_eq!(chosen(&rates, None, RatePreference::Cheapest).as_deref(), Some("cheap-early"));
+        assert_eq!(chosen(&rates, None, RatePreference::Fastest).as_deref(), Some("cheap-early"));
+    }
+}
@@ -0,0 +1,82 @@
+//! Paging through SP-API list operations that return a `NextToken`.
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::Instant;
+use crate::error_handling::Errors;
+use crate::general::Client;
+
+/// One page of a list operation.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// The token for the next page, `None` on the last page.
+    pub next_token: Option<String>,
+}
+
+/// A list operation that can be fetched one page at a time.
+pub trait PagedRequest {
+    type Item;
+
+    /// Fetches the page for `next_token`, or the first page when it is `None`.
+    fn fetch_page(&mut self, client: &mut Client, next_token: Option<String>) -> impl Future<Output = Result<Page<Self::Item>, Errors>>;
+}
+
+/// Follows the `NextToken` of a `PagedRequest` until the last page.
+///
+/// Pages are fetched lazily with `next_page`, so a caller can stop early. Consecutive requests
+/// are spaced by the operation's rate limit, given as `min_interval`.
+pub struct Paginator<R: PagedRequest> {
+    request: R,
+    next_token: Option<String>,
+    finished: bool,
+    min_interval: Duration,
+    last_request: Option<Instant>,
+}
+impl<R: PagedRequest> Paginator<R> {
+    pub fn new(request: R, min_interval: Duration) -> Self {
+        Paginator { request, next_token: None, finished: false, min_interval, last_request: None }
+    }
+    /// Resumes paging from a token returned by an earlier run.
+    pub fn starting_at(mut self, next_token: Option<String>) -> Self {
+        self.next_token = next_token;
+        self
+    }
+    /// The token of the next page to fetch, e.g. to persist and resume later.
+    pub fn next_token(&self) -> Option<&str> {
+        self.next_token.as_deref()
+    }
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Fetches the next page, or returns `None` once the last page has been returned.
+    ///
+    /// After an error the same page is requested again on the next call.
+    pub async fn next_page(&mut self, client: &mut Client) -> Option<Result<Vec<R::Item>, Errors>> {
+        if self.finished {
+            return None;
+        }
+        if let Some(last) = self.last_request {
+            tokio::time::sleep_until(last + self.min_interval).await;
+        }
+        self.last_request = Some(Instant::now());
+
+        match self.request.fetch_page(client, self.next_token.clone()).await {
+            Ok(page) => {
+                self.next_token = page.next_token.filter(|t| !t.is_empty());
+                self.finished = self.next_token.is_none();
+                Some(Ok(page.items))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    /// Fetches every remaining page and returns all items.
+    pub async fn collect_all(mut self, client: &mut Client) -> Result<Vec<R::Item>, Errors> {
+        let mut items = Vec::new();
+        while let Some(page) = self.next_page(client).await {
+            items.extend(page?);
+        }
+        Ok(items)
+    }
+}
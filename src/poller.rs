@@ -0,0 +1,224 @@
+//! Waiting on SP-API operations that finish asynchronously on Amazon's side.
+//!
+//! Reports, feeds, Data Kiosk queries and Fulfillment Inbound operations are all created with
+//! one call and then have to be polled until Amazon is done with them. Each of those operations
+//! implements [`Pollable`], and the calls that start them return an [`OperationHandle`] that can
+//! be awaited with [`OperationHandle::wait`].
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use crate::error_handling::Errors;
+use crate::general::Client;
+
+/// The state of an operation after a single status check.
+#[derive(Debug)]
+pub enum PollStatus<T> {
+    /// The operation is still running. Holds the status reported by Amazon, e.g. `IN_QUEUE`.
+    Pending(String),
+    /// The operation finished successfully.
+    Done(T),
+}
+
+/// An operation whose status can be checked repeatedly until it finishes.
+///
+/// Implementations should return `Err` when Amazon reports that the operation failed or was
+/// cancelled, so the poller stops instead of waiting for the timeout.
+pub trait Pollable {
+    type Output;
+
+    /// Checks the status of the operation once.
+    fn poll(&mut self, client: &mut Client) -> impl Future<Output = Result<PollStatus<Self::Output>, Errors>>;
+}
+
+/// Exponential backoff between status checks.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+    pub multiplier: f64,
+}
+impl Backoff {
+    pub fn new(initial: Duration, max: Duration, multiplier: f64) -> Self {
+        Backoff { initial, max, multiplier }
+    }
+    /// Waits the same amount of time between every check.
+    pub fn fixed(interval: Duration) -> Self {
+        Backoff { initial: interval, max: interval, multiplier: 1.0 }
+    }
+    /// The delay before the check that follows `attempt` pending checks (starting at 0).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        // Computed in seconds and clamped before converting, since `initial * multiplier^attempt`
+        // overflows a `Duration` (and eventually `f64`) for large attempts.
+        let factor = self.multiplier.max(1.0).powi(attempt.min(i32::MAX as u32) as i32);
+        let secs = self.initial.as_secs_f64() * factor;
+        if secs.is_finite() && secs < self.max.as_secs_f64() {
+            Duration::from_secs_f64(secs)
+        } else {
+            self.max
+        }
+    }
+}
+impl Default for Backoff {
+    /// Starts at 2 seconds and doubles up to one minute, which stays within the rate limits of
+    /// the status operations (getQuery, getFeed, getReport, getInboundOperationStatus).
+    fn default() -> Self {
+        Backoff::new(Duration::from_secs(2), Duration::from_secs(60), 2.0)
+    }
+}
+
+/// A cloneable flag used to stop a running [`OperationHandle::wait`] from another task.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    notify: Arc<Notify>,
+}
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+    /// Resolves once [`CancellationToken::cancel`] has been called.
+    pub async fn cancelled(&self) {
+        let notified = self.notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+/// Passed to the progress callback after every check that found the operation still running.
+#[derive(Debug, Clone)]
+pub struct PollProgress {
+    /// Number of status checks made so far.
+    pub attempt: u32,
+    /// Time since [`OperationHandle::wait`] was called.
+    pub elapsed: Duration,
+    /// The status reported by Amazon on the last check.
+    pub status: String,
+    /// How long the poller is going to sleep before the next check.
+    pub next_check_in: Duration,
+}
+
+type ProgressCallback = Box<dyn FnMut(&PollProgress) + Send>;
+
+/// A long-running SP-API operation that can be awaited to completion.
+pub struct OperationHandle<P: Pollable> {
+    operation: P,
+    backoff: Backoff,
+    timeout: Option<Duration>,
+    cancellation: Option<CancellationToken>,
+    on_progress: Option<ProgressCallback>,
+}
+impl<P: Pollable> OperationHandle<P> {
+    pub fn new(operation: P) -> Self {
+        OperationHandle {
+            operation,
+            backoff: Backoff::default(),
+            timeout: None,
+            cancellation: None,
+            on_progress: None,
+        }
+    }
+    /// The underlying operation, e.g. to read its identifier.
+    pub fn operation(&self) -> &P {
+        &self.operation
+    }
+    pub fn into_operation(self) -> P {
+        self.operation
+    }
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+    /// Gives up with `Errors::Timeout` once this much time has passed. No timeout by default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// Stops waiting with `Errors::Cancelled` when the token is cancelled. This only stops the
+    /// polling, the operation itself keeps running on Amazon's side.
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(&PollProgress) + Send + 'static,
+    {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Polls the operation until it finishes, fails, times out or is cancelled.
+    pub async fn wait(mut self, client: &mut Client) -> Result<P::Output, Errors> {
+        let started = Instant::now();
+        let mut attempt = 0;
+        loop {
+            if self.cancellation.as_ref().is_some_and(|c| c.is_cancelled()) {
+                return Err(Errors::Cancelled);
+            }
+            let status = match self.operation.poll(client).await? {
+                PollStatus::Done(output) => return Ok(output),
+                PollStatus::Pending(status) => status,
+            };
+
+            let elapsed = started.elapsed();
+            let mut delay = self.backoff.delay(attempt);
+            attempt += 1;
+            if let Some(timeout) = self.timeout {
+                if elapsed >= timeout {
+                    return Err(Errors::Timeout);
+                }
+                delay = delay.min(timeout - elapsed);
+            }
+            if let Some(callback) = self.on_progress.as_mut() {
+                callback(&PollProgress { attempt, elapsed, status, next_check_in: delay });
+            }
+
+            match &self.cancellation {
+                Some(token) => {
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => {}
+                        _ = token.cancelled() => return Err(Errors::Cancelled),
+                    }
+                }
+                None => tokio::time::sleep(delay).await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_up_to_max() {
+        let backoff = Backoff::default();
+        assert_eq!(backoff.delay(0), Duration::from_secs(2));
+        assert_eq!(backoff.delay(1), Duration::from_secs(4));
+        assert_eq!(backoff.delay(4), Duration::from_secs(32));
+        assert_eq!(backoff.delay(5), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn backoff_does_not_panic_for_large_attempts() {
+        let backoff = Backoff::default();
+        for attempt in [63, 64, 1_000, 1_100, u32::MAX] {
+            assert_eq!(backoff.delay(attempt), Duration::from_secs(60));
+        }
+        let fixed = Backoff::fixed(Duration::from_secs(5));
+        assert_eq!(fixed.delay(u32::MAX), Duration::from_secs(5));
+    }
+}